@@ -3,7 +3,7 @@ use clap::{Parser, Subcommand};
 
 use crate::cli::query::args::Query;
 
-use super::engine::args::Args;
+use super::engine::args::{Args, JobsArgs};
 
 #[derive(Parser, Debug)]
 #[command(name = "eth-indexer")]
@@ -17,5 +17,7 @@ pub struct Cli {
 pub enum Command {
     /// Start eth indexer engine
     Engine(Args),
+    /// Start several eth indexer engines, one per configured job
+    Jobs(JobsArgs),
     Select(Query),
 }
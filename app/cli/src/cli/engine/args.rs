@@ -1,12 +1,13 @@
 use clap::Parser;
 use clap::{arg, command};
 
-use crate::cli::read::CliEventType;
+use crate::cli::read::{CliBlockTip, CliEventType};
 
 #[derive(Parser, Debug)]
 #[command(about = "Start the ETH indexer", long_about = None)]
 pub struct Args {
-    /// Node Provider connection string
+    /// Node Provider connection string (`http(s)://`, `ws(s)://` or `ipc://` followed by the socket path),
+    /// several ones (comma-separated) being failed over in order
     #[arg(short, long)]
     pub rpc_url: String,
 
@@ -18,14 +19,75 @@ pub struct Args {
     #[arg(short, long)]
     pub signer_pk: String,
 
-    /// Addresses to watch (comma-separated)
+    /// Other providers (comma-separated) whose logs must agree with `--rpc-url` ones during backfill
+    #[arg(long)]
+    pub quorum_rpc_urls: Option<String>,
+
+    /// Addresses to watch (comma-separated), every contract on chain if omitted
     #[arg(short, long)]
-    pub addresses: String,
+    pub addresses: Option<String>,
 
     /// Event type to watch
     #[arg(short, long, value_enum)]
     pub event: CliEventType,
 
+    /// Indexed topic1 values to watch, addresses or words (comma-separated, e.g. transfers `from`)
+    #[arg(long)]
+    pub topic1: Option<String>,
+
+    /// Indexed topic2 values to watch, addresses or words (comma-separated, e.g. transfers `to`)
+    #[arg(long)]
+    pub topic2: Option<String>,
+
+    /// Indexed topic3 values to watch, addresses or words (comma-separated)
+    #[arg(long)]
+    pub topic3: Option<String>,
+
+    /// Factory contract whose created contracts are watched along with `--addresses`
+    #[arg(long, requires = "factory_event")]
+    pub factory: Option<String>,
+
+    /// Creation event of the factory, e.g. "PairCreated(address indexed, address indexed, address, uint256)"
+    #[arg(long, requires = "factory")]
+    pub factory_event: Option<String>,
+
+    /// Position of the created contract address among the creation event parameters
+    #[arg(long, default_value_t = 0)]
+    pub factory_arg: usize,
+
+    /// ABI JSON file (or contract artifact) declaring the `abi` events
+    #[arg(long)]
+    pub abi: Option<String>,
+
+    /// ABI event names to watch (comma-separated)
+    #[arg(long)]
+    pub abi_events: Option<String>,
+
+    /// Maintain running holder balances along with `transfer` events
+    #[arg(long, default_value_t = false)]
+    pub track_balances: bool,
+
+    /// Fetch name, symbol and decimals of every newly seen contract
+    #[arg(long, default_value_t = false)]
+    pub fetch_token_metadata: bool,
+
+    /// Fetch the receipt (sender, gas used, status) of every transaction emitting indexed logs
+    #[arg(long, default_value_t = false)]
+    pub fetch_transactions: bool,
+
+    /// Archive the raw logs (address, topics, data) along with decoded rows
+    #[arg(long, default_value_t = false)]
+    pub archive_raw_logs: bool,
+
+    /// Re-derive decoded rows from `--from-block` using the raw log archive, without the node
+    #[arg(long, default_value_t = false)]
+    pub replay: bool,
+
+    /// Listen for control commands (`add <address> [from_block]`, `remove <address>`, `list`)
+    /// on the given TCP address, e.g. "127.0.0.1:9000"
+    #[arg(long)]
+    pub control_addr: Option<String>,
+
     /// From block number to watch
     #[arg(long)]
     pub from_block: Option<u64>,
@@ -38,7 +100,43 @@ pub struct Args {
     #[arg(long)]
     pub backfill_checkpoint_interval: Option<u64>,
 
+    /// Number of blocks to wait before forwarding outcomes (0 = instant)
+    #[arg(long, default_value_t = 0)]
+    pub confirmations: u64,
+
+    /// Block tag treated as the chain tip
+    #[arg(long, value_enum, default_value_t = CliBlockTip::Latest)]
+    pub tip: CliBlockTip,
+
     /// Poll interval in milliseconds
     #[arg(long, default_value_t = 500)]
     pub poll_interval: u64,
+
+    /// Retries of RPC requests failing with transient errors or rate limits, before the engine stops
+    #[arg(long, default_value_t = 5)]
+    pub max_retries: u32,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Start several ETH indexing jobs", long_about = None)]
+pub struct JobsArgs {
+    /// Node Provider connection string, of the jobs not given their own `rpc_url`
+    #[arg(short, long)]
+    pub rpc_url: String,
+
+    /// SQLite connection string
+    #[arg(short, long)]
+    pub db_url: String,
+
+    /// User signing private key
+    #[arg(short, long)]
+    pub signer_pk: String,
+
+    /// Jobs configuration JSON file, each job having the `engine` options and its own checkpoints
+    #[arg(short, long)]
+    pub config: String,
+
+    /// Retries of RPC requests failing with transient errors or rate limits, before the engine stops
+    #[arg(long, default_value_t = 5)]
+    pub max_retries: u32,
 }
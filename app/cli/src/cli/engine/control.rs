@@ -0,0 +1,73 @@
+use alloy::primitives::{Address, BlockNumber};
+use engine::control::ControlHandle;
+use eyre::{Result, eyre};
+use std::str::FromStr;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+// Serves control commands, one per line, answering `ok ...` or `error ...`:
+// * `add <address> [from_block]`: watch an address, backfilled from the given block
+// * `remove <address>`: stop watching an address
+// * `list`: list the watched addresses
+pub async fn spawn(control_addr: &str, control: ControlHandle) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(control_addr).await?;
+    tracing::info!("Control listening on: {:?}", listener.local_addr()?);
+
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    tracing::info!("Control connection from: {peer:?}");
+                    let control = control.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = serve(stream, control).await {
+                            tracing::error!("Control connection failed: {e:?}");
+                        }
+                    });
+                }
+                Err(e) => tracing::error!("Control failed to accept connection: {e:?}"),
+            }
+        }
+    }))
+}
+
+async fn serve(stream: TcpStream, control: ControlHandle) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match execute(&line, &control).await {
+            Ok(response) => format!("ok {response}\n"),
+            Err(e) => format!("error {e}\n"),
+        };
+        writer.write_all(response.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+async fn execute(line: &str, control: &ControlHandle) -> Result<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["add", address, rest @ ..] if rest.len() <= 1 => {
+            let address = Address::from_str(address)?;
+            let from_block = rest.first().map(|block| BlockNumber::from_str(block)).transpose()?;
+            control.add_address(address, from_block).await?;
+            Ok(format!("added {address}"))
+        }
+        ["remove", address] => {
+            let address = Address::from_str(address)?;
+            control.remove_address(address).await?;
+            Ok(format!("removed {address}"))
+        }
+        ["list"] => {
+            let addresses = control.list_addresses().await?;
+            Ok(addresses.iter().map(Address::to_string).collect::<Vec<_>>().join(","))
+        }
+        _ => Err(eyre!("unknown command: {line:?}")),
+    }
+}
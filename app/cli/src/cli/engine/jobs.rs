@@ -0,0 +1,156 @@
+use crate::cli::engine::args::Args as EngineArgs;
+use crate::cli::engine::run::{Job, RunOptions};
+use crate::cli::read::{self, CliBlockTip, CliEventType};
+use engine::args::Args;
+use eyre::{Result, eyre};
+use serde::Deserialize;
+use std::{collections::HashSet, time::Duration};
+
+// Jobs configuration file, e.g.
+// { "jobs": [{ "name": "usdc", "addresses": "0x...", "event": "transfer", "from_block": 6082465 }] }
+#[derive(Debug, Deserialize)]
+pub struct JobsConfig {
+    pub jobs: Vec<JobConfig>,
+}
+
+// Same options as the `engine` command, the node and database being shared by every job
+#[derive(Debug, Deserialize)]
+pub struct JobConfig {
+    // Unique name, under which the job is checkpointed
+    pub name: String,
+    // Node of the chain to index, the `--rpc-url` of the command if omitted
+    pub rpc_url: Option<String>,
+    // Other providers whose logs must agree with the node ones (comma-separated)
+    pub quorum_rpc_urls: Option<String>,
+    pub addresses: Option<String>,
+    pub event: CliEventType,
+    pub topic1: Option<String>,
+    pub topic2: Option<String>,
+    pub topic3: Option<String>,
+    pub factory: Option<String>,
+    pub factory_event: Option<String>,
+    #[serde(default)]
+    pub factory_arg: usize,
+    pub abi: Option<String>,
+    pub abi_events: Option<String>,
+    #[serde(default)]
+    pub track_balances: bool,
+    #[serde(default)]
+    pub fetch_token_metadata: bool,
+    #[serde(default)]
+    pub fetch_transactions: bool,
+    #[serde(default)]
+    pub archive_raw_logs: bool,
+    #[serde(default)]
+    pub replay: bool,
+    pub control_addr: Option<String>,
+    pub from_block: Option<u64>,
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval: u64,
+    pub backfill_checkpoint_interval: Option<u64>,
+    #[serde(default)]
+    pub confirmations: u64,
+    #[serde(default = "default_tip")]
+    pub tip: CliBlockTip,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval: u64,
+}
+
+fn default_checkpoint_interval() -> u64 {
+    12
+}
+
+fn default_tip() -> CliBlockTip {
+    CliBlockTip::Latest
+}
+
+fn default_poll_interval() -> u64 {
+    500
+}
+
+// Reads the jobs of a configuration file, rejecting duplicated names
+pub fn read_jobs(config_path: &str, default_rpc_url: &str) -> Result<Vec<Job>> {
+    let json = std::fs::read_to_string(config_path)?;
+    let config: JobsConfig = serde_json::from_str(&json)?;
+
+    let mut names = HashSet::new();
+    if let Some(job) = config.jobs.iter().find(|job| !names.insert(job.name.as_str())) {
+        return Err(eyre!("Job `{}` is declared more than once in {config_path}", job.name));
+    }
+    config.jobs.into_iter().map(|job| job.into_job(default_rpc_url)).collect()
+}
+
+impl JobConfig {
+    // Maps the job options to the engine args and run options
+    pub fn into_job(self, default_rpc_url: &str) -> Result<Job> {
+        let rpc_url = self.rpc_url.unwrap_or_else(|| default_rpc_url.to_string());
+        let args = Args {
+            addresses: read::parse_addresses(self.addresses.as_deref()),
+            event: read::parse_event(&self.event, self.abi.as_deref(), self.abi_events.as_deref())?,
+            topics: read::parse_topics(
+                self.topic1.as_deref(),
+                self.topic2.as_deref(),
+                self.topic3.as_deref(),
+            )?,
+            from_block: self.from_block,
+            poll_interval: Duration::from_millis(self.poll_interval),
+            checkpoint_interval: self.checkpoint_interval,
+            backfill_checkpoint_interval: self.backfill_checkpoint_interval,
+            confirmations: self.confirmations,
+            tip: self.tip.into(),
+        };
+        let options = RunOptions {
+            track_balances: self.track_balances,
+            fetch_token_metadata: self.fetch_token_metadata,
+            fetch_transactions: self.fetch_transactions,
+            archive_raw_logs: self.archive_raw_logs,
+            replay: self.replay,
+            factory: read::parse_factory(
+                self.factory.as_deref(),
+                self.factory_event.as_deref(),
+                self.factory_arg,
+            )?,
+            control_addr: self.control_addr,
+            quorum_rpc_urls: self
+                .quorum_rpc_urls
+                .map(|rpc_urls| {
+                    rpc_urls.split(',').map(|rpc_url| rpc_url.trim().to_string()).collect()
+                })
+                .unwrap_or_default(),
+        };
+        Ok(Job { name: self.name, rpc_url, options, args })
+    }
+}
+
+// The `engine` command runs a single job
+impl From<&EngineArgs> for JobConfig {
+    fn from(args: &EngineArgs) -> Self {
+        JobConfig {
+            name: store::client::DEFAULT_JOB.to_string(),
+            rpc_url: Some(args.rpc_url.clone()),
+            quorum_rpc_urls: args.quorum_rpc_urls.clone(),
+            addresses: args.addresses.clone(),
+            event: args.event.clone(),
+            topic1: args.topic1.clone(),
+            topic2: args.topic2.clone(),
+            topic3: args.topic3.clone(),
+            factory: args.factory.clone(),
+            factory_event: args.factory_event.clone(),
+            factory_arg: args.factory_arg,
+            abi: args.abi.clone(),
+            abi_events: args.abi_events.clone(),
+            track_balances: args.track_balances,
+            fetch_token_metadata: args.fetch_token_metadata,
+            fetch_transactions: args.fetch_transactions,
+            archive_raw_logs: args.archive_raw_logs,
+            replay: args.replay,
+            control_addr: args.control_addr.clone(),
+            from_block: args.from_block,
+            checkpoint_interval: args.checkpoint_interval,
+            backfill_checkpoint_interval: args.backfill_checkpoint_interval,
+            confirmations: args.confirmations,
+            tip: args.tip.clone(),
+            poll_interval: args.poll_interval,
+        }
+    }
+}
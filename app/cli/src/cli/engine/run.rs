@@ -1,38 +1,413 @@
+use crate::cli::engine::control;
 use alloy::{rpc::types::Log, signers::local::PrivateKeySigner, transports::http::reqwest::Url};
-use chain::rpc::NodeClient;
+use chain::{
+    retry::RetryPolicy,
+    rpc::{NodeClient, parse_rpc_urls},
+};
 use engine::{
     args::Args,
     checkpointer::Checkpointer,
     engine::Engine,
-    sink::{handle::Sink, transfer::TransferSink},
-    source::{handle::Source, log::LogSource},
+    replay,
+    sink::{
+        any_transfer::{AnyTransfer, AnyTransferSink},
+        approval::ApprovalSink,
+        balance::BalanceSink,
+        event_log::EventLogSink,
+        handle::{Sink, contract_addresses},
+        nft_transfer::NftTransferSink,
+        raw_log::{Archived, RawLogSink},
+        token::TokenMetadataSink,
+        transaction::TransactionSink,
+        transfer::TransferSink,
+        transfer_batch::TransferBatchSink,
+        transfer_single::TransferSingleSink,
+    },
+    source::{
+        abi::AbiLogSource,
+        factory::{Factory, FactorySource},
+        filter::EventType,
+        handle::{Source, SourceInput},
+        log::LogSource,
+        quorum::QuorumSource,
+        raw_log::RawLogSource,
+        ws_log::WsLogSource,
+    },
+    state::outcome::Outcome,
+};
+use eyre::{Result, eyre};
+use futures_util::future::try_join_all;
+use std::{collections::HashMap, fmt::Debug, str::FromStr, sync::Arc, time::Duration};
+use store::{
+    approval::model::Approval,
+    client::Client,
+    event_log::model::{AbiLog, EventLog},
+    nft_transfer::model::NftTransfer,
+    transfer::model::Transfer,
+    transfer_batch::model::TransferBatch,
+    transfer_single::model::TransferSingle,
 };
-use eyre::Result;
-use std::{str::FromStr, sync::Arc};
-use store::{client::Client, transfer::model::Transfer};
 
-pub async fn start(rpc_url: &str, db_url: &str, signer_pk: &str, engine_args: Args) -> Result<()> {
-    // Start engine
-    let node_client = NodeClient::new(Url::parse(rpc_url)?, PrivateKeySigner::from_str(signer_pk)?);
-    let source: Arc<dyn Source<Item = Log>> =
-        Arc::new(LogSource { node_client: node_client.clone() });
+// Interval at which the endpoints of a node client are checked, when failing over between several
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+// Blocks an endpoint may lag behind the highest endpoint head before being deemed unhealthy
+const MAX_ENDPOINT_LAG: u64 = 5;
 
+// Attempts to fetch a log chunk again when quorum providers disagree on it
+const QUORUM_RETRIES: usize = 3;
+
+// Optional engine features, on top of the event persistence
+pub struct RunOptions {
+    pub track_balances: bool,
+    pub fetch_token_metadata: bool,
+    pub fetch_transactions: bool,
+    pub archive_raw_logs: bool,
+    // Re-derive decoded tables from the raw log archive, instead of watching the node
+    pub replay: bool,
+    // Watch the contracts created by a factory, on top of the given addresses
+    pub factory: Option<Factory>,
+    // Serve control commands changing the watched addresses on this TCP address
+    pub control_addr: Option<String>,
+    // Cross-check the backfilled logs against these providers, every one of them having to agree
+    pub quorum_rpc_urls: Vec<String>,
+}
+
+// An indexing pipeline, checkpointed under its own name
+pub struct Job {
+    pub name: String,
+    // Node(s) of the chain to index, comma-separated endpoints being failed over in order
+    pub rpc_url: String,
+    pub options: RunOptions,
+    pub args: Args,
+}
+
+// Runs the given jobs side by side, sharing one database pool and one node client per chain
+pub async fn start(
+    db_url: &str,
+    signer_pk: &str,
+    retry_policy: RetryPolicy,
+    jobs: Vec<Job>,
+) -> Result<()> {
+    let signer = PrivateKeySigner::from_str(signer_pk)?;
     let client = Client::init(db_url).await?;
-    let checkpoint_store = store::checkpoint::store::Store::new(client.clone());
-    let checkpointer = Checkpointer::new(checkpoint_store);
-    let transfer_store = store::transfer::store::Store::new(client.clone());
-    let sink: Arc<dyn Sink<Item = Transfer>> = Arc::new(TransferSink { store: transfer_store });
 
-    tracing::info!("Starting the engine {engine_args:?}");
+    // Rows are stored along with the chain id reported by their node
+    let mut node_clients: HashMap<&str, (NodeClient, u64)> = HashMap::new();
+    let mut health_checks = vec![];
+    let rpc_urls = jobs.iter().flat_map(|job| {
+        std::iter::once(job.rpc_url.as_str())
+            .chain(job.options.quorum_rpc_urls.iter().map(String::as_str))
+    });
+    for rpc_url in rpc_urls {
+        if !node_clients.contains_key(rpc_url) {
+            let rpc_urls = parse_rpc_urls(rpc_url)?;
+            let failover = rpc_urls.len() > 1;
+            let node_client = NodeClient::connect_all(rpc_urls, signer.clone())
+                .await?
+                .with_retry_policy(retry_policy);
+            if failover {
+                health_checks
+                    .push(node_client.spawn_health_check(HEALTH_CHECK_INTERVAL, MAX_ENDPOINT_LAG));
+            }
+            let chain_id = node_client.get_chain_id().await?;
+            tracing::info!(
+                "Connected to chain id {chain_id:?}: {}",
+                node_client.current_endpoint()
+            );
+            node_clients.insert(rpc_url, (node_client, chain_id));
+        }
+    }
 
-    let engine = Engine::start(&engine_args, &node_client, source, &checkpointer, sink).await?;
+    // Quorum providers have to index the same chain as the node of their job
+    let mut quorum_clients = vec![];
+    for job in &jobs {
+        let (_, chain_id) = &node_clients[job.rpc_url.as_str()];
+        let mut clients = vec![];
+        for rpc_url in &job.options.quorum_rpc_urls {
+            let (quorum_client, quorum_chain_id) = &node_clients[rpc_url.as_str()];
+            if quorum_chain_id != chain_id {
+                return Err(eyre!(
+                    "Quorum provider of job `{}` is on chain id {quorum_chain_id:?} instead of {chain_id:?}",
+                    job.name
+                ));
+            }
+            clients.push(quorum_client.clone());
+        }
+        quorum_clients.push(clients);
+    }
+
+    // Jobs are started concurrently, so that their backfills overlap
+    let engines = try_join_all(jobs.iter().zip(quorum_clients).map(|(job, quorum_clients)| {
+        let (node_client, chain_id) = &node_clients[job.rpc_url.as_str()];
+        start_job(node_client, *chain_id, quorum_clients, &client, job)
+    }))
+    .await?;
+
+    let mut running = vec![];
+    for (job, engine) in jobs.iter().zip(engines) {
+        let Some(engine) = engine else { continue };
+        let control_server = match &job.options.control_addr {
+            Some(control_addr) => Some(control::spawn(control_addr, engine.control()).await?),
+            None => None,
+        };
+        running.push((engine, control_server));
+    }
+    // Nothing to watch (e.g. replays)
+    if running.is_empty() {
+        return Ok(());
+    }
 
     // Wait for user to request shutdown (SIGINT)
     tokio::signal::ctrl_c().await?;
     tracing::info!("Shutting down engine...");
 
     // Gracefully shutdown
-    engine.shutdown().await;
+    for (engine, control_server) in running {
+        if let Some(control_server) = control_server {
+            control_server.abort();
+        }
+        engine.shutdown().await;
+    }
+    for health_check in health_checks {
+        health_check.abort();
+    }
 
     Ok(())
 }
+
+// WebSocket nodes push logs (`eth_subscribe`) instead of being polled
+fn is_websocket(rpc_url: &Url) -> bool {
+    matches!(rpc_url.scheme(), "ws" | "wss")
+}
+
+// Starts the engine of a job, none when the job only replays the raw log archive
+async fn start_job(
+    node_client: &NodeClient,
+    chain_id: u64,
+    quorum_clients: Vec<NodeClient>,
+    client: &Client,
+    job: &Job,
+) -> Result<Option<Engine>> {
+    let Job { name, options, args: engine_args, .. } = job;
+    tracing::info!("Starting job: {name:?} on chain id: {chain_id:?}");
+
+    let log_source: Arc<dyn Source<Item = Log>> = if options.replay {
+        let raw_log_store = store::raw_log::store::Store::new(client.clone()).on_chain(chain_id);
+        Arc::new(RawLogSource { store: raw_log_store })
+    } else if parse_rpc_urls(&job.rpc_url)?.iter().all(is_websocket) {
+        Arc::new(WsLogSource { node_client: node_client.clone() })
+    } else {
+        Arc::new(LogSource { node_client: node_client.clone() })
+    };
+    let log_source: Arc<dyn Source<Item = Log>> = match (quorum_clients.is_empty(), options.replay)
+    {
+        (false, false) => {
+            let mut sources = vec![log_source];
+            for quorum_client in quorum_clients {
+                sources.push(Arc::new(LogSource { node_client: quorum_client }));
+            }
+            let quorum = sources.len();
+            Arc::new(QuorumSource {
+                sources,
+                quorum,
+                retries: QUORUM_RETRIES,
+                retry_delay: engine_args.poll_interval,
+            })
+        }
+        _ => log_source,
+    };
+    let log_source: Arc<dyn Source<Item = Log>> = match (&options.factory, options.replay) {
+        (Some(factory), false) => {
+            let contract_store =
+                store::contract::store::Store::new(client.clone()).on_chain(chain_id);
+            let factory = factory.clone();
+            Arc::new(
+                FactorySource::init(log_source, node_client.clone(), contract_store, factory)
+                    .await?,
+            )
+        }
+        _ => log_source,
+    };
+
+    let checkpoint_store =
+        store::checkpoint::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+    let block_store =
+        store::block::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+    let watched_address_store =
+        store::watched_address::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+    let checkpointer =
+        Checkpointer::new(checkpoint_store, block_store).with_watch_list(watched_address_store);
+    let checkpointer = if options.track_balances {
+        let balance_store =
+            store::balance::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+        checkpointer.with_balance_snapshots(balance_store)
+    } else {
+        checkpointer
+    };
+
+    match &engine_args.event {
+        EventType::Transfer => {
+            let sink: Arc<dyn Sink<Item = Transfer>> = if options.track_balances {
+                let balance_store = store::balance::store::Store::new(client.clone())
+                    .on_chain(chain_id)
+                    .for_job(name);
+                Arc::new(BalanceSink { store: balance_store })
+            } else {
+                let transfer_store = store::transfer::store::Store::new(client.clone())
+                    .on_chain(chain_id)
+                    .for_job(name);
+                Arc::new(TransferSink { store: transfer_store })
+            };
+
+            run(job, chain_id, node_client, log_source, &checkpointer, sink, client).await
+        }
+        EventType::NftTransfer => {
+            let nft_transfer_store = store::nft_transfer::store::Store::new(client.clone())
+                .on_chain(chain_id)
+                .for_job(name);
+            let sink: Arc<dyn Sink<Item = NftTransfer>> =
+                Arc::new(NftTransferSink { store: nft_transfer_store });
+
+            run(job, chain_id, node_client, log_source, &checkpointer, sink, client).await
+        }
+        EventType::AnyTransfer => {
+            let transfer_sink: Arc<dyn Sink<Item = Transfer>> = if options.track_balances {
+                let balance_store = store::balance::store::Store::new(client.clone())
+                    .on_chain(chain_id)
+                    .for_job(name);
+                Arc::new(BalanceSink { store: balance_store })
+            } else {
+                let transfer_store = store::transfer::store::Store::new(client.clone())
+                    .on_chain(chain_id)
+                    .for_job(name);
+                Arc::new(TransferSink { store: transfer_store })
+            };
+            let nft_transfer_store = store::nft_transfer::store::Store::new(client.clone())
+                .on_chain(chain_id)
+                .for_job(name);
+            let sink: Arc<dyn Sink<Item = AnyTransfer>> = Arc::new(AnyTransferSink {
+                transfer_sink,
+                nft_transfer_sink: Arc::new(NftTransferSink { store: nft_transfer_store }),
+            });
+
+            run(job, chain_id, node_client, log_source, &checkpointer, sink, client).await
+        }
+        EventType::TransferSingle => {
+            let transfer_single_store = store::transfer_single::store::Store::new(client.clone())
+                .on_chain(chain_id)
+                .for_job(name);
+            let sink: Arc<dyn Sink<Item = TransferSingle>> =
+                Arc::new(TransferSingleSink { store: transfer_single_store });
+
+            run(job, chain_id, node_client, log_source, &checkpointer, sink, client).await
+        }
+        EventType::TransferBatch => {
+            let transfer_batch_store = store::transfer_batch::store::Store::new(client.clone())
+                .on_chain(chain_id)
+                .for_job(name);
+            let sink: Arc<dyn Sink<Item = TransferBatch>> =
+                Arc::new(TransferBatchSink { store: transfer_batch_store });
+
+            run(job, chain_id, node_client, log_source, &checkpointer, sink, client).await
+        }
+        EventType::Approval => {
+            let approval_store =
+                store::approval::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+            let sink: Arc<dyn Sink<Item = Approval>> =
+                Arc::new(ApprovalSink { store: approval_store });
+
+            run(job, chain_id, node_client, log_source, &checkpointer, sink, client).await
+        }
+        EventType::Abi(events) => {
+            let events: Vec<_> = events.iter().cloned().map(Arc::new).collect();
+            let source: Arc<dyn Source<Item = AbiLog>> =
+                Arc::new(AbiLogSource { source: log_source, events: events.clone() });
+            let event_log_store = store::event_log::store::Store::init(client.clone(), events)
+                .await?
+                .on_chain(chain_id)
+                .for_job(name);
+            let sink: Arc<dyn Sink<Item = EventLog>> =
+                Arc::new(EventLogSink { store: event_log_store });
+
+            run(job, chain_id, node_client, source, &checkpointer, sink, client).await
+        }
+    }
+}
+
+async fn run<E, T>(
+    job: &Job,
+    chain_id: u64,
+    node_client: &NodeClient,
+    source: Arc<dyn Source<Item = E>>,
+    checkpointer: &Checkpointer,
+    sink: Arc<dyn Sink<Item = T>>,
+    client: &Client,
+) -> Result<Option<Engine>>
+where
+    E: SourceInput + Debug + Clone + Send + Sync + 'static,
+    <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
+    T: Outcome + TryFrom<E> + Debug + Clone + Send + Sync + 'static,
+    Archived<T>: TryFrom<E>,
+    <E as TryInto<Archived<T>>>::Error: Debug + Send + Sync + 'static,
+{
+    let Job { name, options, args: engine_args, .. } = job;
+    let raw_log_store =
+        store::raw_log::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+
+    if options.replay {
+        tracing::info!("Replaying the raw log archive {engine_args:?}");
+        let contract_addresses = contract_addresses(&replay::addresses(engine_args));
+        match raw_log_store.get_last_raw_log_block_number(&contract_addresses).await? {
+            None => tracing::info!("No raw logs to replay"),
+            Some(to_block) => {
+                let nbr_of_outcomes = replay::replay(engine_args, to_block, source, sink).await?;
+                tracing::info!("Replayed outcomes: {nbr_of_outcomes:?}");
+            }
+        }
+        return Ok(None);
+    }
+
+    if options.archive_raw_logs {
+        let sink: Arc<dyn Sink<Item = Archived<T>>> =
+            Arc::new(RawLogSink::new(sink, raw_log_store));
+        watch(job, chain_id, node_client, source, checkpointer, sink, client).await.map(Some)
+    } else {
+        watch(job, chain_id, node_client, source, checkpointer, sink, client).await.map(Some)
+    }
+}
+
+async fn watch<E, T>(
+    job: &Job,
+    chain_id: u64,
+    node_client: &NodeClient,
+    source: Arc<dyn Source<Item = E>>,
+    checkpointer: &Checkpointer,
+    sink: Arc<dyn Sink<Item = T>>,
+    client: &Client,
+) -> Result<Engine>
+where
+    E: SourceInput + Debug + Clone + Send + Sync + 'static,
+    <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
+    T: Outcome + TryFrom<E> + Debug + Send + Sync + 'static,
+{
+    let Job { name, options, args: engine_args, .. } = job;
+    tracing::info!("Starting the engine {engine_args:?}");
+
+    let sink: Arc<dyn Sink<Item = T>> = if options.fetch_token_metadata {
+        let token_store = store::token::store::Store::new(client.clone()).on_chain(chain_id);
+        Arc::new(TokenMetadataSink::new(sink, token_store, node_client.clone()))
+    } else {
+        sink
+    };
+
+    let sink: Arc<dyn Sink<Item = T>> = if options.fetch_transactions {
+        let transaction_store =
+            store::transaction::store::Store::new(client.clone()).on_chain(chain_id).for_job(name);
+        Arc::new(TransactionSink::new(sink, transaction_store, node_client.clone()))
+    } else {
+        sink
+    };
+
+    Engine::start(engine_args, node_client, source, checkpointer, sink).await
+}
@@ -15,5 +15,47 @@ pub struct Query {
 
     /// From block number to watch
     #[arg(long)]
-    pub from_block: FromBlock,
+    pub from_block: Option<FromBlock>,
+
+    /// Chain id of the transfers, balances, supply changes and checkpoints
+    #[arg(long, default_value_t = store::client::DEFAULT_CHAIN_ID)]
+    pub chain_id: u64,
+
+    /// Job whose checkpoints are selected (`--from-block last` and `checkpoint` entity),
+    /// or whose transfers the balances at block are folded from
+    #[arg(long, default_value = store::client::DEFAULT_JOB)]
+    pub job: String,
+
+    /// To block number (inclusive), defaults to the last indexed block
+    #[arg(long)]
+    pub to_block: Option<u64>,
+
+    /// From block timestamp (inclusive), selects transfers by time instead of block number
+    #[arg(long, requires = "to_timestamp")]
+    pub from_timestamp: Option<i64>,
+
+    /// To block timestamp (inclusive)
+    #[arg(long, requires = "from_timestamp")]
+    pub to_timestamp: Option<i64>,
+
+    /// Block number at which balances are computed, from indexed transfers
+    #[arg(long)]
+    pub at_block: Option<u64>,
+
+    /// Token contract of the (NFT) transfers, of the balances at block, or of the supply changes.
+    /// Factory of the discovered contracts.
+    #[arg(long)]
+    pub contract: Option<String>,
+
+    /// Holder of the balance at block (otherwise selects the top holders)
+    #[arg(long)]
+    pub holder: Option<String>,
+
+    /// Number of top holders at block
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// Only select unlimited (max uint256) allowances
+    #[arg(long, default_value_t = false)]
+    pub unlimited: bool,
 }
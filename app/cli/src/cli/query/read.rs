@@ -5,6 +5,15 @@ use clap::ValueEnum;
 #[derive(Debug, Clone, ValueEnum)]
 pub enum Entity {
     Transfer,
+    NftTransfer,
+    TransferSingle,
+    TransferBatch,
+    Approval,
+    Allowance,
+    Balance,
+    Supply,
+    Transaction,
+    Contract,
     Checkpoint,
 }
 
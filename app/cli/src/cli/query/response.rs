@@ -1,5 +1,19 @@
+use alloy::primitives::{I256, U256, utils::format_units};
 use serde::{Serialize, ser::SerializeStruct};
-use store::{checkpoint::model::Checkpoint, transfer::model::Transfer, utils};
+use store::{
+    approval::model::{Allowance, Approval},
+    balance::model::Balance,
+    checkpoint::model::Checkpoint,
+    contract::model::Contract,
+    nft_transfer::model::NftTransfer,
+    supply::model::SupplyChange,
+    token::model::Token,
+    transaction::model::Transaction,
+    transfer::model::Transfer,
+    transfer_batch::model::TransferBatch,
+    transfer_single::model::TransferSingle,
+    utils,
+};
 
 // Tuple wrapper for Checkpoint
 pub struct CheckpointResponse(pub Checkpoint);
@@ -10,7 +24,8 @@ impl Serialize for CheckpointResponse {
         S: serde::Serializer,
     {
         let c = &self.0;
-        let mut state = serializer.serialize_struct("Checkpoint", 3)?;
+        let mut state = serializer.serialize_struct("Checkpoint", 4)?;
+        state.serialize_field("chain_id", &c.chain_id)?;
         state.serialize_field("block_number", &c.block_number)?;
         state.serialize_field("block_hash", &utils::bytes_to_hex(&c.block_hash))?;
         state.serialize_field("parent_hash", &utils::bytes_to_hex(&c.parent_hash))?;
@@ -18,8 +33,8 @@ impl Serialize for CheckpointResponse {
     }
 }
 
-// Tuple wrapper for Transfer
-pub struct TransferResponse(pub Transfer);
+// Tuple wrapper for Transfer, along with its token metadata (if fetched)
+pub struct TransferResponse(pub Transfer, pub Option<Token>);
 
 impl Serialize for TransferResponse {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -27,8 +42,11 @@ impl Serialize for TransferResponse {
         S: serde::Serializer,
     {
         let t = &self.0;
-        let mut state = serializer.serialize_struct("Transfer", 8)?;
+        let token = self.1.as_ref();
+        let mut state = serializer.serialize_struct("Transfer", 12)?;
+        state.serialize_field("chain_id", &t.chain_id)?;
         state.serialize_field("block_number", &t.block_number)?;
+        state.serialize_field("block_timestamp", &t.block_timestamp)?;
         state.serialize_field("block_hash", &utils::bytes_to_hex(&t.block_hash))?;
         state.serialize_field("transaction_hash", &utils::bytes_to_hex(&t.transaction_hash))?;
         state.serialize_field("log_index", &t.log_index)?;
@@ -36,6 +54,234 @@ impl Serialize for TransferResponse {
         state.serialize_field("from_address", &utils::bytes_to_address(&t.from_address))?;
         state.serialize_field("to_address", &utils::bytes_to_address(&t.to_address))?;
         state.serialize_field("amount", &utils::bytes_to_u256(&t.amount))?;
+        state.serialize_field("formatted_amount", &format_amount(&t.amount, token))?;
+        state.serialize_field("symbol", &token.and_then(|token| token.symbol.as_ref()))?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for NftTransfer
+pub struct NftTransferResponse(pub NftTransfer);
+
+impl Serialize for NftTransferResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let t = &self.0;
+        let mut state = serializer.serialize_struct("NftTransfer", 8)?;
+        state.serialize_field("block_number", &t.block_number)?;
+        state.serialize_field("block_hash", &utils::bytes_to_hex(&t.block_hash))?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&t.transaction_hash))?;
+        state.serialize_field("log_index", &t.log_index)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&t.contract_address))?;
+        state.serialize_field("from_address", &utils::bytes_to_address(&t.from_address))?;
+        state.serialize_field("to_address", &utils::bytes_to_address(&t.to_address))?;
+        state.serialize_field("token_id", &utils::bytes_to_u256(&t.token_id))?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for TransferSingle
+pub struct TransferSingleResponse(pub TransferSingle);
+
+impl Serialize for TransferSingleResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let t = &self.0;
+        let mut state = serializer.serialize_struct("TransferSingle", 10)?;
+        state.serialize_field("block_number", &t.block_number)?;
+        state.serialize_field("block_hash", &utils::bytes_to_hex(&t.block_hash))?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&t.transaction_hash))?;
+        state.serialize_field("log_index", &t.log_index)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&t.contract_address))?;
+        state.serialize_field("operator_address", &utils::bytes_to_address(&t.operator_address))?;
+        state.serialize_field("from_address", &utils::bytes_to_address(&t.from_address))?;
+        state.serialize_field("to_address", &utils::bytes_to_address(&t.to_address))?;
+        state.serialize_field("token_id", &utils::bytes_to_u256(&t.token_id))?;
+        state.serialize_field("amount", &utils::bytes_to_u256(&t.amount))?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for TransferBatch
+pub struct TransferBatchResponse(pub TransferBatch);
+
+impl Serialize for TransferBatchResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let t = &self.0;
+        let token_ids: Vec<String> =
+            t.token_ids.iter().map(|id| utils::bytes_to_u256(id)).collect();
+        let amounts: Vec<String> = t.amounts.iter().map(|a| utils::bytes_to_u256(a)).collect();
+        let mut state = serializer.serialize_struct("TransferBatch", 10)?;
+        state.serialize_field("block_number", &t.block_number)?;
+        state.serialize_field("block_hash", &utils::bytes_to_hex(&t.block_hash))?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&t.transaction_hash))?;
+        state.serialize_field("log_index", &t.log_index)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&t.contract_address))?;
+        state.serialize_field("operator_address", &utils::bytes_to_address(&t.operator_address))?;
+        state.serialize_field("from_address", &utils::bytes_to_address(&t.from_address))?;
+        state.serialize_field("to_address", &utils::bytes_to_address(&t.to_address))?;
+        state.serialize_field("token_ids", &token_ids)?;
+        state.serialize_field("amounts", &amounts)?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for Approval, along with its token metadata (if fetched)
+pub struct ApprovalResponse(pub Approval, pub Option<Token>);
+
+impl Serialize for ApprovalResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let a = &self.0;
+        let token = self.1.as_ref();
+        let mut state = serializer.serialize_struct("Approval", 10)?;
+        state.serialize_field("block_number", &a.block_number)?;
+        state.serialize_field("block_hash", &utils::bytes_to_hex(&a.block_hash))?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&a.transaction_hash))?;
+        state.serialize_field("log_index", &a.log_index)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&a.contract_address))?;
+        state.serialize_field("owner_address", &utils::bytes_to_address(&a.owner_address))?;
+        state.serialize_field("spender_address", &utils::bytes_to_address(&a.spender_address))?;
+        state.serialize_field("amount", &utils::bytes_to_u256(&a.amount))?;
+        state.serialize_field("formatted_amount", &format_amount(&a.amount, token))?;
+        state.serialize_field("symbol", &token.and_then(|token| token.symbol.as_ref()))?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for Allowance, along with its token metadata (if fetched)
+pub struct AllowanceResponse(pub Allowance, pub Option<Token>);
+
+impl Serialize for AllowanceResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let a = &self.0;
+        let token = self.1.as_ref();
+        let mut state = serializer.serialize_struct("Allowance", 8)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&a.contract_address))?;
+        state.serialize_field("owner_address", &utils::bytes_to_address(&a.owner_address))?;
+        state.serialize_field("spender_address", &utils::bytes_to_address(&a.spender_address))?;
+        state.serialize_field("amount", &utils::bytes_to_u256(&a.amount))?;
+        state.serialize_field("formatted_amount", &format_amount(&a.amount, token))?;
+        state.serialize_field("symbol", &token.and_then(|token| token.symbol.as_ref()))?;
+        state.serialize_field("block_number", &a.block_number)?;
+        state.serialize_field("log_index", &a.log_index)?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for Balance, along with its token metadata (if fetched)
+pub struct BalanceResponse(pub Balance, pub Option<Token>);
+
+impl Serialize for BalanceResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let b = &self.0;
+        let token = self.1.as_ref();
+        let mut state = serializer.serialize_struct("Balance", 5)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&b.contract_address))?;
+        state.serialize_field("holder_address", &utils::bytes_to_address(&b.holder_address))?;
+        state.serialize_field("balance", &utils::bytes_to_i256(&b.balance))?;
+        state.serialize_field("formatted_balance", &format_signed_amount(&b.balance, token))?;
+        state.serialize_field("symbol", &token.and_then(|token| token.symbol.as_ref()))?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for SupplyChange, along with its token metadata (if fetched)
+pub struct SupplyChangeResponse(pub SupplyChange, pub Option<Token>);
+
+impl Serialize for SupplyChangeResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s = &self.0;
+        let token = self.1.as_ref();
+        let mut state = serializer.serialize_struct("SupplyChange", 9)?;
+        state.serialize_field("block_number", &s.block_number)?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&s.transaction_hash))?;
+        state.serialize_field("log_index", &s.log_index)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&s.contract_address))?;
+        state.serialize_field("amount", &utils::bytes_to_i256(&s.amount))?;
+        state.serialize_field("total_supply", &utils::bytes_to_i256(&s.total_supply))?;
+        state.serialize_field("formatted_amount", &format_signed_amount(&s.amount, token))?;
+        state.serialize_field(
+            "formatted_total_supply",
+            &format_signed_amount(&s.total_supply, token),
+        )?;
+        state.serialize_field("symbol", &token.and_then(|token| token.symbol.as_ref()))?;
+        state.end()
+    }
+}
+
+// Human readable amount, when the token decimals are known
+fn format_amount(amount: &[u8], token: Option<&Token>) -> Option<String> {
+    let decimals = u8::try_from(token?.decimals?).ok()?;
+    let amount = U256::try_from_be_slice(amount)?;
+    format_units(amount, decimals).ok()
+}
+
+// Human readable signed amount (balances and supply changes), when the token decimals are known
+fn format_signed_amount(amount: &[u8], token: Option<&Token>) -> Option<String> {
+    let decimals = u8::try_from(token?.decimals?).ok()?;
+    let amount = I256::from_raw(U256::try_from_be_slice(amount)?);
+    format_units(amount, decimals).ok()
+}
+
+// Tuple wrapper for Transaction
+pub struct TransactionResponse(pub Transaction);
+
+impl Serialize for TransactionResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let t = &self.0;
+        let mut state = serializer.serialize_struct("Transaction", 8)?;
+        state.serialize_field("block_number", &t.block_number)?;
+        state.serialize_field("block_hash", &utils::bytes_to_hex(&t.block_hash))?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&t.transaction_hash))?;
+        state.serialize_field("tx_from", &utils::bytes_to_address(&t.tx_from))?;
+        state.serialize_field("tx_to", &t.tx_to.as_deref().map(utils::bytes_to_address))?;
+        state.serialize_field("gas_used", &t.gas_used)?;
+        state.serialize_field(
+            "effective_gas_price",
+            &utils::bytes_to_u256(&t.effective_gas_price),
+        )?;
+        state.serialize_field("status", &t.status)?;
+        state.end()
+    }
+}
+
+// Tuple wrapper for Contract
+pub struct ContractResponse(pub Contract);
+
+impl Serialize for ContractResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let c = &self.0;
+        let mut state = serializer.serialize_struct("Contract", 5)?;
+        state.serialize_field("contract_address", &utils::bytes_to_address(&c.contract_address))?;
+        state.serialize_field("factory_address", &utils::bytes_to_address(&c.factory_address))?;
+        state.serialize_field("block_number", &c.block_number)?;
+        state.serialize_field("transaction_hash", &utils::bytes_to_hex(&c.transaction_hash))?;
+        state.serialize_field("log_index", &c.log_index)?;
         state.end()
     }
 }
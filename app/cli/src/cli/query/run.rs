@@ -1,36 +1,305 @@
 use crate::cli::query::args::Query;
 use crate::cli::query::read::{Entity, FromBlock};
-use crate::cli::query::response::{CheckpointResponse, TransferResponse};
+use crate::cli::query::response::{
+    AllowanceResponse, ApprovalResponse, BalanceResponse, CheckpointResponse, ContractResponse,
+    NftTransferResponse, SupplyChangeResponse, TransactionResponse, TransferBatchResponse,
+    TransferResponse, TransferSingleResponse,
+};
+use alloy::primitives::Address;
 use eyre::{Result, eyre};
-use store::client::Client;
+use std::{collections::HashMap, str::FromStr};
+use store::{client::Client, token::model::Token};
 
 pub async fn select(query: &Query) -> Result<()> {
     let client = Client::init(&query.db_url).await?;
 
-    let checkpoint_store = store::checkpoint::store::Store::new(client.clone());
+    let checkpoint_store = store::checkpoint::store::Store::new(client.clone())
+        .on_chain(query.chain_id)
+        .for_job(&query.job);
 
     let from_block_number = match query.from_block {
-        FromBlock::Number(block_number) => Ok(block_number),
-        FromBlock::Last => match checkpoint_store.get_last_checkpoint().await {
+        None => Err(eyre!("--from-block is required by the `{:?}` entity", query.entity)),
+        Some(FromBlock::Number(block_number)) => Ok(block_number),
+        Some(FromBlock::Last) => match checkpoint_store.get_last_checkpoint().await {
             Ok(Some(checkpoint)) => Ok(checkpoint.block_number as u64),
             Ok(None) => Err(eyre!("Last Checkpoint Not Found")),
             Err(e) => Err(eyre!(e)),
         },
     };
 
+    // Token metadata, used to render human readable amounts
+    let token_store = store::token::store::Store::new(client.clone()).on_chain(query.chain_id);
+    let tokens: HashMap<Vec<u8>, Token> = token_store
+        .get_tokens()
+        .await?
+        .into_iter()
+        .map(|token| (token.contract_address.clone(), token))
+        .collect();
+    let token_of = |contract_address: &[u8]| tokens.get(contract_address).cloned();
+
     match query.entity {
         Entity::Transfer => {
-            let transfer_store = store::transfer::store::Store::new(client.clone());
-
-            let block_number = from_block_number?;
+            let transfer_store =
+                store::transfer::store::Store::new(client.clone()).on_chain(query.chain_id);
 
-            let transfers = transfer_store.get_transfers_from_block_number(block_number).await?;
+            let transfers = match (query.from_timestamp, query.to_timestamp, &query.contract) {
+                (Some(from_ts), Some(to_ts), _) => {
+                    transfer_store.get_transfers_between_timestamps(from_ts, to_ts).await?
+                }
+                (_, _, Some(contract)) => {
+                    transfer_store
+                        .get_contract_transfers_from_block_number(
+                            Address::from_str(contract)?.as_slice(),
+                            from_block_number?,
+                        )
+                        .await?
+                }
+                _ => transfer_store.get_transfers_from_block_number(from_block_number?).await?,
+            };
 
             if transfers.is_empty() {
                 println!("No Transfers Found")
             } else {
-                let response: Vec<TransferResponse> =
-                    transfers.into_iter().map(TransferResponse).collect();
+                let response: Vec<TransferResponse> = transfers
+                    .into_iter()
+                    .map(|transfer| {
+                        let token = token_of(&transfer.contract_address);
+                        TransferResponse(transfer, token)
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::NftTransfer => {
+            let nft_transfer_store =
+                store::nft_transfer::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let block_number = from_block_number?;
+
+            let nft_transfers = match &query.contract {
+                Some(contract) => {
+                    nft_transfer_store
+                        .get_contract_nft_transfers_from_block_number(
+                            Address::from_str(contract)?.as_slice(),
+                            block_number,
+                        )
+                        .await?
+                }
+                None => {
+                    nft_transfer_store.get_nft_transfers_from_block_number(block_number).await?
+                }
+            };
+
+            if nft_transfers.is_empty() {
+                println!("No NFT Transfers Found")
+            } else {
+                let response: Vec<NftTransferResponse> =
+                    nft_transfers.into_iter().map(NftTransferResponse).collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::TransferSingle => {
+            let transfer_single_store =
+                store::transfer_single::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let block_number = from_block_number?;
+
+            let transfer_singles =
+                transfer_single_store.get_transfer_singles_from_block_number(block_number).await?;
+
+            if transfer_singles.is_empty() {
+                println!("No Transfer Singles Found")
+            } else {
+                let response: Vec<TransferSingleResponse> =
+                    transfer_singles.into_iter().map(TransferSingleResponse).collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::TransferBatch => {
+            let transfer_batch_store =
+                store::transfer_batch::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let block_number = from_block_number?;
+
+            let transfer_batches =
+                transfer_batch_store.get_transfer_batches_from_block_number(block_number).await?;
+
+            if transfer_batches.is_empty() {
+                println!("No Transfer Batches Found")
+            } else {
+                let response: Vec<TransferBatchResponse> =
+                    transfer_batches.into_iter().map(TransferBatchResponse).collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::Approval => {
+            let approval_store =
+                store::approval::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let block_number = from_block_number?;
+
+            let approvals = approval_store.get_approvals_from_block_number(block_number).await?;
+
+            if approvals.is_empty() {
+                println!("No Approvals Found")
+            } else {
+                let response: Vec<ApprovalResponse> = approvals
+                    .into_iter()
+                    .map(|approval| {
+                        let token = token_of(&approval.contract_address);
+                        ApprovalResponse(approval, token)
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::Allowance => {
+            let approval_store =
+                store::approval::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let block_number = from_block_number?;
+
+            let allowances = if query.unlimited {
+                approval_store.get_unlimited_allowances_from_block_number(block_number).await?
+            } else {
+                approval_store.get_allowances_from_block_number(block_number).await?
+            };
+
+            if allowances.is_empty() {
+                println!("No Allowances Found")
+            } else {
+                let response: Vec<AllowanceResponse> = allowances
+                    .into_iter()
+                    .map(|allowance| {
+                        let token = token_of(&allowance.contract_address);
+                        AllowanceResponse(allowance, token)
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::Balance => {
+            let balance_store = store::balance::store::Store::new(client.clone())
+                .on_chain(query.chain_id)
+                .for_job(&query.job);
+
+            let balances = match query.at_block {
+                // Running balances
+                None => balance_store.get_balances().await?,
+                // Historical balances
+                Some(block_number) => {
+                    let contract = query
+                        .contract
+                        .as_deref()
+                        .ok_or_else(|| eyre!("--contract is required by --at-block"))?;
+                    let contract_address = Address::from_str(contract)?.to_vec();
+                    match query.holder.as_deref() {
+                        Some(holder) => {
+                            // holders are stored as indexed topics (32 bytes)
+                            let holder_address = Address::from_str(holder)?.into_word().to_vec();
+                            let balance = balance_store
+                                .get_balance_at_block_number(
+                                    &contract_address,
+                                    &holder_address,
+                                    block_number,
+                                )
+                                .await?;
+                            vec![balance]
+                        }
+                        None => {
+                            balance_store
+                                .get_top_holders_at_block_number(
+                                    &contract_address,
+                                    block_number,
+                                    query.top,
+                                )
+                                .await?
+                        }
+                    }
+                }
+            };
+
+            if balances.is_empty() {
+                println!("No Balances Found")
+            } else {
+                let response: Vec<BalanceResponse> = balances
+                    .into_iter()
+                    .map(|balance| {
+                        let token = token_of(&balance.contract_address);
+                        BalanceResponse(balance, token)
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::Supply => {
+            let supply_store =
+                store::supply::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let contract = query
+                .contract
+                .as_deref()
+                .ok_or_else(|| eyre!("--contract is required by supply"))?;
+            let contract_address = Address::from_str(contract)?.to_vec();
+            let block_number = from_block_number?;
+            let to_block_number = query.to_block.unwrap_or(i64::MAX as u64);
+
+            let supply_changes = supply_store
+                .get_supply_changes_between_block_numbers(
+                    &contract_address,
+                    block_number,
+                    to_block_number,
+                )
+                .await?;
+
+            if supply_changes.is_empty() {
+                println!("No Supply Changes Found")
+            } else {
+                let response: Vec<SupplyChangeResponse> = supply_changes
+                    .into_iter()
+                    .map(|supply_change| {
+                        let token = token_of(&supply_change.contract_address);
+                        SupplyChangeResponse(supply_change, token)
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::Transaction => {
+            let transaction_store =
+                store::transaction::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let block_number = from_block_number?;
+
+            let transactions =
+                transaction_store.get_transactions_from_block_number(block_number).await?;
+
+            if transactions.is_empty() {
+                println!("No Transactions Found")
+            } else {
+                let response: Vec<TransactionResponse> =
+                    transactions.into_iter().map(TransactionResponse).collect();
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            }
+        }
+        Entity::Contract => {
+            let contract_store =
+                store::contract::store::Store::new(client.clone()).on_chain(query.chain_id);
+
+            let contracts = match &query.contract {
+                Some(factory) => {
+                    contract_store
+                        .get_contracts_by_factory(Address::from_str(factory)?.as_slice())
+                        .await?
+                }
+                None => contract_store.get_contracts().await?,
+            };
+
+            if contracts.is_empty() {
+                println!("No Contracts Found")
+            } else {
+                let response: Vec<ContractResponse> =
+                    contracts.into_iter().map(ContractResponse).collect();
                 println!("{}", serde_json::to_string_pretty(&response).unwrap());
             }
         }
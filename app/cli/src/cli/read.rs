@@ -1,23 +1,148 @@
-use alloy::{primitives::Address, rpc::types::ValueOrArray};
+use alloy::{
+    json_abi::{ContractObject, Event},
+    primitives::{Address, B256},
+    rpc::types::ValueOrArray,
+};
 use clap::ValueEnum;
-use engine::source::filter::EventType;
+use engine::{
+    source::{
+        factory::Factory,
+        filter::{EventType, Topics},
+    },
+    tip::BlockTip,
+};
+use eyre::{Result, eyre};
+use serde::Deserialize;
 
-#[derive(Debug, Clone, ValueEnum)]
+#[derive(Debug, Clone, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum CliEventType {
     Transfer,
+    NftTransfer,
+    AnyTransfer,
+    TransferSingle,
+    TransferBatch,
+    Approval,
+    Abi,
 }
 
-impl From<CliEventType> for EventType {
-    fn from(value: CliEventType) -> Self {
+pub fn parse_event(
+    event: &CliEventType,
+    abi_path: Option<&str>,
+    abi_events: Option<&str>,
+) -> Result<EventType> {
+    match event {
+        CliEventType::Transfer => Ok(EventType::Transfer),
+        CliEventType::NftTransfer => Ok(EventType::NftTransfer),
+        CliEventType::AnyTransfer => Ok(EventType::AnyTransfer),
+        CliEventType::TransferSingle => Ok(EventType::TransferSingle),
+        CliEventType::TransferBatch => Ok(EventType::TransferBatch),
+        CliEventType::Approval => Ok(EventType::Approval),
+        CliEventType::Abi => {
+            let abi_path = abi_path.ok_or_else(|| eyre!("--abi is required by `abi` events"))?;
+            let abi_events =
+                abi_events.ok_or_else(|| eyre!("--abi-events is required by `abi` events"))?;
+            parse_abi_events(abi_path, abi_events).map(EventType::Abi)
+        }
+    }
+}
+
+// Reads the given events (comma-separated names) from an ABI JSON file or contract artifact
+pub fn parse_abi_events(abi_path: &str, abi_events: &str) -> Result<Vec<Event>> {
+    let json = std::fs::read_to_string(abi_path)?;
+    let abi = serde_json::from_str::<ContractObject>(&json)?
+        .abi
+        .ok_or_else(|| eyre!("ABI not found in {abi_path}"))?;
+
+    abi_events
+        .split(',')
+        .map(|name| {
+            let name = name.trim();
+            match abi.event(name).map(Vec::as_slice) {
+                Some([event]) if event.anonymous => {
+                    Err(eyre!("Anonymous event `{name}` cannot be filtered by signature"))
+                }
+                Some([event]) => Ok(event.clone()),
+                Some(_) => Err(eyre!("Ambiguous overloaded event `{name}` in {abi_path}")),
+                None => Err(eyre!("Event `{name}` not found in {abi_path}")),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CliBlockTip {
+    Latest,
+    Safe,
+    Finalized,
+}
+
+impl From<CliBlockTip> for BlockTip {
+    fn from(value: CliBlockTip) -> Self {
         match value {
-            CliEventType::Transfer => EventType::Transfer,
+            CliBlockTip::Latest => BlockTip::Latest,
+            CliBlockTip::Safe => BlockTip::Safe,
+            CliBlockTip::Finalized => BlockTip::Finalized,
         }
     }
 }
 
-pub fn parse_addresses(input: &str) -> ValueOrArray<Address> {
+// No addresses means no address filter: logs of every contract on chain are watched
+pub fn parse_addresses(input: Option<&str>) -> ValueOrArray<Address> {
+    let Some(input) = input else {
+        return ValueOrArray::Array(vec![]);
+    };
     let parts: Vec<_> =
         input.split(',').map(|s| s.trim().parse::<Address>().expect("Invalid address")).collect();
 
     if parts.len() == 1 { ValueOrArray::Value(parts[0]) } else { ValueOrArray::Array(parts) }
 }
+
+// Reads the indexed topics filter: each topic is a comma-separated list of
+// addresses (left-padded to a word, as indexed by events) or 32-byte words
+pub fn parse_topics(
+    topic1: Option<&str>,
+    topic2: Option<&str>,
+    topic3: Option<&str>,
+) -> Result<Topics> {
+    Ok(Topics {
+        topic1: parse_topic(topic1)?,
+        topic2: parse_topic(topic2)?,
+        topic3: parse_topic(topic3)?,
+    })
+}
+
+fn parse_topic(input: Option<&str>) -> Result<Vec<B256>> {
+    let Some(input) = input else {
+        return Ok(vec![]);
+    };
+    input
+        .split(',')
+        .map(|value| {
+            let value = value.trim();
+            if let Ok(address) = value.parse::<Address>() {
+                Ok(address.into_word())
+            } else {
+                value.parse::<B256>().map_err(|_| eyre!("`{value}` is not an address or a topic"))
+            }
+        })
+        .collect()
+}
+
+// Reads the factory contract, along with its creation event (human-readable signature)
+pub fn parse_factory(
+    factory: Option<&str>,
+    factory_event: Option<&str>,
+    factory_arg: usize,
+) -> Result<Option<Factory>> {
+    let (Some(factory), Some(factory_event)) = (factory, factory_event) else {
+        return Ok(None);
+    };
+    let address = factory.trim().parse::<Address>()?;
+    let event = Event::parse(factory_event.trim())?;
+    if event.inputs.get(factory_arg).is_none_or(|input| input.ty != "address") {
+        return Err(eyre!("Parameter {factory_arg} of `{factory_event}` is not an address"));
+    }
+    Ok(Some(Factory { address, event, argument_index: factory_arg }))
+}
@@ -1,6 +1,8 @@
 mod cli {
     pub mod engine {
         pub mod args;
+        pub mod control;
+        pub mod jobs;
         pub mod run;
     }
     pub mod query {
@@ -13,13 +15,12 @@ mod cli {
     pub mod read;
 }
 
+use chain::retry::RetryPolicy;
 use clap::Parser;
-use engine::args::Args;
 use eyre::Result;
-use std::time::Duration;
 
 use crate::cli::cmd::{Cli, Command};
-use crate::cli::read;
+use crate::cli::engine::jobs::{self, JobConfig};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -30,16 +31,16 @@ async fn main() -> Result<()> {
     match &cli.command {
         Command::Engine(args) => {
             tracing::info!("Engine Command: {:?}", args);
-            // map CLI args to Engine Args
-            let start_args = Args {
-                addresses: read::parse_addresses(&args.addresses),
-                event: args.event.clone().into(),
-                from_block: args.from_block,
-                poll_interval: Duration::from_millis(args.poll_interval),
-                checkpoint_interval: args.checkpoint_interval,
-                backfill_checkpoint_interval: args.backfill_checkpoint_interval,
-            };
-            cli::engine::run::start(&args.rpc_url, &args.db_url, &args.signer_pk, start_args).await
+            // map CLI args to a single Engine job
+            let job = JobConfig::from(args).into_job(&args.rpc_url)?;
+            let retry_policy = RetryPolicy { max_retries: args.max_retries, ..Default::default() };
+            cli::engine::run::start(&args.db_url, &args.signer_pk, retry_policy, vec![job]).await
+        }
+        Command::Jobs(args) => {
+            tracing::info!("Jobs Command: {:?}", args);
+            let jobs = jobs::read_jobs(&args.config, &args.rpc_url)?;
+            let retry_policy = RetryPolicy { max_retries: args.max_retries, ..Default::default() };
+            cli::engine::run::start(&args.db_url, &args.signer_pk, retry_policy, jobs).await
         }
         Command::Select(query) => {
             tracing::info!("Engine Query: {:?}", query);
@@ -50,7 +51,7 @@ async fn main() -> Result<()> {
 
 fn init_tracing(cli: &Cli) {
     match &cli.command {
-        Command::Engine(_) => {
+        Command::Engine(_) | Command::Jobs(_) => {
             // install global subscriber configured based on RUST_LOG envvar.
             tracing_subscriber::fmt::init();
         }
@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+use alloy::transports::http::reqwest::Url;
+
+use crate::rpc::NodeClientProvider;
+
+// A node connection, with its health as last observed
+pub(crate) struct Endpoint {
+    // Shown in logs instead of the URL, which may embed an API key
+    pub(crate) label: String,
+    pub(crate) provider: NodeClientProvider,
+    healthy: AtomicBool,
+}
+
+impl Endpoint {
+    pub(crate) fn new(label: String, provider: NodeClientProvider) -> Self {
+        Self { label, provider, healthy: AtomicBool::new(true) }
+    }
+
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+}
+
+// Endpoints of the same chain, requests being sent to the current one
+pub(crate) struct Endpoints {
+    endpoints: Vec<Endpoint>,
+    current: AtomicUsize,
+    // Switches from a failed endpoint to another one, since startup
+    failovers: AtomicU64,
+}
+
+impl Endpoints {
+    pub(crate) fn new(endpoints: Vec<Endpoint>) -> Self {
+        assert!(!endpoints.is_empty(), "at least one endpoint is required");
+        Self { endpoints, current: AtomicUsize::new(0), failovers: AtomicU64::new(0) }
+    }
+
+    pub(crate) fn failovers(&self) -> u64 {
+        self.failovers.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &Endpoint {
+        &self.endpoints[index]
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Endpoint> {
+        self.endpoints.iter()
+    }
+
+    pub(crate) fn current(&self) -> (usize, &Endpoint) {
+        let index = self.current.load(Ordering::Relaxed);
+        (index, &self.endpoints[index])
+    }
+
+    pub(crate) fn set_healthy(&self, index: usize, healthy: bool) {
+        let endpoint = &self.endpoints[index];
+        if endpoint.healthy.swap(healthy, Ordering::Relaxed) != healthy {
+            match healthy {
+                true => tracing::info!("RPC endpoint {} is healthy again", endpoint.label),
+                false => tracing::warn!("RPC endpoint {} is unhealthy", endpoint.label),
+            }
+        }
+    }
+
+    // Marks the given endpoint unhealthy and moves the requests to the next healthy one
+    // (or simply the next one when none is healthy).
+    // Concurrent failures of the same endpoint only move the requests once.
+    pub(crate) fn fail_over(&self, failed: usize, reason: &str) {
+        self.set_healthy(failed, false);
+        if self.len() == 1 {
+            return;
+        }
+        let next = (1..self.len())
+            .map(|offset| (failed + offset) % self.len())
+            .find(|index| self.endpoints[*index].is_healthy())
+            .unwrap_or((failed + 1) % self.len());
+        if self.current.compare_exchange(failed, next, Ordering::Relaxed, Ordering::Relaxed).is_ok()
+        {
+            let failovers = self.failovers.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::warn!(
+                failovers,
+                "RPC endpoint {} failed ({reason}), switched to: {}",
+                self.endpoints[failed].label,
+                self.endpoints[next].label
+            );
+        }
+    }
+}
+
+// Scheme and host of the URL (path of IPC sockets), leaving out paths and queries holding API keys
+pub(crate) fn label(rpc_url: &Url) -> String {
+    match (rpc_url.host_str(), rpc_url.port()) {
+        (Some(host), Some(port)) => format!("{}://{host}:{port}", rpc_url.scheme()),
+        (Some(host), None) => format!("{}://{host}", rpc_url.scheme()),
+        (None, _) => format!("{}://{}", rpc_url.scheme(), rpc_url.path()),
+    }
+}
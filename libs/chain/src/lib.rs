@@ -1 +1,4 @@
+mod endpoint;
+pub mod retry;
 pub mod rpc;
+pub mod token;
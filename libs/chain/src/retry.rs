@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use alloy::transports::RpcError;
+use alloy::transports::TransportErrorKind;
+
+// JSON-RPC error code of requests exceeding the provider limits (e.g. Infura, Alchemy)
+const LIMIT_EXCEEDED_CODE: i64 = -32005;
+
+// JSON-RPC error code of internal node errors
+const INTERNAL_ERROR_CODE: i64 = -32603;
+
+// Backoff factor of rate limited requests, over the one of transient errors
+const RATE_LIMIT_BACKOFF_FACTOR: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    // Connection, HTTP server or node internal errors, likely to pass on retry
+    Transient,
+    // Too many requests (HTTP 429, `-32005`), passing once the provider is given time
+    RateLimited,
+    // Invalid requests and responses, failing the same on every retry
+    Permanent,
+}
+
+pub fn classify(error: &RpcError<TransportErrorKind>) -> ErrorClass {
+    match error {
+        RpcError::Transport(TransportErrorKind::HttpError(http_error)) => match http_error.status {
+            429 => ErrorClass::RateLimited,
+            408 | 500.. => ErrorClass::Transient,
+            _ => ErrorClass::Permanent,
+        },
+        RpcError::Transport(_) | RpcError::NullResp => ErrorClass::Transient,
+        RpcError::ErrorResp(payload) => {
+            let message = payload.message.to_lowercase();
+            if payload.code == LIMIT_EXCEEDED_CODE
+                || payload.code == 429
+                || message.contains("rate limit")
+                || message.contains("too many requests")
+            {
+                ErrorClass::RateLimited
+            } else if payload.code == INTERNAL_ERROR_CODE {
+                ErrorClass::Transient
+            } else {
+                ErrorClass::Permanent
+            }
+        }
+        _ => ErrorClass::Permanent,
+    }
+}
+
+// Retries of the requests failing on every endpoint with a transient error or a rate limit
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    // Retries of a request before its error is returned (0 to return the first error)
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Exponential backoff of the given retry (from 0), rate limits backing off longer.
+    // Jittered over its upper half, so that clients failing together do not retry together.
+    pub fn backoff(&self, retry: u32, class: ErrorClass) -> Duration {
+        let factor = match class {
+            ErrorClass::RateLimited => RATE_LIMIT_BACKOFF_FACTOR,
+            _ => 1,
+        };
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor.saturating_mul(2u32.saturating_pow(retry)))
+            .min(self.max_backoff);
+        backoff.mul_f64(rand::random_range(0.5..=1.0))
+    }
+}
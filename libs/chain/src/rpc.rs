@@ -1,21 +1,28 @@
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use alloy::eips::BlockId;
 use alloy::eips::BlockNumberOrTag;
 use alloy::network::EthereumWallet;
 use alloy::primitives::Address;
+use alloy::primitives::B256;
 use alloy::primitives::BlockHash;
+use alloy::primitives::TxHash;
+use alloy::primitives::U256;
 use alloy::providers::Identity;
 use alloy::providers::RootProvider;
 use alloy::providers::fillers::{
     BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller, WalletFiller,
 };
-use alloy::providers::{Provider, ProviderBuilder};
+use alloy::providers::{IpcConnect, Provider, ProviderBuilder, WsConnect};
 use alloy::rpc::client::PollerStream;
 use alloy::rpc::client::RpcClient;
 use alloy::rpc::types::Block;
 use alloy::rpc::types::Filter;
 use alloy::rpc::types::Log;
+use alloy::rpc::types::TransactionReceipt;
 use alloy::rpc::types::ValueOrArray;
 use alloy::signers::local::PrivateKeySigner;
 use alloy::transports::RpcError;
@@ -23,10 +30,15 @@ use alloy::transports::TransportErrorKind;
 use alloy::transports::http::reqwest;
 use eyre::Result;
 use futures_util::StreamExt;
-use futures_util::stream::BoxStream;
+use futures_util::future::join_all;
+use futures_util::stream::{self, BoxStream};
 use reqwest::Url;
+use tokio::task::JoinHandle;
 
-type NodeClientProvider = FillProvider<
+use crate::endpoint::{self, Endpoint, Endpoints};
+use crate::retry::{ErrorClass, RetryPolicy, classify};
+
+pub(crate) type NodeClientProvider = FillProvider<
     JoinFill<
         JoinFill<
             Identity,
@@ -37,76 +49,329 @@ type NodeClientProvider = FillProvider<
     RootProvider,
 >;
 
+// Gauge of the endpoint requests are sent to, and counter of the failovers between endpoints
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointMetrics {
+    // Label (scheme and host) of the current endpoint
+    pub active_endpoint: String,
+    pub healthy_endpoints: usize,
+    pub failovers: u64,
+}
+
+// Client of one or several endpoints of the same chain, cheap to clone.
+// Requests are sent to the current endpoint, and move to another one when it fails.
 #[derive(Clone)]
 pub struct NodeClient {
-    provider: NodeClientProvider,
+    endpoints: Arc<Endpoints>,
+    retry_policy: RetryPolicy,
 }
 
 impl NodeClient {
     pub fn new(rpc_url: Url, signer: PrivateKeySigner) -> Self {
+        let label = endpoint::label(&rpc_url);
         let rpc_client = RpcClient::new_http(rpc_url);
         let provider = ProviderBuilder::new().wallet(signer).connect_client(rpc_client);
-        Self { provider }
+        Self::with_endpoints(vec![Endpoint::new(label, provider)])
+    }
+
+    fn with_endpoints(endpoints: Vec<Endpoint>) -> Self {
+        Self {
+            endpoints: Arc::new(Endpoints::new(endpoints)),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    // Connects to every given endpoint (in order of preference), failing over between them.
+    // Endpoints unreachable at startup are left out, as long as one of them is connected.
+    pub async fn connect_all(
+        rpc_urls: Vec<Url>,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, RpcError<TransportErrorKind>> {
+        let mut endpoints = vec![];
+        let mut last_error = None;
+        for rpc_url in rpc_urls {
+            let label = endpoint::label(&rpc_url);
+            match Self::connect(rpc_url, signer.clone()).await {
+                Ok(node_client) => {
+                    let (_, connected) = node_client.endpoints.current();
+                    endpoints.push(Endpoint::new(label, connected.provider.clone()));
+                }
+                Err(e) => {
+                    tracing::error!("RPC endpoint {label} failed to connect: {e:?}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        match (endpoints.is_empty(), last_error) {
+            (true, Some(e)) => Err(e),
+            (true, None) => Err(TransportErrorKind::custom_str("no RPC endpoint given")),
+            (false, _) => Ok(Self::with_endpoints(endpoints)),
+        }
+    }
+
+    // Connects with the transport matching the URL scheme: WebSocket (`ws://`, `wss://`),
+    // IPC (`ipc://` followed by the socket path) or HTTP otherwise
+    pub async fn connect(
+        rpc_url: Url,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, RpcError<TransportErrorKind>> {
+        match rpc_url.scheme() {
+            "ws" | "wss" => Self::connect_ws(rpc_url, signer).await,
+            "ipc" => Self::connect_ipc(PathBuf::from(rpc_url.path()), signer).await,
+            _ => Ok(Self::new(rpc_url, signer)),
+        }
+    }
+
+    // Connects over a WebSocket (`ws://` or `wss://` URL), enabling `eth_subscribe`.
+    // The connection is re-established by the transport when dropped.
+    pub async fn connect_ws(
+        rpc_url: Url,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, RpcError<TransportErrorKind>> {
+        let label = endpoint::label(&rpc_url);
+        let provider =
+            ProviderBuilder::new().wallet(signer).connect_ws(WsConnect::new(rpc_url)).await?;
+        Ok(Self::with_endpoints(vec![Endpoint::new(label, provider)]))
+    }
+
+    // Connects over the Unix domain socket (or Windows named pipe) of a co-located node
+    pub async fn connect_ipc(
+        path: PathBuf,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, RpcError<TransportErrorKind>> {
+        let label = format!("ipc://{}", path.display());
+        let provider =
+            ProviderBuilder::new().wallet(signer).connect_ipc(IpcConnect::new(path)).await?;
+        Ok(Self::with_endpoints(vec![Endpoint::new(label, provider)]))
     }
 
+    // Provider of the current endpoint
     pub fn borrow_provider(&self) -> &NodeClientProvider {
-        &self.provider
+        &self.endpoints.current().1.provider
+    }
+
+    // Label (scheme and host) of the endpoint requests are currently sent to
+    pub fn current_endpoint(&self) -> String {
+        self.endpoints.current().1.label.clone()
+    }
+
+    pub fn endpoint_metrics(&self) -> EndpointMetrics {
+        EndpointMetrics {
+            active_endpoint: self.current_endpoint(),
+            healthy_endpoints: self
+                .endpoints
+                .iter()
+                .filter(|endpoint| endpoint.is_healthy())
+                .count(),
+            failovers: self.endpoints.failovers(),
+        }
+    }
+
+    // Sends a request to the current endpoint, then to the next ones while endpoints fail
+    // with a transient error or a rate limit. Once every endpoint failed, the request is retried
+    // after a backoff, until the retry policy is exhausted. Permanent errors are returned at once.
+    pub(crate) async fn request<T, F, Fut>(
+        &self,
+        send: F,
+    ) -> Result<T, RpcError<TransportErrorKind>>
+    where
+        F: Fn(NodeClientProvider) -> Fut,
+        Fut: Future<Output = Result<T, RpcError<TransportErrorKind>>>,
+    {
+        let mut attempts = 1;
+        let mut retry = 0;
+        loop {
+            let (index, endpoint) = self.endpoints.current();
+            let e = match send(endpoint.provider.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(e) => e,
+            };
+            let class = classify(&e);
+            if class == ErrorClass::Permanent {
+                return Err(e);
+            }
+            if self.endpoints.len() > 1 {
+                self.endpoints.fail_over(index, &e.to_string());
+            }
+            if attempts < self.endpoints.len() {
+                attempts += 1;
+                continue;
+            }
+            if retry == self.retry_policy.max_retries {
+                tracing::error!("RPC request failed, {retry:?} retries exhausted: {e:?}");
+                return Err(e);
+            }
+            let backoff = self.retry_policy.backoff(retry, class);
+            retry += 1;
+            attempts = 1;
+            tracing::warn!(
+                "RPC request failed ({class:?}), retry {retry:?} of {:?} in {backoff:?}: {e}",
+                self.retry_policy.max_retries
+            );
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    // Checks every endpoint at the given interval: endpoints failing to answer, or whose head is
+    // more than `max_lag` blocks behind the highest one, are marked unhealthy and requests are
+    // moved away from them. The endpoint metrics are reported after every check.
+    pub fn spawn_health_check(&self, interval: Duration, max_lag: u64) -> JoinHandle<()> {
+        let node_client = self.clone();
+        let endpoints = Arc::clone(&self.endpoints);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let heads =
+                    join_all(endpoints.iter().map(|endpoint| endpoint.provider.get_block_number()))
+                        .await;
+                let highest = heads.iter().flatten().max().copied().unwrap_or_default();
+                for (index, head) in heads.iter().enumerate() {
+                    match head {
+                        Ok(head) => endpoints.set_healthy(index, highest - head <= max_lag),
+                        Err(e) => {
+                            tracing::warn!(
+                                "RPC endpoint {} failed the health check: {e:?}",
+                                endpoints.get(index).label
+                            );
+                            endpoints.set_healthy(index, false);
+                        }
+                    }
+                }
+                let (current, endpoint) = endpoints.current();
+                if !endpoint.is_healthy() {
+                    endpoints.fail_over(current, "unhealthy");
+                }
+                let metrics = node_client.endpoint_metrics();
+                tracing::debug!(
+                    active_endpoint = metrics.active_endpoint,
+                    healthy_endpoints = metrics.healthy_endpoints,
+                    failovers = metrics.failovers,
+                    "RPC endpoint metrics"
+                );
+            }
+        })
+    }
+
+    pub async fn get_chain_id(&self) -> Result<u64, RpcError<TransportErrorKind>> {
+        self.request(|provider| async move { provider.get_chain_id().await }).await
     }
 
     pub async fn get_latest_block_number(&self) -> Result<u64, RpcError<TransportErrorKind>> {
-        self.provider.get_block_number().await
+        self.request(|provider| async move { provider.get_block_number().await }).await
     }
 
     pub async fn get_latest_block(&self) -> Result<Option<Block>, RpcError<TransportErrorKind>> {
-        self.provider.get_block(BlockId::latest()).full().await
+        self.request(|provider| async move { provider.get_block(BlockId::latest()).full().await })
+            .await
     }
 
     pub async fn get_latest_finalized_block(
         &self,
     ) -> Result<Option<Block>, RpcError<TransportErrorKind>> {
-        self.provider.get_block(BlockId::finalized()).full().await
+        self.request(
+            |provider| async move { provider.get_block(BlockId::finalized()).full().await },
+        )
+        .await
+    }
+
+    pub async fn get_latest_safe_block(
+        &self,
+    ) -> Result<Option<Block>, RpcError<TransportErrorKind>> {
+        self.request(|provider| async move { provider.get_block(BlockId::safe()).full().await })
+            .await
     }
 
     pub async fn get_accounts(&self) -> Result<Vec<Address>, RpcError<TransportErrorKind>> {
-        self.provider.get_accounts().await
+        self.request(|provider| async move { provider.get_accounts().await }).await
     }
 
     pub async fn get_logs(
         &self,
         addresses: ValueOrArray<Address>,
-        event: &str,
+        events: impl IntoIterator<Item = impl AsRef<[u8]>>,
+        topics: [Vec<B256>; 3],
         from_block_number: BlockNumberOrTag,
         to_block_number: BlockNumberOrTag,
     ) -> Result<Vec<Log>, RpcError<TransportErrorKind>> {
-        let filter = Filter::new()
-            .address(addresses)
-            .event(event)
+        let filter = with_topics(Filter::new().address(addresses).events(events), topics)
             .from_block(from_block_number)
             .to_block(to_block_number);
 
-        self.provider.get_logs(&filter).await
+        let filter = &filter;
+        self.request(|provider| async move { provider.get_logs(filter).await }).await
     }
 
     pub async fn watch_logs(
         &self,
         addresses: ValueOrArray<Address>,
-        event: &str,
+        events: impl IntoIterator<Item = impl AsRef<[u8]>>,
+        topics: [Vec<B256>; 3],
         from_block_number: BlockNumberOrTag,
         poll_interval: Duration,
-    ) -> Result<PollerStream<Vec<Log>>, RpcError<TransportErrorKind>> {
-        let filter = Filter::new().address(addresses).event(event).from_block(from_block_number);
+    ) -> Result<BoxStream<'static, Vec<Log>>, RpcError<TransportErrorKind>> {
+        let filter = with_topics(Filter::new().address(addresses).events(events), topics)
+            .from_block(from_block_number);
 
-        self.provider
-            .watch_logs(&filter)
-            .await
-            .map(|poller_builder| poller_builder.with_poll_interval(poll_interval).into_stream())
+        let (index, filter_id) = self.new_filter(&filter).await?;
+        let watch = LogWatch {
+            node_client: self.clone(),
+            filter,
+            index,
+            filter_id,
+            poll_interval,
+            polled: false,
+            retry: 0,
+        };
+        Ok(stream::unfold(watch, LogWatch::next).boxed())
+    }
+
+    // Installs a log filter, returning the endpoint it was installed on along with its id
+    async fn new_filter(
+        &self,
+        filter: &Filter,
+    ) -> Result<(usize, U256), RpcError<TransportErrorKind>> {
+        self.request(|provider| async move {
+            let (index, _) = self.endpoints.current();
+            provider.new_filter(filter).await.map(|filter_id| (index, filter_id))
+        })
+        .await
+    }
+
+    // Pushed logs (`eth_subscribe("logs")`), requires a WebSocket connection
+    pub async fn subscribe_logs(
+        &self,
+        addresses: ValueOrArray<Address>,
+        events: impl IntoIterator<Item = impl AsRef<[u8]>>,
+        topics: [Vec<B256>; 3],
+    ) -> Result<BoxStream<'static, Log>, RpcError<TransportErrorKind>> {
+        let filter = with_topics(Filter::new().address(addresses).events(events), topics);
+
+        let filter = &filter;
+        let subscription =
+            self.request(|provider| async move { provider.subscribe_logs(filter).await }).await?;
+        Ok(subscription.into_stream().boxed())
+    }
+
+    // Pushed chain head numbers (`eth_subscribe("newHeads")`), requires a WebSocket connection
+    pub async fn subscribe_block_numbers(
+        &self,
+    ) -> Result<BoxStream<'static, u64>, RpcError<TransportErrorKind>> {
+        let subscription =
+            self.request(|provider| async move { provider.subscribe_blocks().await }).await?;
+        Ok(subscription.into_stream().map(|header| header.number).boxed())
     }
 
     pub async fn watch_block_hashes(
         &self,
         poll_interval: Duration,
     ) -> Result<PollerStream<Vec<BlockHash>>, RpcError<TransportErrorKind>> {
-        self.provider
+        self.borrow_provider()
             .watch_blocks()
             .await
             .map(|block_provider| block_provider.with_poll_interval(poll_interval).into_stream())
@@ -116,7 +381,7 @@ impl NodeClient {
         &'a self,
         poll_interval: Duration,
     ) -> Result<BoxStream<'a, Result<Block, RpcError<TransportErrorKind>>>> {
-        let mut watcher = self.provider.watch_full_blocks().await?;
+        let mut watcher = self.borrow_provider().watch_full_blocks().await?;
         watcher.set_poll_interval(poll_interval);
         Ok(watcher.into_stream().boxed())
     }
@@ -125,20 +390,138 @@ impl NodeClient {
         &self,
         block_hash: BlockHash,
     ) -> Result<Option<Block>, RpcError<TransportErrorKind>> {
-        self.provider.get_block_by_hash(block_hash).await
+        self.request(|provider| async move { provider.get_block_by_hash(block_hash).await }).await
     }
 
     pub async fn get_block_by_number(
         &self,
         block_number: u64,
     ) -> Result<Option<Block>, RpcError<TransportErrorKind>> {
-        self.provider.get_block_by_number(block_number.into()).await
+        self.request(
+            |provider| async move { provider.get_block_by_number(block_number.into()).await },
+        )
+        .await
     }
 
     pub async fn get_block_by_id(
         &self,
         block_id: BlockId,
     ) -> Result<Option<Block>, RpcError<TransportErrorKind>> {
-        self.provider.get_block(block_id).await
+        self.request(|provider| async move { provider.get_block(block_id).await }).await
+    }
+
+    // Receipts of every transaction of a block, in a single `eth_getBlockReceipts` call
+    pub async fn get_block_receipts(
+        &self,
+        block_hash: BlockHash,
+    ) -> Result<Option<Vec<TransactionReceipt>>, RpcError<TransportErrorKind>> {
+        self.request(|provider| async move {
+            provider.get_block_receipts(BlockId::hash(block_hash)).await
+        })
+        .await
+    }
+
+    pub async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>, RpcError<TransportErrorKind>> {
+        self.request(
+            |provider| async move { provider.get_transaction_receipt(transaction_hash).await },
+        )
+        .await
+    }
+}
+
+// Polls the changes of a log filter. When its endpoint fails (or drops the filter), the filter
+// is installed again from the last polled block on, on the endpoint requests moved to, and polled
+// again after a backoff. The watch ends once the retry policy of the client is exhausted, or the
+// filter cannot be installed again.
+struct LogWatch {
+    node_client: NodeClient,
+    filter: Filter,
+    index: usize,
+    filter_id: U256,
+    poll_interval: Duration,
+    polled: bool,
+    // Retries since the last successful poll
+    retry: u32,
+}
+
+impl LogWatch {
+    async fn next(mut self) -> Option<(Vec<Log>, Self)> {
+        loop {
+            if self.polled {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+            self.polled = true;
+            let provider = self.node_client.endpoints.get(self.index).provider.clone();
+            match provider.get_filter_changes::<Log>(self.filter_id).await {
+                Ok(logs) => {
+                    if let Some(block_number) = logs.iter().filter_map(|log| log.block_number).max()
+                    {
+                        self.filter = self.filter.from_block(block_number);
+                    }
+                    self.retry = 0;
+                    return Some((logs, self));
+                }
+                Err(e) => {
+                    let retry_policy = self.node_client.retry_policy;
+                    if self.retry == retry_policy.max_retries {
+                        tracing::error!(
+                            "Failed to poll logs, {:?} retries exhausted: {e:?}",
+                            self.retry
+                        );
+                        return None;
+                    }
+                    tracing::error!("Failed to poll logs: {e:?}");
+                    let class = classify(&e);
+                    if class != ErrorClass::Permanent {
+                        self.node_client.endpoints.fail_over(self.index, &e.to_string());
+                    }
+                    match self.node_client.new_filter(&self.filter).await {
+                        Ok((index, filter_id)) => {
+                            self.index = index;
+                            self.filter_id = filter_id;
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to install the log filter: {e:?}");
+                            return None;
+                        }
+                    }
+                    let backoff = retry_policy.backoff(self.retry, class);
+                    self.retry += 1;
+                    tracing::warn!(
+                        "Polling logs again, retry {:?} of {:?} in {backoff:?}",
+                        self.retry,
+                        retry_policy.max_retries
+                    );
+                    tokio::time::sleep(backoff).await;
+                    self.polled = false;
+                }
+            }
+        }
+    }
+}
+
+// Endpoints of a comma-separated list of RPC URLs (e.g. `ws://..., ipc:///path/to/node.ipc`),
+// in order of preference
+pub fn parse_rpc_urls(rpc_urls: &str) -> Result<Vec<Url>> {
+    Ok(rpc_urls.split(',').map(|rpc_url| Url::parse(rpc_url.trim())).collect::<Result<_, _>>()?)
+}
+
+// Filters on the indexed topics following the event signature (topic1 to topic3).
+// An empty topic matches any value.
+fn with_topics(filter: Filter, topics: [Vec<B256>; 3]) -> Filter {
+    let [topic1, topic2, topic3] = topics;
+    let mut filter = filter;
+    if !topic1.is_empty() {
+        filter = filter.topic1(topic1);
+    }
+    if !topic2.is_empty() {
+        filter = filter.topic2(topic2);
+    }
+    if !topic3.is_empty() {
+        filter = filter.topic3(topic3);
     }
+    filter
 }
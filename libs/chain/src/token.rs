@@ -0,0 +1,60 @@
+use alloy::contract::Error as ContractError;
+use alloy::primitives::Address;
+use alloy::sol;
+use alloy::transports::{RpcError, TransportErrorKind};
+
+use crate::rpc::NodeClient;
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20Metadata {
+        function name() external view returns (string);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+}
+
+// Optional ERC-20 metadata, missing when a token does not implement (or reverts on) a getter
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+impl NodeClient {
+    // Each getter is a request of its own, failed over and retried like any other
+    pub async fn get_token_metadata(
+        &self,
+        contract_address: Address,
+    ) -> Result<TokenMetadata, RpcError<TransportErrorKind>> {
+        let name = self
+            .request(|provider| async move {
+                optional(IERC20Metadata::new(contract_address, provider).name().call().await)
+            })
+            .await?;
+        let symbol = self
+            .request(|provider| async move {
+                optional(IERC20Metadata::new(contract_address, provider).symbol().call().await)
+            })
+            .await?;
+        let decimals = self
+            .request(|provider| async move {
+                optional(IERC20Metadata::new(contract_address, provider).decimals().call().await)
+            })
+            .await?;
+        Ok(TokenMetadata { name, symbol, decimals })
+    }
+}
+
+// Execution and decoding errors mean the getter is not supported (e.g. `bytes32` names),
+// transport errors are raised so that the call can be retried
+fn optional<T>(
+    result: Result<T, ContractError>,
+) -> Result<Option<T>, RpcError<TransportErrorKind>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ContractError::TransportError(e)) if !e.is_error_resp() => Err(e),
+        Err(_) => Ok(None),
+    }
+}
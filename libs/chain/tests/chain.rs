@@ -76,7 +76,8 @@ mod tests {
         let mut previous_events_logs_stream = node_client
             .watch_logs(
                 ValueOrArray::Value(*contract.address()),
-                "Transfer(address,address,uint256)",
+                ["Transfer(address,address,uint256)"],
+                Default::default(),
                 BlockNumberOrTag::Number(latest_block),
                 Duration::from_millis(100),
             )
@@ -102,7 +103,8 @@ mod tests {
         let mut previous_event_logs_stream = node_client
             .watch_logs(
                 ValueOrArray::Value(*contract.address()),
-                "Transfer(address,address,uint256)",
+                ["Transfer(address,address,uint256)"],
+                Default::default(),
                 BlockNumberOrTag::Latest,
                 Duration::from_millis(100),
             )
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use eyre::Result;
+
+    use alloy::{signers::local::PrivateKeySigner, transports::http::reqwest::Url};
+    use serde_json::{Value, json};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, UnixListener, UnixStream},
+    };
+
+    use chain::rpc::{NodeClient, parse_rpc_urls};
+
+    // Serves every JSON-RPC request with the given result, over a Unix domain socket
+    async fn spawn_ipc_node(path: &Path, result: &'static str) -> Result<()> {
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path)?;
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, result));
+            }
+        });
+        Ok(())
+    }
+
+    async fn serve(mut stream: UnixStream, result: &'static str) -> Result<()> {
+        let mut buffer = vec![];
+        let mut chunk = [0u8; 4096];
+        loop {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            // Requests are concatenated JSON values, possibly split over reads
+            let mut requests = serde_json::Deserializer::from_slice(&buffer).into_iter::<Value>();
+            let mut responses = String::new();
+            while let Some(Ok(request)) = requests.next() {
+                responses +=
+                    &json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+            }
+            let parsed = requests.byte_offset();
+            buffer.drain(..parsed);
+            stream.write_all(responses.as_bytes()).await?;
+        }
+    }
+
+    fn socket_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.ipc", std::process::id()))
+    }
+
+    #[test]
+    fn test_parse_mixed_rpc_urls() -> Result<()> {
+        let rpc_urls = parse_rpc_urls(
+            "http://localhost:8545, wss://node.example/v3/key ,ipc:///tmp/reth.ipc",
+        )?;
+        let schemes: Vec<&str> = rpc_urls.iter().map(Url::scheme).collect();
+        assert_eq!(schemes, ["http", "wss", "ipc"]);
+        assert_eq!(rpc_urls[1].path(), "/v3/key");
+        // The socket path follows the `ipc://` scheme
+        assert_eq!(rpc_urls[2].host_str(), None);
+        assert_eq!(rpc_urls[2].path(), "/tmp/reth.ipc");
+
+        assert!(parse_rpc_urls("http://localhost:8545,").is_err());
+        assert!(parse_rpc_urls("http://localhost:8545, //node.example").is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connect_ipc_url() -> Result<()> {
+        let path = socket_path("endpoint-ipc");
+        spawn_ipc_node(&path, "0x7a69").await?;
+
+        let rpc_url = Url::parse(&format!("ipc://{}", path.display()))?;
+        let node_client = NodeClient::connect(rpc_url, PrivateKeySigner::random()).await?;
+        assert_eq!(node_client.current_endpoint(), format!("ipc://{}", path.display()));
+        assert_eq!(node_client.get_chain_id().await?, 31337);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connect_mixed_rpc_urls() -> Result<()> {
+        let path = socket_path("endpoint-mixed");
+        spawn_ipc_node(&path, "0x7a69").await?;
+        // WebSocket endpoint nothing listens on
+        let closed = TcpListener::bind("127.0.0.1:0").await?.local_addr()?;
+
+        // Endpoints unreachable at startup are left out, the others kept in order
+        let rpc_urls =
+            parse_rpc_urls(&format!("ws://{closed}, ipc://{}, http://{closed}", path.display()))?;
+        let node_client = NodeClient::connect_all(rpc_urls, PrivateKeySigner::random()).await?;
+        assert_eq!(node_client.current_endpoint(), format!("ipc://{}", path.display()));
+        assert_eq!(node_client.get_chain_id().await?, 31337);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use eyre::Result;
+
+    use alloy::eips::BlockNumberOrTag;
+    use alloy::primitives::Address;
+    use alloy::rpc::types::ValueOrArray;
+    use alloy::{signers::local::PrivateKeySigner, transports::http::reqwest::Url};
+    use futures_util::StreamExt;
+    use serde_json::{Value, json};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::{TcpListener, TcpStream},
+    };
+
+    use chain::retry::RetryPolicy;
+    use chain::rpc::{EndpointMetrics, NodeClient};
+
+    // Result of a JSON-RPC request, or the code of its error
+    type Handler = dyn Fn(&str) -> std::result::Result<Value, i64> + Send + Sync;
+
+    // Serves every JSON-RPC request with the given result, over HTTP
+    async fn spawn_node(result: &'static str) -> Result<Url> {
+        spawn_handler(move |_| Ok(json!(result))).await
+    }
+
+    // Serves JSON-RPC requests with the result of the handler for their method, over HTTP
+    async fn spawn_handler<F>(handler: F) -> Result<Url>
+    where
+        F: Fn(&str) -> std::result::Result<Value, i64> + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = Url::parse(&format!("http://{}", listener.local_addr()?))?;
+        let handler: Arc<Handler> = Arc::new(handler);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&handler)));
+            }
+        });
+        Ok(url)
+    }
+
+    async fn serve(stream: TcpStream, handler: Arc<Handler>) -> Result<()> {
+        let mut stream = BufReader::new(stream);
+        loop {
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await? == 0 {
+                    return Ok(());
+                }
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse()?;
+                }
+            }
+            let mut body = vec![0; content_length];
+            stream.read_exact(&mut body).await?;
+            let request: Value = serde_json::from_slice(&body)?;
+            let response = match handler(request["method"].as_str().unwrap_or_default()) {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                Err(code) => json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": { "code": code, "message": "failed" },
+                }),
+            }
+            .to_string();
+            let http_response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+                response.len()
+            );
+            stream.get_mut().write_all(http_response.as_bytes()).await?;
+        }
+    }
+
+    // Address nothing listens on
+    async fn closed_endpoint() -> Result<Url> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        Ok(Url::parse(&format!("http://{}", listener.local_addr()?))?)
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_next_endpoint() -> Result<()> {
+        let down_url = closed_endpoint().await?;
+        let up_url = spawn_node("0x7a69").await?;
+        let node_client = NodeClient::connect_all(
+            vec![down_url.clone(), up_url.clone()],
+            PrivateKeySigner::random(),
+        )
+        .await?;
+
+        // Requests are sent to the first endpoint, then moved to the next one once it failed
+        assert_eq!(node_client.current_endpoint(), format!("http://{}", down_url.authority()));
+        assert_eq!(node_client.get_chain_id().await?, 31337);
+        assert_eq!(node_client.current_endpoint(), format!("http://{}", up_url.authority()));
+
+        // The switch is counted, requests staying on the healthy endpoint
+        assert_eq!(
+            node_client.endpoint_metrics(),
+            EndpointMetrics {
+                active_endpoint: format!("http://{}", up_url.authority()),
+                healthy_endpoints: 1,
+                failovers: 1,
+            }
+        );
+        node_client.get_chain_id().await?;
+        assert_eq!(node_client.endpoint_metrics().failovers, 1);
+
+        Ok(())
+    }
+
+    // Node whose filter changes always fail with an internal error, counting requests per method
+    async fn failing_filter_node(
+        new_filter_error: Option<i64>,
+    ) -> Result<(Url, Arc<AtomicUsize>, Arc<AtomicUsize>)> {
+        let (new_filters, polls) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (new_filters_seen, polls_seen) = (Arc::clone(&new_filters), Arc::clone(&polls));
+        let url = spawn_handler(move |method| match method {
+            "eth_newFilter" => match new_filters_seen.fetch_add(1, Ordering::Relaxed) {
+                0 => Ok(json!("0x1")),
+                _ => new_filter_error.map_or(Ok(json!("0x2")), Err),
+            },
+            "eth_getFilterChanges" => {
+                polls_seen.fetch_add(1, Ordering::Relaxed);
+                Err(-32603)
+            }
+            _ => Err(-32601),
+        })
+        .await?;
+        Ok((url, new_filters, polls))
+    }
+
+    #[tokio::test]
+    async fn test_log_watch_ends_once_retries_exhausted() -> Result<()> {
+        let retry_policy = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(10),
+        };
+        let watch = |url: Url| async move {
+            NodeClient::new(url, PrivateKeySigner::random())
+                .with_retry_policy(retry_policy)
+                .watch_logs(
+                    ValueOrArray::Value(Address::repeat_byte(0xAA)),
+                    ["Transfer(address,address,uint256)"],
+                    Default::default(),
+                    BlockNumberOrTag::Number(0),
+                    Duration::from_millis(1),
+                )
+                .await
+        };
+
+        // The filter is installed again after every failed poll, until retries are exhausted
+        let (url, new_filters, polls) = failing_filter_node(None).await?;
+        let mut logs = watch(url).await?;
+        assert!(tokio::time::timeout(Duration::from_secs(5), logs.next()).await?.is_none());
+        assert_eq!(polls.load(Ordering::Relaxed), 3);
+        assert_eq!(new_filters.load(Ordering::Relaxed), 3);
+
+        // The watch ends as soon as the filter cannot be installed again
+        let (url, new_filters, polls) = failing_filter_node(Some(-32602)).await?;
+        let mut logs = watch(url).await?;
+        assert!(tokio::time::timeout(Duration::from_secs(5), logs.next()).await?.is_none());
+        assert_eq!(polls.load(Ordering::Relaxed), 1);
+        assert_eq!(new_filters.load(Ordering::Relaxed), 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use alloy::rpc::json_rpc::ErrorPayload;
+    use alloy::transports::{HttpError, RpcError, TransportErrorKind};
+
+    use chain::retry::{ErrorClass, RetryPolicy, classify};
+
+    fn http_error(status: u16) -> RpcError<TransportErrorKind> {
+        RpcError::Transport(TransportErrorKind::HttpError(HttpError {
+            status,
+            body: String::new(),
+        }))
+    }
+
+    fn error_resp(code: i64, message: &'static str) -> RpcError<TransportErrorKind> {
+        RpcError::ErrorResp(ErrorPayload { code, message: message.into(), data: None })
+    }
+
+    #[test]
+    fn test_classify_rpc_errors() {
+        assert_eq!(classify(&http_error(429)), ErrorClass::RateLimited);
+        assert_eq!(classify(&error_resp(-32005, "limit exceeded")), ErrorClass::RateLimited);
+        assert_eq!(classify(&error_resp(-32000, "Rate limit reached")), ErrorClass::RateLimited);
+
+        assert_eq!(classify(&http_error(503)), ErrorClass::Transient);
+        assert_eq!(classify(&TransportErrorKind::backend_gone()), ErrorClass::Transient);
+        assert_eq!(classify(&error_resp(-32603, "internal error")), ErrorClass::Transient);
+
+        assert_eq!(classify(&http_error(401)), ErrorClass::Permanent);
+        assert_eq!(classify(&error_resp(-32602, "invalid params")), ErrorClass::Permanent);
+    }
+
+    #[test]
+    fn test_backoff_grows_jittered_up_to_max() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+        for _ in 0..100 {
+            let backoff = policy.backoff(2, ErrorClass::Transient);
+            assert!(backoff >= Duration::from_millis(200) && backoff <= Duration::from_millis(400));
+
+            // Rate limits back off longer, never beyond the max backoff
+            let backoff = policy.backoff(2, ErrorClass::RateLimited);
+            assert!(backoff >= Duration::from_millis(500) && backoff <= Duration::from_secs(1));
+            assert!(policy.backoff(30, ErrorClass::Transient) <= Duration::from_secs(1));
+        }
+    }
+}
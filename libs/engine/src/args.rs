@@ -5,7 +5,10 @@ use alloy::{
     rpc::types::ValueOrArray,
 };
 
-use crate::source::filter::EventType;
+use crate::{
+    source::filter::{EventType, Topics},
+    tip::BlockTip,
+};
 
 #[derive(Debug)]
 pub struct Args {
@@ -13,6 +16,8 @@ pub struct Args {
     pub addresses: ValueOrArray<Address>,
     // Event filter to watch
     pub event: EventType,
+    // Indexed topics filter to watch (e.g. transfers to given wallets)
+    pub topics: Topics,
     // Block from which start indexing.
     // If not provided, the engine starts at
     // latest known block that has been checkpointed
@@ -21,6 +26,12 @@ pub struct Args {
     pub checkpoint_interval: u64,
     // Positive number of blocks handled between checkpoints
     pub backfill_checkpoint_interval: Option<u64>,
+    // Number of blocks the chain head must be ahead of a block
+    // before its outcomes are forwarded to the sink.
+    // Zero means outcomes are forwarded instantly.
+    pub confirmations: u64,
+    // Block tag treated as the chain tip by both gapfill and live-watch
+    pub tip: BlockTip,
     // Throttling (rate-limit) node requests:
     // minimum time to wait between consecutive calls
     pub poll_interval: Duration,
@@ -1,29 +1,167 @@
+use alloy::primitives::{Address, BlockNumber};
 use eyre::{Result, eyre};
+use store::balance::store::Store as BalanceStore;
+use store::block::model::BlockHeader;
+use store::block::store::Store as BlockStore;
 use store::checkpoint::model::Checkpoint;
 use store::checkpoint::store::Store as CheckpointStore;
+use store::watched_address::model::WatchedAddress;
+use store::watched_address::store::Store as WatchedAddressStore;
 
 #[derive(Clone)]
 pub struct Checkpointer {
     store: CheckpointStore,
+    block_store: BlockStore,
+    // Snapshots the balances of the job at every checkpoint, if tracked
+    balance_store: Option<BalanceStore>,
+    // Persists the watch list changes and backfills of the job, if any
+    watched_address_store: Option<WatchedAddressStore>,
 }
 
 impl Checkpointer {
-    pub fn new(store: CheckpointStore) -> Self {
-        Self { store }
+    pub fn new(store: CheckpointStore, block_store: BlockStore) -> Self {
+        Self { store, block_store, balance_store: None, watched_address_store: None }
+    }
+
+    pub fn with_balance_snapshots(self, balance_store: BalanceStore) -> Self {
+        Self { balance_store: Some(balance_store), ..self }
+    }
+
+    pub fn with_watch_list(self, watched_address_store: WatchedAddressStore) -> Self {
+        Self { watched_address_store: Some(watched_address_store), ..self }
     }
 
     pub async fn get_last_checkpoint(&self) -> Result<Option<Checkpoint>> {
         self.store.get_last_checkpoint().await.map_err(|e| eyre!(e))
     }
 
+    pub async fn get_last_checkpoints(&self, limit: u64) -> Result<Vec<Checkpoint>> {
+        self.store.get_last_checkpoints(limit).await.map_err(|e| eyre!(e))
+    }
+
     pub async fn checkpoint(&self, checkpoint: &Checkpoint) -> Result<()> {
         match self.store.insert_checkpoint(checkpoint).await {
             Ok(_) => {
                 tracing::info!("Checkpoint saved {checkpoint:?}");
-                Ok(())
             }
             Err(e) => {
                 tracing::error!("Checkpointer failed on [insert_checkpoint]: {e:?}");
+                return Err(eyre!(e));
+            }
+        }
+        let Some(balance_store) = &self.balance_store else {
+            return Ok(());
+        };
+        match balance_store.snapshot_balances(checkpoint.block_number as u64).await {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Balances snapshotted {checkpoint:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Checkpointer failed on [snapshot_balances]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    pub async fn record_block(&self, block: &BlockHeader) -> Result<()> {
+        match self.block_store.insert_block(block).await {
+            Ok(_) => {
+                tracing::info!("Block recorded {block:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Checkpointer failed on [insert_block]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    pub async fn rollback(&self, block_number: BlockNumber) -> Result<()> {
+        match self.store.delete_checkpoints_after_block_number(block_number).await {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Checkpoints rolled back to {block_number:?}: {nbr_of_rows:?}");
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Checkpointer failed on [delete_checkpoints_after_block_number]: {e:?}"
+                );
+                return Err(eyre!(e));
+            }
+        }
+        match self.block_store.delete_blocks_after_block_number(block_number).await {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Blocks rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Checkpointer failed on [delete_blocks_after_block_number]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    // Watch list changes made while running, restored on restart
+    pub async fn get_watch_list(&self) -> Result<Vec<WatchedAddress>> {
+        let Some(store) = &self.watched_address_store else {
+            return Ok(vec![]);
+        };
+        store.get_watched_addresses().await.map_err(|e| eyre!(e))
+    }
+
+    pub async fn watch_address(
+        &self,
+        address: Address,
+        backfill: Option<(BlockNumber, BlockNumber)>,
+    ) -> Result<()> {
+        let Some(store) = &self.watched_address_store else {
+            return Ok(());
+        };
+        match store.insert_watched_address(address.as_slice(), backfill).await {
+            Ok(_) => {
+                tracing::info!("Watched address saved {address:?}, backfill: {backfill:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Checkpointer failed on [insert_watched_address]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    pub async fn unwatch_address(&self, address: Address) -> Result<()> {
+        let Some(store) = &self.watched_address_store else {
+            return Ok(());
+        };
+        match store.remove_watched_address(address.as_slice()).await {
+            Ok(_) => {
+                tracing::info!("Unwatched address saved {address:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Checkpointer failed on [remove_watched_address]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    // Backfills of added addresses bypass the engine state: each has its own checkpoint,
+    // the next block to backfill
+    pub async fn checkpoint_backfill(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+    ) -> Result<()> {
+        let Some(store) = &self.watched_address_store else {
+            return Ok(());
+        };
+        match store.update_backfill_from_block(address.as_slice(), from_block).await {
+            Ok(_) => {
+                tracing::info!("Backfill checkpoint saved {address:?}: {from_block:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Checkpointer failed on [update_backfill_from_block]: {e:?}");
                 Err(eyre!(e))
             }
         }
@@ -9,7 +9,7 @@ use std::sync::Arc;
 use sync::consumer::Consumer;
 use tokio::sync::{broadcast, mpsc};
 
-async fn consume_event_outcome<T: Debug>(
+pub(crate) async fn consume_event_outcome<T: Debug>(
     event: Event<T>,
     checkpointer: &Checkpointer,
     sink: &dyn Sink<Item = T>,
@@ -24,6 +24,10 @@ async fn consume_event_outcome<T: Debug>(
             let checkpoint = block.as_ref();
             checkpointer.checkpoint(&checkpoint.into()).await
         }
+        Event::Block(block) => {
+            tracing::info!("Consumer consumed block: {:?}", block.block_number);
+            checkpointer.record_block(&block).await
+        }
         Event::Element(e) => {
             tracing::info!("Consumer consumed element: {e:?}");
             sink.process(&e).await
@@ -40,6 +44,11 @@ async fn consume_event_outcome<T: Debug>(
                 sink.process_batch(&events).await
             }
         }
+        Event::Rollback(block_number) => {
+            tracing::warn!("Consumer consumed rollback to block number: {block_number:?}");
+            checkpointer.rollback(block_number).await?;
+            sink.rollback(block_number, &[]).await
+        }
     }
 }
 
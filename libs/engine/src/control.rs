@@ -0,0 +1,288 @@
+use crate::args::Args;
+use crate::checkpointer::Checkpointer;
+use crate::sink::handle::Sink;
+use crate::source::filter::{ChunkFilter, EventType, Topics};
+use crate::source::handle::{Source, SourceInput};
+use crate::state::logic::{self, State};
+use crate::state::outcome::Outcome;
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, BlockNumber},
+    rpc::types::ValueOrArray,
+};
+use chain::rpc::NodeClient;
+use eyre::{Result, eyre};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+use store::watched_address::model::WatchedAddress;
+use tokio::sync::{Mutex, mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+
+// Changes to the watched addresses, sent to a running engine
+#[derive(Debug)]
+pub enum Command {
+    // Watches an address, backfilling its outcomes from the given block
+    // (or from the current block when not provided)
+    AddAddress {
+        address: Address,
+        from_block: Option<BlockNumber>,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    RemoveAddress {
+        address: Address,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    ListAddresses {
+        reply: oneshot::Sender<Vec<Address>>,
+    },
+}
+
+// Handle to send commands to a running engine, cheap to clone
+#[derive(Debug, Clone)]
+pub struct ControlHandle {
+    tx: mpsc::Sender<Command>,
+}
+
+impl ControlHandle {
+    pub async fn add_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumber>,
+    ) -> Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.send(Command::AddAddress { address, from_block, reply }).await?;
+        rx.await?
+    }
+
+    pub async fn remove_address(&self, address: Address) -> Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.send(Command::RemoveAddress { address, reply }).await?;
+        rx.await?
+    }
+
+    pub async fn list_addresses(&self) -> Result<Vec<Address>> {
+        let (reply, rx) = oneshot::channel();
+        self.send(Command::ListAddresses { reply }).await?;
+        Ok(rx.await?)
+    }
+
+    async fn send(&self, command: Command) -> Result<()> {
+        self.tx.send(command).await.map_err(|_| eyre!("Engine is not running"))
+    }
+}
+
+struct Control<E, T> {
+    // None when the engine watches every contract
+    addresses_tx: Option<watch::Sender<Vec<Address>>>,
+    event: EventType,
+    topics: Topics,
+    backfill_chunk_size: u64,
+    shared_state: Arc<Mutex<State>>,
+    node_client: NodeClient,
+    checkpointer: Checkpointer,
+    source: Arc<dyn Source<Item = E>>,
+    sink: Arc<dyn Sink<Item = T>>,
+}
+
+// Applies the watch list changes persisted by a previous run to the configured addresses
+pub(crate) fn restore_watch_list(addresses: &mut Vec<Address>, watch_list: &[WatchedAddress]) {
+    for watched_address in watch_list {
+        let Ok(address) = Address::try_from(watched_address.address.as_slice()) else {
+            continue;
+        };
+        if watched_address.watched && !addresses.contains(&address) {
+            addresses.push(address);
+        } else if !watched_address.watched && addresses.len() > 1 {
+            addresses.retain(|watched| *watched != address);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn<E, T>(
+    args: &Args,
+    addresses_tx: Option<watch::Sender<Vec<Address>>>,
+    watch_list: &[WatchedAddress],
+    shared_state: Arc<Mutex<State>>,
+    node_client: NodeClient,
+    checkpointer: Checkpointer,
+    source: Arc<dyn Source<Item = E>>,
+    sink: Arc<dyn Sink<Item = T>>,
+) -> (ControlHandle, JoinHandle<()>)
+where
+    E: SourceInput + TryInto<T> + Clone + Debug + Send + Sync + 'static,
+    <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
+    T: Outcome + TryFrom<E> + Send + Sync + 'static,
+{
+    let control = Arc::new(Control {
+        addresses_tx,
+        event: args.event.clone(),
+        topics: args.topics.clone(),
+        backfill_chunk_size: args
+            .backfill_checkpoint_interval
+            .unwrap_or(args.checkpoint_interval)
+            .max(1),
+        shared_state,
+        node_client,
+        checkpointer,
+        source,
+        sink,
+    });
+
+    // Backfills interrupted by a restart resume from their checkpoint
+    if control.addresses_tx.is_some() {
+        for watched_address in watch_list {
+            let (Some((from_block, to_block)), Ok(address)) = (
+                watched_address.pending_backfill(),
+                Address::try_from(watched_address.address.as_slice()),
+            ) else {
+                continue;
+            };
+            control.spawn_backfill(address, from_block as u64, to_block as u64);
+        }
+    }
+
+    let (tx, mut rx) = mpsc::channel::<Command>(16);
+    let handle = tokio::spawn(async move {
+        while let Some(command) = rx.recv().await {
+            match command {
+                Command::AddAddress { address, from_block, reply } => {
+                    let _ = reply.send(control.add_address(address, from_block).await);
+                }
+                Command::RemoveAddress { address, reply } => {
+                    let _ = reply.send(control.remove_address(address).await);
+                }
+                Command::ListAddresses { reply } => {
+                    let _ = reply.send(control.list_addresses());
+                }
+            }
+        }
+    });
+    (ControlHandle { tx }, handle)
+}
+
+impl<E, T> Control<E, T>
+where
+    E: SourceInput + TryInto<T> + Clone + Debug + Send + Sync + 'static,
+    <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
+    T: Outcome + TryFrom<E> + Send + Sync + 'static,
+{
+    fn addresses_tx(&self) -> Result<&watch::Sender<Vec<Address>>> {
+        self.addresses_tx
+            .as_ref()
+            .ok_or_else(|| eyre!("Engine watches every contract, addresses can not be changed"))
+    }
+
+    fn list_addresses(&self) -> Vec<Address> {
+        self.addresses_tx.as_ref().map(|tx| tx.borrow().clone()).unwrap_or_default()
+    }
+
+    async fn add_address(
+        self: &Arc<Self>,
+        address: Address,
+        from_block: Option<BlockNumber>,
+    ) -> Result<()> {
+        let addresses_tx = self.addresses_tx()?;
+        if addresses_tx.borrow().contains(&address) {
+            return Err(eyre!("Address {address:?} is already watched"));
+        }
+        let to_block = self.shared_state.lock().await.get_current_block_number();
+        // Blocks already handled by the engine are backfilled aside. The following ones, up to
+        // the chain head, are caught up by the re-subscribed stream (see `WatchListSource`):
+        // they go through the engine state, for checkpoints, confirmations and reorgs
+        let backfill = from_block
+            .filter(|from_block| *from_block <= to_block)
+            .map(|from_block| (from_block, to_block));
+        self.checkpointer.watch_address(address, backfill).await?;
+        addresses_tx.send_modify(|addresses| addresses.push(address));
+        tracing::info!("Control added watched address: {address:?}");
+
+        if let Some((from_block, to_block)) = backfill {
+            self.spawn_backfill(address, from_block, to_block);
+        }
+        Ok(())
+    }
+
+    fn spawn_backfill(
+        self: &Arc<Self>,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) {
+        let control = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = control.backfill(address, from_block, to_block).await {
+                tracing::error!("Control failed to backfill {address:?}: {e:?}");
+            }
+        });
+    }
+
+    async fn remove_address(&self, address: Address) -> Result<()> {
+        let addresses_tx = self.addresses_tx()?;
+        let addresses = addresses_tx.borrow().clone();
+        if !addresses.contains(&address) {
+            return Err(eyre!("Address {address:?} is not watched"));
+        }
+        if addresses.len() == 1 {
+            return Err(eyre!("Address {address:?} is the last watched address"));
+        }
+        self.checkpointer.unwatch_address(address).await?;
+        addresses_tx.send_modify(|addresses| addresses.retain(|watched| *watched != address));
+        tracing::info!("Control removed watched address: {address:?}");
+        Ok(())
+    }
+
+    // Sources the outcomes of a single address between two blocks, chunk by chunk,
+    // checkpointing the next block to backfill after each one.
+    // It bypasses the engine state, which is safe for blocks the engine already handled:
+    // they are covered by its checkpoints, and a reorg below them rolls back every contract
+    // before re-indexing the watched addresses, the added one included.
+    async fn backfill(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<()> {
+        tracing::info!(
+            "Control backfill of {address:?} started from: {from_block:?} to: {to_block:?}"
+        );
+        let mut chunk_from_block = from_block;
+        while chunk_from_block <= to_block {
+            let chunk_to_block =
+                chunk_from_block.saturating_add(self.backfill_chunk_size - 1).min(to_block);
+            let filter = ChunkFilter {
+                addresses: ValueOrArray::Value(address),
+                event: self.event.clone(),
+                topics: self.topics.clone(),
+                from_block_number: BlockNumberOrTag::Number(chunk_from_block),
+                to_block_number: BlockNumberOrTag::Number(chunk_to_block),
+            };
+
+            let mut outcomes: Vec<T> = vec![];
+            let mut blocks = HashSet::new();
+            for input in self.source.chunk(filter).await? {
+                // Headers of blocks holding outcomes of the address only were not recorded
+                if blocks.insert(input.block_hash())
+                    && let Some(block_header) =
+                        logic::block_header(&input, &self.node_client).await?
+                {
+                    self.checkpointer.record_block(&block_header).await?;
+                }
+                match input.clone().try_into() {
+                    Ok(outcome) => outcomes.push(outcome),
+                    Err(e) => tracing::error!(
+                        "Skip: Failed to convert backfilled input: {input:?} - reason {e:?}"
+                    ),
+                }
+            }
+            if !outcomes.is_empty() {
+                self.sink.process_batch(&outcomes).await?;
+            }
+            chunk_from_block = chunk_to_block + 1;
+            self.checkpointer.checkpoint_backfill(address, chunk_from_block).await?;
+        }
+        tracing::info!("Control backfill of {address:?} finished at: {to_block:?}");
+        Ok(())
+    }
+}
@@ -1,24 +1,28 @@
 use crate::args::Args;
 use crate::checkpointer::Checkpointer;
+use crate::control::{self, ControlHandle};
 use crate::gapfiller;
 use crate::sink::handle::Sink;
 use crate::source::handle::{Source, SourceInput};
+use crate::source::watch_list::WatchListSource;
 use crate::state::event::Events;
 use crate::state::logic;
 use crate::state::outcome::Outcome;
 use crate::{consumer, live_watcher};
-use alloy::rpc::types::Block;
+use alloy::rpc::types::{Block, ValueOrArray};
 use chain::rpc::NodeClient;
-use eyre::{Result, eyre};
+use eyre::Result;
 use std::fmt::Debug;
 use std::sync::Arc;
-use tokio::sync::{Mutex, broadcast, mpsc};
+use tokio::sync::{Mutex, broadcast, mpsc, watch};
 use tokio::task::JoinHandle;
 
 pub struct Engine {
     shutdown_tx: broadcast::Sender<()>,
     consumer_handle: JoinHandle<()>,
     producer_handle: JoinHandle<()>,
+    control: ControlHandle,
+    control_handle: JoinHandle<()>,
 }
 
 fn channel_size(args: &Args) -> usize {
@@ -27,7 +31,7 @@ fn channel_size(args: &Args) -> usize {
     // expected block/event rate
     let avg_events_per_block: usize = 50;
     // number of blocks to wait before considering a block irreversible
-    let finality: usize = 12;
+    let finality: usize = args.confirmations as usize;
     let channel_size: usize = std::cmp::max(finality, args.checkpoint_interval as usize)
         * avg_events_per_block
         * burst_factor;
@@ -47,13 +51,35 @@ impl Engine {
         <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
         T: Outcome + TryFrom<E> + Debug + Send + Sync + 'static,
     {
-        // Lookup latest block
-        let block_tip: Block =
-            node_client.get_latest_block().await?.ok_or_else(|| eyre!("Latest block not found"))?;
+        // Lookup tip block
+        let block_tip: Block = args.tip.get_block(node_client).await?;
         let tip_number = block_tip.number();
-        tracing::info!("Engine started at block tip number: {tip_number:?}");
-
-        let state = logic::init_state(&block_tip, args.from_block, checkpointer).await?;
+        let tip = args.tip;
+        tracing::info!("Engine started at {tip:?} block tip number: {tip_number:?}");
+
+        // Watched addresses may change at runtime, unless every contract is watched
+        let mut watched_addresses = match &args.addresses {
+            ValueOrArray::Value(address) => vec![*address],
+            ValueOrArray::Array(addresses) => addresses.clone(),
+        };
+        let watch_list = checkpointer.get_watch_list().await?;
+        let (addresses_tx, watched_source) = if watched_addresses.is_empty() {
+            (None, Arc::clone(&source))
+        } else {
+            control::restore_watch_list(&mut watched_addresses, &watch_list);
+            let (addresses_tx, addresses_rx) = watch::channel(watched_addresses);
+            let watched_source: Arc<dyn Source<Item = E>> =
+                Arc::new(WatchListSource { source: Arc::clone(&source), addresses: addresses_rx });
+            (Some(addresses_tx), watched_source)
+        };
+
+        let mut state = logic::init_state(&block_tip, args.from_block, checkpointer).await?;
+
+        // Roll back blocks reorganized while the engine was down
+        if let Some(ancestor_block_number) = state.find_common_ancestor(node_client).await? {
+            let rollback_event = state.roll_back(ancestor_block_number);
+            consumer::consume_event_outcome(rollback_event, checkpointer, sink.as_ref()).await?;
+        }
 
         // Wrap in a Arc + Mutex for interior mutability.
         // * Arc, allows sharing across async tasks/closures.
@@ -83,7 +109,7 @@ impl Engine {
             gapfill_shutdown_tx.clone(),
             Arc::clone(&shared_state),
             Arc::new(node_client.clone()),
-            Arc::clone(&source),
+            Arc::clone(&watched_source),
         )
         .await?;
 
@@ -116,15 +142,33 @@ impl Engine {
             shutdown_tx.clone(),
             Arc::clone(&shared_state),
             Arc::new(node_client.clone()),
-            Arc::clone(&source),
+            Arc::clone(&watched_source),
         )
         .await?;
 
-        Ok(Self { shutdown_tx, consumer_handle, producer_handle })
+        // 3. Run control of the watched addresses (backfilled from the unwrapped source)
+        let (control, control_handle) = control::spawn(
+            args,
+            addresses_tx,
+            &watch_list,
+            Arc::clone(&shared_state),
+            node_client.clone(),
+            checkpointer.clone(),
+            Arc::clone(&source),
+            Arc::clone(&sink),
+        );
+
+        Ok(Self { shutdown_tx, consumer_handle, producer_handle, control, control_handle })
+    }
+
+    // Handle to add or remove watched addresses while running
+    pub fn control(&self) -> ControlHandle {
+        self.control.clone()
     }
 
     // Send shutdown signal and wait for both producer and consumer to finish
     pub async fn shutdown(self) {
+        self.control_handle.abort();
         let _ = self.shutdown_tx.send(());
         let _ = self.producer_handle.await;
         let _ = self.consumer_handle.await;
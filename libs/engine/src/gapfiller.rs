@@ -27,10 +27,12 @@ where
     T: Outcome + TryFrom<E> + Send + Sync + 'static,
 {
     let checkpoint_interval = args.backfill_checkpoint_interval.unwrap_or(args.checkpoint_interval);
-    let latest_block_number = block_tip.number();
+    // Blocks not yet confirmed are left to the live-watcher
+    let latest_block_number = block_tip.number().saturating_sub(args.confirmations);
 
     let shared_addresses = args.addresses.clone();
     let shared_event = args.event.clone();
+    let shared_topics = args.topics.clone();
 
     let producer_callback = move || {
         let state_for_producer = Arc::clone(&shared_state);
@@ -39,6 +41,7 @@ where
 
         let address_for_producer = shared_addresses.clone();
         let event_for_producer = shared_event.clone();
+        let topics_for_producer = shared_topics.clone();
 
         async move {
             let from_block_number = state_for_producer.lock().await.get_current_block_number() + 1;
@@ -56,6 +59,7 @@ where
             let chunk_filter = ChunkFilter {
                 addresses: address_for_producer,
                 event: event_for_producer,
+                topics: topics_for_producer,
                 from_block_number: from_block_number.into(),
                 to_block_number: chunk_block_number.into(),
             };
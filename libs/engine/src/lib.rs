@@ -1,20 +1,40 @@
 pub mod args;
 pub mod checkpointer;
 pub mod consumer;
+pub mod control;
 pub mod engine;
 pub mod gapfiller;
 pub mod live_watcher;
 pub mod pubsub {}
+pub mod replay;
 pub mod sink {
+    pub mod any_transfer;
+    pub mod approval;
+    pub mod balance;
+    pub mod event_log;
     pub mod handle;
+    pub mod nft_transfer;
+    pub mod raw_log;
+    pub mod token;
+    pub mod transaction;
     pub mod transfer;
+    pub mod transfer_batch;
+    pub mod transfer_single;
 }
 pub mod source {
+    pub mod abi;
+    pub mod factory;
     pub mod filter;
     pub mod handle;
     pub mod log;
+    pub mod quorum;
+    pub mod raw_log;
+    pub mod watch_list;
+    pub mod ws_log;
 }
+pub mod tip;
 pub mod state {
+    pub mod buffer;
     pub mod event;
     pub mod logic;
     pub mod outcome;
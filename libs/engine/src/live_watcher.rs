@@ -1,9 +1,13 @@
 use crate::args::Args;
-use crate::source::filter::StreamFilter;
+use crate::source::filter::{ChunkFilter, EventType, StreamFilter, Topics};
 use crate::source::handle::{Source, SourceInput};
+use crate::state::buffer::ConfirmationBuffer;
 use crate::state::event::Events;
 use crate::state::logic::State;
 use crate::state::outcome::Outcome;
+use crate::tip::BlockTip;
+use alloy::primitives::Address;
+use alloy::rpc::types::ValueOrArray;
 use chain::rpc::NodeClient;
 use eyre::{Result, eyre};
 use futures_util::StreamExt;
@@ -25,13 +29,25 @@ where
     <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
     T: Outcome + TryFrom<E> + Send + Sync + 'static,
 {
+    if args.tip != BlockTip::Latest {
+        return Ok(spawn_tip_follower(args, tx, shutdown_tx, shared_state, node_client, source));
+    }
+
     let checkpoint_interval = args.checkpoint_interval;
+    let poll_interval = args.poll_interval;
+
+    let shared_addresses = args.addresses.clone();
+    let shared_event = args.event.clone();
+    let shared_topics = args.topics.clone();
 
     let stream_filter = StreamFilter {
         addresses: args.addresses.clone(),
         event: args.event.clone(),
+        topics: args.topics.clone(),
         from_block_number: shared_state.lock().await.get_current_block_number().into(),
         poll_interval: args.poll_interval,
+        tip: args.tip,
+        confirmations: args.confirmations,
     };
     let inputs_stream = source.stream(stream_filter).await?;
 
@@ -40,23 +56,80 @@ where
     // * Mutex, gives async mutable access:
     let shared_inputs_stream = Arc::new(Mutex::new(inputs_stream));
 
+    // Outcomes are only buffered when waiting for confirmations
+    let shared_buffer = match args.confirmations {
+        0 => None,
+        confirmations => Some(Arc::new(Mutex::new(ConfirmationBuffer::new(confirmations)))),
+    };
+
     // A closure that returns a future
     let producer_callback = move || {
         let inputs_stream_for_producer = Arc::clone(&shared_inputs_stream);
         let node_client_for_producer = Arc::clone(&node_client);
         let state_for_producer = Arc::clone(&shared_state);
+        let source_for_producer = Arc::clone(&source);
+        let buffer_for_producer = shared_buffer.clone();
+
+        let address_for_producer = shared_addresses.clone();
+        let event_for_producer = shared_event.clone();
+        let topics_for_producer = shared_topics.clone();
+
         async move {
-            match inputs_stream_for_producer.lock().await.next().await {
-                Some(input) => {
-                    state_for_producer
-                        .lock()
+            let mut inputs_stream = inputs_stream_for_producer.lock().await;
+            let node_client = node_client_for_producer.as_ref();
+            match buffer_for_producer {
+                // Instant: forward outcomes as soon as inputs are received
+                None => match inputs_stream.next().await {
+                    Some(input) => {
+                        roll_forward_input(
+                            input,
+                            &mut *state_for_producer.lock().await,
+                            checkpoint_interval,
+                            node_client,
+                            source_for_producer.as_ref(),
+                            address_for_producer,
+                            event_for_producer,
+                            topics_for_producer,
+                        )
                         .await
-                        .roll_forward(input, checkpoint_interval, node_client_for_producer.as_ref())
-                        .await
-                }
-                None => {
-                    tracing::error!("Stream ended");
-                    Err(eyre!("Stream ended"))
+                    }
+                    None => {
+                        tracing::error!("Stream ended");
+                        Err(eyre!("Stream ended"))
+                    }
+                },
+                // Confirmations: wait at most one poll interval for the next input,
+                // so that buffered outcomes keep being released as the chain head advances
+                Some(buffer) => {
+                    match tokio::time::timeout(poll_interval, inputs_stream.next()).await {
+                        Ok(Some(input)) => {
+                            let events = roll_forward_input(
+                                input,
+                                &mut *state_for_producer.lock().await,
+                                checkpoint_interval,
+                                node_client,
+                                source_for_producer.as_ref(),
+                                address_for_producer,
+                                event_for_producer,
+                                topics_for_producer,
+                            )
+                            .await?;
+                            buffer.lock().await.push(events);
+                        }
+                        Ok(None) => {
+                            tracing::error!("Stream ended");
+                            return Err(eyre!("Stream ended"));
+                        }
+                        Err(_elapsed) => {
+                            // no input received, just check the chain head
+                        }
+                    }
+                    let head_block_number = node_client.get_latest_block_number().await?;
+                    let mut buffer = buffer.lock().await;
+                    let released_events = buffer.release(head_block_number);
+                    let pending = buffer.len();
+                    tracing::debug!("Buffer pending {pending:?} at head: {head_block_number:?}");
+                    Ok(released_events)
                 }
             }
         }
@@ -66,3 +139,128 @@ where
     // and sends them rolled forward events to tx (consumer)
     Ok(Producer::spawn(tx, shutdown_tx, producer_callback))
 }
+
+// Follows a non-latest block tip (safe / finalized) by polling it,
+// fetching inputs in chunks as the tip advances
+fn spawn_tip_follower<E, T>(
+    args: &Args,
+    tx: mpsc::Sender<Result<Events<T>>>,
+    shutdown_tx: broadcast::Sender<()>,
+    shared_state: Arc<Mutex<State>>,
+    node_client: Arc<NodeClient>,
+    source: Arc<dyn Source<Item = E>>,
+) -> tokio::task::JoinHandle<()>
+where
+    E: SourceInput + TryInto<T> + Clone + Debug + Send + Sync + 'static,
+    <E as TryInto<T>>::Error: Debug + Send + Sync + 'static,
+    T: Outcome + TryFrom<E> + Send + Sync + 'static,
+{
+    let checkpoint_interval = args.checkpoint_interval;
+    let poll_interval = args.poll_interval;
+    let tip = args.tip;
+
+    let shared_addresses = args.addresses.clone();
+    let shared_event = args.event.clone();
+    let shared_topics = args.topics.clone();
+
+    // Last block number scanned for inputs.
+    // It advances even when scanned blocks hold no inputs.
+    let shared_scanned_block_number = Arc::new(Mutex::new(None::<u64>));
+
+    let producer_callback = move || {
+        let node_client_for_producer = Arc::clone(&node_client);
+        let state_for_producer = Arc::clone(&shared_state);
+        let source_for_producer = Arc::clone(&source);
+        let scanned_block_number_for_producer = Arc::clone(&shared_scanned_block_number);
+
+        let address_for_producer = shared_addresses.clone();
+        let event_for_producer = shared_event.clone();
+        let topics_for_producer = shared_topics.clone();
+
+        async move {
+            let node_client = node_client_for_producer.as_ref();
+            let mut state = state_for_producer.lock().await;
+            let mut scanned_block_number = scanned_block_number_for_producer.lock().await;
+
+            let from_block_number =
+                scanned_block_number.unwrap_or(state.get_current_block_number()) + 1;
+            let tip_block_number = tip.get_block(node_client).await?.number();
+            if from_block_number > tip_block_number {
+                // tip did not advance, wait before polling it again
+                tokio::time::sleep(poll_interval).await;
+                return Ok(Events(vec![]));
+            }
+
+            let chunk_block_number =
+                from_block_number.saturating_add(checkpoint_interval - 1).min(tip_block_number);
+            tracing::info!("Tip follower chunk: [{from_block_number:?}, {chunk_block_number:?}]");
+
+            let chunk_filter = ChunkFilter {
+                addresses: address_for_producer,
+                event: event_for_producer,
+                topics: topics_for_producer,
+                from_block_number: from_block_number.into(),
+                to_block_number: chunk_block_number.into(),
+            };
+            let source_inputs = source_for_producer.chunk(chunk_filter).await?;
+            let events =
+                state.roll_forward_batch(source_inputs, checkpoint_interval, node_client).await?;
+
+            *scanned_block_number = Some(chunk_block_number);
+            Ok(events)
+        }
+    };
+
+    // Spawn producer: produces received inputs from tip chunks
+    // and sends them rolled forward batch events to tx (consumer)
+    Producer::spawn(tx, shutdown_tx, producer_callback)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn roll_forward_input<E, T>(
+    input: E,
+    state: &mut State,
+    checkpoint_interval: u64,
+    node_client: &NodeClient,
+    source: &dyn Source<Item = E>,
+    addresses: ValueOrArray<Address>,
+    event: EventType,
+    topics: Topics,
+) -> Result<Events<T>>
+where
+    E: SourceInput + TryInto<T> + Clone + Debug,
+    <E as TryInto<T>>::Error: Debug,
+    T: Outcome + TryFrom<E>,
+{
+    match state.find_reorg(&input, node_client).await? {
+        None => state.roll_forward(input, checkpoint_interval, node_client).await,
+        Some(ancestor_block_number) => {
+            let rollback_event = state.roll_back(ancestor_block_number);
+
+            // Re-index the canonical branch up to the received input block
+            let from_block_number = ancestor_block_number + 1;
+            let to_block_number = input.block_number().unwrap_or(ancestor_block_number);
+            let source_inputs = if from_block_number <= to_block_number {
+                let chunk_filter = ChunkFilter {
+                    addresses,
+                    event,
+                    topics,
+                    from_block_number: from_block_number.into(),
+                    to_block_number: to_block_number.into(),
+                };
+                source.chunk(chunk_filter).await?
+            } else {
+                vec![]
+            };
+            let Events(reindexed_events) =
+                state.roll_forward_batch(source_inputs, checkpoint_interval, node_client).await?;
+            let Events(input_events) =
+                state.roll_forward(input, checkpoint_interval, node_client).await?;
+
+            let mut outcomes = vec![rollback_event];
+            outcomes.extend(reindexed_events);
+            outcomes.extend(input_events);
+            Ok(Events(outcomes))
+        }
+    }
+}
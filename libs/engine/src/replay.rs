@@ -0,0 +1,82 @@
+use crate::args::Args;
+use crate::sink::handle::Sink;
+use crate::source::filter::ChunkFilter;
+use crate::source::handle::{Source, SourceInput};
+use crate::state::outcome::Outcome;
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, BlockNumber},
+    rpc::types::ValueOrArray,
+};
+use eyre::Result;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+// Number of blocks re-derived per sink batch
+const REPLAY_CHUNK_SIZE: u64 = 1_000;
+
+// Re-derives outcomes from a source without watching the chain (e.g. the raw log archive):
+// outcomes of the replayed addresses from `args.from_block` on are rolled back first,
+// then processed again chunk by chunk.
+// Returns the number of re-derived outcomes.
+pub async fn replay<E, T>(
+    args: &Args,
+    to_block: BlockNumber,
+    source: Arc<dyn Source<Item = E>>,
+    sink: Arc<dyn Sink<Item = T>>,
+) -> Result<u64>
+where
+    E: SourceInput + TryInto<T> + Clone + Debug + Send + Sync + 'static,
+    <E as TryInto<T>>::Error: Debug,
+    T: Outcome + TryFrom<E> + Send + Sync + 'static,
+{
+    let from_block = args.from_block.unwrap_or_default();
+    tracing::info!("Replay started from block number: {from_block:?} to: {to_block:?}");
+
+    sink.rollback(from_block.saturating_sub(1), &addresses(args)).await?;
+
+    let mut nbr_of_outcomes = 0;
+    let mut chunk_from_block = from_block;
+    while chunk_from_block <= to_block {
+        let chunk_to_block = chunk_from_block.saturating_add(REPLAY_CHUNK_SIZE - 1).min(to_block);
+        let filter = ChunkFilter {
+            addresses: args.addresses.clone(),
+            event: args.event.clone(),
+            topics: args.topics.clone(),
+            from_block_number: BlockNumberOrTag::Number(chunk_from_block),
+            to_block_number: BlockNumberOrTag::Number(chunk_to_block),
+        };
+
+        let mut outcomes: Vec<T> = vec![];
+        for input in source.chunk(filter).await? {
+            match input.clone().try_into() {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(e) => {
+                    tracing::error!(
+                        "Skip: Failed to convert replayed input: {input:?} - reason {e:?}"
+                    )
+                }
+            }
+        }
+        if !outcomes.is_empty() {
+            sink.process_batch(&outcomes).await?;
+            nbr_of_outcomes += outcomes.len() as u64;
+        }
+        tracing::info!("Replayed up to block number: {chunk_to_block:?}");
+
+        chunk_from_block = chunk_to_block.saturating_add(1);
+        if chunk_to_block == u64::MAX {
+            break;
+        }
+    }
+
+    Ok(nbr_of_outcomes)
+}
+
+// Contract addresses replayed, none meaning every archived contract
+pub fn addresses(args: &Args) -> Vec<Address> {
+    match &args.addresses {
+        ValueOrArray::Value(address) => vec![*address],
+        ValueOrArray::Array(addresses) => addresses.clone(),
+    }
+}
@@ -0,0 +1,101 @@
+use alloy::primitives::{Address, BlockHash, BlockNumber, TxHash};
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use std::sync::Arc;
+use store::{nft_transfer::model::NftTransfer, transfer::model::Transfer};
+
+use crate::sink::handle::Sink;
+use crate::state::outcome::Outcome;
+
+// ERC-20 or ERC-721 transfer: both share the same signature,
+// ERC-721 also indexes its third parameter (token id) as a 4th topic
+#[derive(Debug, Clone)]
+pub enum AnyTransfer {
+    Fungible(Transfer),
+    NonFungible(NftTransfer),
+}
+
+impl TryFrom<Log> for AnyTransfer {
+    type Error = eyre::Report;
+
+    fn try_from(log: Log) -> Result<Self> {
+        match log.topics().len() {
+            3 => Transfer::try_from(log).map(AnyTransfer::Fungible),
+            4 => NftTransfer::try_from(log).map(AnyTransfer::NonFungible),
+            nbr_of_topics => Err(eyre!("not an ERC-20/721 transfer: {nbr_of_topics:?} topics")),
+        }
+    }
+}
+
+impl Outcome for AnyTransfer {
+    fn block_number(&self) -> BlockNumber {
+        match self {
+            AnyTransfer::Fungible(transfer) => transfer.block_number(),
+            AnyTransfer::NonFungible(nft_transfer) => nft_transfer.block_number(),
+        }
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        match self {
+            AnyTransfer::Fungible(transfer) => transfer.block_hash(),
+            AnyTransfer::NonFungible(nft_transfer) => nft_transfer.block_hash(),
+        }
+    }
+
+    fn contract_address(&self) -> Address {
+        match self {
+            AnyTransfer::Fungible(transfer) => transfer.contract_address(),
+            AnyTransfer::NonFungible(nft_transfer) => nft_transfer.contract_address(),
+        }
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        match self {
+            AnyTransfer::Fungible(transfer) => transfer.transaction_hash(),
+            AnyTransfer::NonFungible(nft_transfer) => nft_transfer.transaction_hash(),
+        }
+    }
+}
+
+// Routes ERC-20 transfers and ERC-721 transfers to their own sink
+pub struct AnyTransferSink {
+    pub transfer_sink: Arc<dyn Sink<Item = Transfer>>,
+    pub nft_transfer_sink: Arc<dyn Sink<Item = NftTransfer>>,
+}
+
+#[async_trait::async_trait]
+impl Sink for AnyTransferSink {
+    type Item = AnyTransfer;
+
+    async fn process(&self, any_transfer: &AnyTransfer) -> Result<()> {
+        match any_transfer {
+            AnyTransfer::Fungible(transfer) => self.transfer_sink.process(transfer).await,
+            AnyTransfer::NonFungible(nft_transfer) => {
+                self.nft_transfer_sink.process(nft_transfer).await
+            }
+        }
+    }
+
+    async fn process_batch(&self, any_transfers: &[AnyTransfer]) -> Result<()> {
+        let mut transfers = vec![];
+        let mut nft_transfers = vec![];
+        for any_transfer in any_transfers {
+            match any_transfer {
+                AnyTransfer::Fungible(transfer) => transfers.push(transfer.clone()),
+                AnyTransfer::NonFungible(nft_transfer) => nft_transfers.push(nft_transfer.clone()),
+            }
+        }
+        if !transfers.is_empty() {
+            self.transfer_sink.process_batch(&transfers).await?;
+        }
+        if !nft_transfers.is_empty() {
+            self.nft_transfer_sink.process_batch(&nft_transfers).await?;
+        }
+        Ok(())
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        self.transfer_sink.rollback(block_number, addresses).await?;
+        self.nft_transfer_sink.rollback(block_number, addresses).await
+    }
+}
@@ -0,0 +1,58 @@
+use alloy::primitives::{Address, BlockNumber};
+use eyre::{Result, eyre};
+use store::approval::{model::Approval, store::Store};
+
+use crate::sink::handle::{Sink, contract_addresses};
+
+pub struct ApprovalSink {
+    pub store: Store,
+}
+
+#[async_trait::async_trait]
+impl Sink for ApprovalSink {
+    type Item = Approval;
+
+    async fn process(&self, approval: &Approval) -> Result<()> {
+        match self.store.insert_approval(approval).await {
+            Ok(_) => {
+                tracing::info!("Processed: {approval:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_approval]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn process_batch(&self, approvals: &[Approval]) -> Result<()> {
+        match self.store.insert_approvals_batch(approvals).await {
+            Ok(_) => {
+                let nbr_of_rows = approvals.len();
+                tracing::info!("Processed batch: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_approvals_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_approvals_after_block_number(block_number, &contract_addresses(addresses))
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [delete_approvals_after_block_number]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use store::{balance::store::Store, transfer::model::Transfer};
+
+use crate::sink::transfer::{TransferSink, TransferStore};
+
+// Persists transfers and keeps the holders balances up to date
+pub type BalanceSink = TransferSink<Store>;
+
+#[async_trait::async_trait]
+impl TransferStore for Store {
+    async fn insert_transfer(&self, transfer: &Transfer) -> Result<()> {
+        Ok(Store::insert_transfer(self, transfer).await?)
+    }
+
+    async fn insert_transfers_batch(&self, transfers: &[Transfer]) -> Result<()> {
+        Ok(Store::insert_transfers_batch(self, transfers).await?)
+    }
+
+    async fn delete_transfers_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64> {
+        Ok(Store::delete_transfers_after_block_number(self, block_number, contract_addresses)
+            .await?)
+    }
+}
@@ -0,0 +1,60 @@
+use alloy::primitives::{Address, BlockNumber};
+use eyre::{Result, eyre};
+use store::event_log::{model::EventLog, store::Store};
+
+use crate::sink::handle::{Sink, contract_addresses};
+
+pub struct EventLogSink {
+    pub store: Store,
+}
+
+#[async_trait::async_trait]
+impl Sink for EventLogSink {
+    type Item = EventLog;
+
+    async fn process(&self, event_log: &EventLog) -> Result<()> {
+        match self.store.insert_event_log(event_log).await {
+            Ok(_) => {
+                tracing::info!("Processed: {event_log:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_event_log]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn process_batch(&self, event_logs: &[EventLog]) -> Result<()> {
+        match self.store.insert_event_logs_batch(event_logs).await {
+            Ok(_) => {
+                let nbr_of_rows = event_logs.len();
+                tracing::info!("Processed batch: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_event_logs_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_event_logs_after_block_number(block_number, &contract_addresses(addresses))
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Processor failed on [delete_event_logs_after_block_number]: {e:?}"
+                );
+                Err(eyre!(e))
+            }
+        }
+    }
+}
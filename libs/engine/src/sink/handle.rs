@@ -1,3 +1,4 @@
+use alloy::primitives::{Address, BlockNumber};
 use eyre::Result;
 
 #[async_trait::async_trait]
@@ -7,4 +8,13 @@ pub trait Sink: Send + Sync {
     async fn process(&self, element: &Self::Item) -> Result<()>;
 
     async fn process_batch(&self, elements: &[Self::Item]) -> Result<()>;
+
+    // Discards every processed element above the given block number,
+    // only the ones of the given contracts if any (e.g. replayed ones)
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()>;
+}
+
+// Contract addresses as stored, to scope the rolled back rows
+pub fn contract_addresses(addresses: &[Address]) -> Vec<Vec<u8>> {
+    addresses.iter().map(|address| address.to_vec()).collect()
 }
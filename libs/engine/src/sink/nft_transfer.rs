@@ -0,0 +1,60 @@
+use alloy::primitives::{Address, BlockNumber};
+use eyre::{Result, eyre};
+use store::nft_transfer::{model::NftTransfer, store::Store};
+
+use crate::sink::handle::{Sink, contract_addresses};
+
+pub struct NftTransferSink {
+    pub store: Store,
+}
+
+#[async_trait::async_trait]
+impl Sink for NftTransferSink {
+    type Item = NftTransfer;
+
+    async fn process(&self, nft_transfer: &NftTransfer) -> Result<()> {
+        match self.store.insert_nft_transfer(nft_transfer).await {
+            Ok(_) => {
+                tracing::info!("Processed: {nft_transfer:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_nft_transfer]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn process_batch(&self, nft_transfers: &[NftTransfer]) -> Result<()> {
+        match self.store.insert_nft_transfers_batch(nft_transfers).await {
+            Ok(_) => {
+                let nbr_of_rows = nft_transfers.len();
+                tracing::info!("Processed batch: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_nft_transfers_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_nft_transfers_after_block_number(block_number, &contract_addresses(addresses))
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Processor failed on [delete_nft_transfers_after_block_number]: {e:?}"
+                );
+                Err(eyre!(e))
+            }
+        }
+    }
+}
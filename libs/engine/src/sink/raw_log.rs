@@ -0,0 +1,140 @@
+use alloy::primitives::{Address, BlockHash, BlockNumber, TxHash};
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use std::sync::Arc;
+use store::event_log::model::{AbiLog, EventLog};
+use store::raw_log::{model::RawLog, store::Store};
+
+use crate::sink::handle::{Sink, contract_addresses};
+use crate::state::outcome::Outcome;
+
+// Raw log paired with the outcome decoded from it, none when the log does not decode:
+// the raw log is archived all the same, so that a later replay may decode it
+#[derive(Debug)]
+pub struct Archived<T> {
+    pub raw_log: RawLog,
+    pub outcome: Option<T>,
+}
+
+impl<T> Archived<T> {
+    fn new<E: std::fmt::Debug>(raw_log: RawLog, outcome: Result<T, E>) -> Self {
+        let outcome = match outcome {
+            Ok(outcome) => Some(outcome),
+            Err(e) => {
+                tracing::warn!("Archiving undecoded raw log: {raw_log:?} - reason {e:?}");
+                None
+            }
+        };
+        Self { raw_log, outcome }
+    }
+}
+
+impl<T> TryFrom<Log> for Archived<T>
+where
+    T: TryFrom<Log, Error = eyre::Report>,
+{
+    type Error = eyre::Report;
+
+    fn try_from(log: Log) -> Result<Self> {
+        let raw_log = RawLog::try_from(&log)?;
+        Ok(Self::new(raw_log, T::try_from(log)))
+    }
+}
+
+impl TryFrom<AbiLog> for Archived<EventLog> {
+    type Error = eyre::Report;
+
+    fn try_from(abi_log: AbiLog) -> Result<Self> {
+        let raw_log = RawLog::try_from(&abi_log.log)?;
+        Ok(Self::new(raw_log, EventLog::try_from(abi_log)))
+    }
+}
+
+impl<T> Outcome for Archived<T> {
+    fn block_number(&self) -> BlockNumber {
+        self.raw_log.block_number as u64
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.raw_log.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.raw_log.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.raw_log.transaction_hash)
+    }
+}
+
+// Decorates a sink: archives every raw log before processing the outcome decoded from it
+pub struct RawLogSink<T> {
+    sink: Arc<dyn Sink<Item = T>>,
+    store: Store,
+}
+
+impl<T> RawLogSink<T> {
+    pub fn new(sink: Arc<dyn Sink<Item = T>>, store: Store) -> Self {
+        Self { sink, store }
+    }
+
+    async fn archive(&self, raw_logs: &[RawLog]) -> Result<()> {
+        match self.store.insert_raw_logs_batch(raw_logs).await {
+            Ok(_) => {
+                let nbr_of_rows = raw_logs.len();
+                tracing::info!("Archived raw logs: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_raw_logs_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> Sink for RawLogSink<T>
+where
+    T: Clone + Send + Sync,
+{
+    type Item = Archived<T>;
+
+    async fn process(&self, element: &Archived<T>) -> Result<()> {
+        self.archive(std::slice::from_ref(&element.raw_log)).await?;
+        match &element.outcome {
+            Some(outcome) => self.sink.process(outcome).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn process_batch(&self, elements: &[Archived<T>]) -> Result<()> {
+        let raw_logs: Vec<RawLog> =
+            elements.iter().map(|element| element.raw_log.clone()).collect();
+        let outcomes: Vec<T> =
+            elements.iter().filter_map(|element| element.outcome.clone()).collect();
+        self.archive(&raw_logs).await?;
+        if outcomes.is_empty() {
+            return Ok(());
+        }
+        self.sink.process_batch(&outcomes).await
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_raw_logs_after_block_number(block_number, &contract_addresses(addresses))
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Raw logs rolled back to {block_number:?}: {nbr_of_rows:?}");
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [delete_raw_logs_after_block_number]: {e:?}");
+                return Err(eyre!(e));
+            }
+        }
+        self.sink.rollback(block_number, addresses).await
+    }
+}
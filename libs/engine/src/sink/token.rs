@@ -0,0 +1,76 @@
+use alloy::primitives::{Address, BlockNumber};
+use chain::rpc::NodeClient;
+use eyre::{Result, eyre};
+use std::collections::HashSet;
+use std::sync::Arc;
+use store::token::{model::Token, store::Store};
+use tokio::sync::Mutex;
+
+use crate::sink::handle::Sink;
+use crate::state::outcome::Outcome;
+
+// Decorates a sink: fetches the metadata (name, symbol, decimals) of
+// every contract address seen for the first time, before processing
+pub struct TokenMetadataSink<T> {
+    sink: Arc<dyn Sink<Item = T>>,
+    store: Store,
+    node_client: NodeClient,
+    // Contract addresses whose metadata is already stored
+    known_contracts: Mutex<HashSet<Address>>,
+}
+
+impl<T> TokenMetadataSink<T> {
+    pub fn new(sink: Arc<dyn Sink<Item = T>>, store: Store, node_client: NodeClient) -> Self {
+        Self { sink, store, node_client, known_contracts: Mutex::new(HashSet::new()) }
+    }
+
+    async fn fetch_token_metadata(&self, contract_addresses: HashSet<Address>) -> Result<()> {
+        let mut known_contracts = self.known_contracts.lock().await;
+        for contract_address in contract_addresses {
+            if known_contracts.contains(&contract_address) {
+                continue;
+            }
+            let stored = self.store.get_token(contract_address.as_slice()).await?;
+            if stored.is_none() {
+                let metadata = self.node_client.get_token_metadata(contract_address).await?;
+                let token = Token {
+                    contract_address: contract_address.to_vec(),
+                    name: metadata.name,
+                    symbol: metadata.symbol,
+                    decimals: metadata.decimals.map(i64::from),
+                };
+                match self.store.insert_token(&token).await {
+                    Ok(_) => tracing::info!("Fetched token metadata: {token:?}"),
+                    Err(e) => {
+                        tracing::error!("Processor failed on [insert_token]: {e:?}");
+                        return Err(eyre!(e));
+                    }
+                }
+            }
+            known_contracts.insert(contract_address);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> Sink for TokenMetadataSink<T>
+where
+    T: Outcome + Send + Sync,
+{
+    type Item = T;
+
+    async fn process(&self, element: &T) -> Result<()> {
+        self.fetch_token_metadata(HashSet::from([element.contract_address()])).await?;
+        self.sink.process(element).await
+    }
+
+    async fn process_batch(&self, elements: &[T]) -> Result<()> {
+        self.fetch_token_metadata(elements.iter().map(Outcome::contract_address).collect()).await?;
+        self.sink.process_batch(elements).await
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        self.sink.rollback(block_number, addresses).await
+    }
+}
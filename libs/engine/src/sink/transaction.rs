@@ -0,0 +1,186 @@
+use alloy::primitives::{Address, BlockHash, BlockNumber, TxHash};
+use alloy::rpc::types::TransactionReceipt;
+use chain::rpc::NodeClient;
+use eyre::{Result, eyre};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use store::transaction::{model::Transaction, store::Store};
+
+use crate::sink::handle::Sink;
+use crate::state::outcome::Outcome;
+
+// JSON-RPC error code of methods the node does not serve
+const METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+// Receipts fetched for the last block, and the transactions stored from them
+#[derive(Default)]
+struct BlockReceipts {
+    block_hash: BlockHash,
+    receipts: HashMap<TxHash, TransactionReceipt>,
+    stored: HashSet<TxHash>,
+}
+
+impl BlockReceipts {
+    // Starts over when moving to another block
+    fn of(&mut self, block_hash: BlockHash) -> &mut Self {
+        if self.block_hash != block_hash {
+            *self = Self { block_hash, ..Default::default() };
+        }
+        self
+    }
+}
+
+// Decorates a sink: fetches the receipt (sender, gas used, status) of
+// every transaction emitting an outcome, before processing
+pub struct TransactionSink<T> {
+    sink: Arc<dyn Sink<Item = T>>,
+    store: Store,
+    node_client: NodeClient,
+    // Cleared once the node does not serve `eth_getBlockReceipts`
+    block_receipts_supported: AtomicBool,
+    // Outcomes of a block are processed one by one in live mode: receipts are fetched once
+    last_block: Mutex<BlockReceipts>,
+}
+
+impl<T: Outcome> TransactionSink<T> {
+    pub fn new(sink: Arc<dyn Sink<Item = T>>, store: Store, node_client: NodeClient) -> Self {
+        Self {
+            sink,
+            store,
+            node_client,
+            block_receipts_supported: AtomicBool::new(true),
+            last_block: Mutex::new(BlockReceipts::default()),
+        }
+    }
+
+    async fn fetch_transactions(&self, elements: &[T]) -> Result<()> {
+        // Transaction hashes grouped per block, to fetch receipts once per block
+        let mut blocks: BTreeMap<(BlockNumber, BlockHash), HashSet<TxHash>> = BTreeMap::new();
+        for element in elements {
+            blocks
+                .entry((element.block_number(), element.block_hash()))
+                .or_default()
+                .insert(element.transaction_hash());
+        }
+
+        let mut transactions: Vec<Transaction> = vec![];
+        for ((_, block_hash), transaction_hashes) in blocks {
+            // Receipts not fetched yet, of the transactions not stored yet
+            let missing: HashSet<TxHash> = {
+                let mut last_block = self.last_block.lock().unwrap();
+                let last_block = last_block.of(block_hash);
+                transaction_hashes
+                    .iter()
+                    .filter(|transaction_hash| {
+                        !last_block.stored.contains(*transaction_hash)
+                            && !last_block.receipts.contains_key(*transaction_hash)
+                    })
+                    .copied()
+                    .collect()
+            };
+            let receipts = if missing.is_empty() {
+                vec![]
+            } else {
+                self.get_receipts(block_hash, &missing).await?
+            };
+
+            let mut last_block = self.last_block.lock().unwrap();
+            let last_block = last_block.of(block_hash);
+            last_block
+                .receipts
+                .extend(receipts.into_iter().map(|receipt| (receipt.transaction_hash, receipt)));
+            for transaction_hash in transaction_hashes {
+                if let Some(receipt) = last_block.receipts.get(&transaction_hash)
+                    && last_block.stored.insert(transaction_hash)
+                {
+                    transactions.push(receipt.into());
+                }
+            }
+        }
+        if transactions.is_empty() {
+            return Ok(());
+        }
+
+        match self.store.insert_transactions_batch(&transactions).await {
+            Ok(_) => {
+                let nbr_of_rows = transactions.len();
+                tracing::info!("Fetched transactions: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_transactions_batch]: {e:?}");
+                *self.last_block.lock().unwrap() = BlockReceipts::default();
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn get_receipts(
+        &self,
+        block_hash: BlockHash,
+        transaction_hashes: &HashSet<TxHash>,
+    ) -> Result<Vec<TransactionReceipt>> {
+        if self.block_receipts_supported.load(Ordering::Relaxed) {
+            match self.node_client.get_block_receipts(block_hash).await {
+                Ok(Some(receipts)) => return Ok(receipts),
+                Ok(None) => {
+                    tracing::warn!("Block receipts not found: {block_hash:?}");
+                }
+                // Only a missing method means the node does not serve it: other errors (rate
+                // limits, internal errors) are left to the retries of the request
+                Err(e) if e.as_error_resp().is_some_and(|e| e.code == METHOD_NOT_FOUND_CODE) => {
+                    tracing::warn!("Block receipts not supported, fetching one by one: {e:?}");
+                    self.block_receipts_supported.store(false, Ordering::Relaxed);
+                }
+                Err(e) => return Err(eyre!(e)),
+            }
+        }
+
+        let mut receipts = vec![];
+        for transaction_hash in transaction_hashes {
+            match self.node_client.get_transaction_receipt(*transaction_hash).await? {
+                Some(receipt) => receipts.push(receipt),
+                None => tracing::warn!("Transaction receipt not found: {transaction_hash:?}"),
+            }
+        }
+        Ok(receipts)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> Sink for TransactionSink<T>
+where
+    T: Outcome + Send + Sync,
+{
+    type Item = T;
+
+    async fn process(&self, element: &T) -> Result<()> {
+        self.fetch_transactions(std::slice::from_ref(element)).await?;
+        self.sink.process(element).await
+    }
+
+    async fn process_batch(&self, elements: &[T]) -> Result<()> {
+        self.fetch_transactions(elements).await?;
+        self.sink.process_batch(elements).await
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        *self.last_block.lock().unwrap() = BlockReceipts::default();
+        // Transactions are not tied to a contract: keep them when rolling back some contracts only
+        if addresses.is_empty() {
+            match self.store.delete_transactions_after_block_number(block_number).await {
+                Ok(nbr_of_rows) => {
+                    tracing::info!("Transactions rolled back to {block_number:?}: {nbr_of_rows:?}");
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Processor failed on [delete_transactions_after_block_number]: {e:?}"
+                    );
+                    return Err(eyre!(e));
+                }
+            }
+        }
+        self.sink.rollback(block_number, addresses).await
+    }
+}
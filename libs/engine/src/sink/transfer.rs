@@ -1,14 +1,47 @@
+use alloy::primitives::{Address, BlockNumber};
 use eyre::{Result, eyre};
 use store::transfer::{model::Transfer, store::Store};
 
-use crate::sink::handle::Sink;
+use crate::sink::handle::{Sink, contract_addresses};
 
-pub struct TransferSink {
-    pub store: Store,
+// Stores persisting transfers: the transfer logs only, or along with the balances
+#[async_trait::async_trait]
+pub trait TransferStore: Send + Sync {
+    async fn insert_transfer(&self, transfer: &Transfer) -> Result<()>;
+    async fn insert_transfers_batch(&self, transfers: &[Transfer]) -> Result<()>;
+    async fn delete_transfers_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64>;
 }
 
 #[async_trait::async_trait]
-impl Sink for TransferSink {
+impl TransferStore for Store {
+    async fn insert_transfer(&self, transfer: &Transfer) -> Result<()> {
+        Ok(Store::insert_transfer(self, transfer).await?)
+    }
+
+    async fn insert_transfers_batch(&self, transfers: &[Transfer]) -> Result<()> {
+        Ok(Store::insert_transfers_batch(self, transfers).await?)
+    }
+
+    async fn delete_transfers_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64> {
+        Ok(Store::delete_transfers_after_block_number(self, block_number, contract_addresses)
+            .await?)
+    }
+}
+
+pub struct TransferSink<S: TransferStore = Store> {
+    pub store: S,
+}
+
+#[async_trait::async_trait]
+impl<S: TransferStore> Sink for TransferSink<S> {
     type Item = Transfer;
 
     async fn process(&self, transfer: &Transfer) -> Result<()> {
@@ -37,4 +70,21 @@ impl Sink for TransferSink {
             }
         }
     }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_transfers_after_block_number(block_number, &contract_addresses(addresses))
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [delete_transfers_after_block_number]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
 }
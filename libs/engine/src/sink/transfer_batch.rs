@@ -0,0 +1,63 @@
+use alloy::primitives::{Address, BlockNumber};
+use eyre::{Result, eyre};
+use store::transfer_batch::{model::TransferBatch, store::Store};
+
+use crate::sink::handle::{Sink, contract_addresses};
+
+pub struct TransferBatchSink {
+    pub store: Store,
+}
+
+#[async_trait::async_trait]
+impl Sink for TransferBatchSink {
+    type Item = TransferBatch;
+
+    async fn process(&self, transfer_batch: &TransferBatch) -> Result<()> {
+        match self.store.insert_transfer_batch(transfer_batch).await {
+            Ok(_) => {
+                tracing::info!("Processed: {transfer_batch:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_transfer_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn process_batch(&self, transfer_batches: &[TransferBatch]) -> Result<()> {
+        match self.store.insert_transfer_batches_batch(transfer_batches).await {
+            Ok(_) => {
+                let nbr_of_rows = transfer_batches.len();
+                tracing::info!("Processed batch: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_transfer_batches_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_transfer_batches_after_block_number(
+                block_number,
+                &contract_addresses(addresses),
+            )
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Processor failed on [delete_transfer_batches_after_block_number]: {e:?}"
+                );
+                Err(eyre!(e))
+            }
+        }
+    }
+}
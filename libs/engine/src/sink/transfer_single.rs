@@ -0,0 +1,63 @@
+use alloy::primitives::{Address, BlockNumber};
+use eyre::{Result, eyre};
+use store::transfer_single::{model::TransferSingle, store::Store};
+
+use crate::sink::handle::{Sink, contract_addresses};
+
+pub struct TransferSingleSink {
+    pub store: Store,
+}
+
+#[async_trait::async_trait]
+impl Sink for TransferSingleSink {
+    type Item = TransferSingle;
+
+    async fn process(&self, transfer_single: &TransferSingle) -> Result<()> {
+        match self.store.insert_transfer_single(transfer_single).await {
+            Ok(_) => {
+                tracing::info!("Processed: {transfer_single:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_transfer_single]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn process_batch(&self, transfer_singles: &[TransferSingle]) -> Result<()> {
+        match self.store.insert_transfer_singles_batch(transfer_singles).await {
+            Ok(_) => {
+                let nbr_of_rows = transfer_singles.len();
+                tracing::info!("Processed batch: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Processor failed on [insert_transfer_singles_batch]: {e:?}");
+                Err(eyre!(e))
+            }
+        }
+    }
+
+    async fn rollback(&self, block_number: BlockNumber, addresses: &[Address]) -> Result<()> {
+        match self
+            .store
+            .delete_transfer_singles_after_block_number(
+                block_number,
+                &contract_addresses(addresses),
+            )
+            .await
+        {
+            Ok(nbr_of_rows) => {
+                tracing::info!("Rolled back to {block_number:?}: {nbr_of_rows:?}");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Processor failed on [delete_transfer_singles_after_block_number]: {e:?}"
+                );
+                Err(eyre!(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::source::filter::{ChunkFilter, StreamFilter};
+use crate::source::handle::{Source, SourceInput};
+use alloy::{
+    json_abi::Event,
+    primitives::{BlockHash, BlockNumber},
+    rpc::types::Log,
+};
+use eyre::Result;
+use futures_util::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use std::sync::Arc;
+use store::event_log::model::AbiLog;
+
+// Pairs logs sourced from an inner log source with the ABI event matching their first topic
+pub struct AbiLogSource {
+    pub source: Arc<dyn Source<Item = Log>>,
+    pub events: Vec<Arc<Event>>,
+}
+
+impl SourceInput for AbiLog {
+    fn block_number(&self) -> Option<BlockNumber> {
+        self.log.block_number
+    }
+
+    fn block_hash(&self) -> Option<BlockHash> {
+        self.log.block_hash
+    }
+
+    fn block_timestamp(&self) -> Option<u64> {
+        self.log.block_timestamp
+    }
+}
+
+fn match_event(events: &[Arc<Event>], log: Log) -> Option<AbiLog> {
+    let selector = log.topic0()?;
+    let event = events.iter().find(|event| event.selector() == *selector)?;
+    Some(AbiLog { event: Arc::clone(event), log })
+}
+
+#[async_trait::async_trait]
+impl Source for AbiLogSource {
+    type Item = AbiLog;
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        let abi_logs_chunk = self
+            .source
+            .chunk(filter)
+            .await?
+            .into_iter()
+            .filter_map(|log| match_event(&self.events, log))
+            .collect();
+
+        Ok(abi_logs_chunk)
+    }
+
+    async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        let events = self.events.clone();
+        let abi_logs_stream = self
+            .source
+            .stream(filter)
+            .await?
+            .flat_map(move |log| stream::iter(match_event(&events, log)));
+
+        Ok(Box::pin(abi_logs_stream))
+    }
+}
@@ -0,0 +1,237 @@
+use crate::source::filter::{ChunkFilter, StreamFilter};
+use crate::source::handle::Source;
+use crate::tip::BlockTip;
+use alloy::{
+    dyn_abi::{DynSolValue, EventExt},
+    eips::BlockNumberOrTag,
+    json_abi::Event,
+    primitives::{Address, BlockNumber},
+    rpc::types::{Log, ValueOrArray},
+};
+use chain::rpc::NodeClient;
+use eyre::{Result, eyre};
+use futures_util::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use store::contract::{model::Contract, store::Store};
+use tokio::sync::RwLock;
+
+// Addresses per node log filter, as providers bound them (and the filter size)
+const MAX_ADDRESSES_PER_FILTER: usize = 1_000;
+// Blocks polled per chunk by the stream, as providers bound the range of a log filter
+const STREAM_CHUNK_SIZE: u64 = 1_000;
+
+// Factory contract creating the contracts to watch (e.g. Uniswap `PairCreated`)
+#[derive(Debug, Clone)]
+pub struct Factory {
+    pub address: Address,
+    pub event: Event,
+    // Position of the created contract address among the event inputs
+    pub argument_index: usize,
+}
+
+// Sources logs from an inner log source, watching the contracts created by a factory on top
+// of the given addresses. Every chunk first discovers the contracts created within its block
+// range, so that their logs are sourced from their creation block.
+pub struct FactorySource {
+    discovery: Arc<Discovery>,
+}
+
+struct Discovery {
+    source: Arc<dyn Source<Item = Log>>,
+    node_client: NodeClient,
+    store: Store,
+    factory: Factory,
+    // Contracts discovered so far, persisted so that restarts resume with them
+    contracts: RwLock<BTreeSet<Address>>,
+}
+
+impl FactorySource {
+    pub async fn init(
+        source: Arc<dyn Source<Item = Log>>,
+        node_client: NodeClient,
+        store: Store,
+        factory: Factory,
+    ) -> Result<Self> {
+        let contracts: BTreeSet<Address> = store
+            .get_contracts_by_factory(factory.address.as_slice())
+            .await?
+            .iter()
+            .map(|contract| Address::from_slice(&contract.contract_address))
+            .collect();
+        let nbr_of_contracts = contracts.len();
+        tracing::info!("Factory {:?} contracts resumed: {nbr_of_contracts:?}", factory.address);
+
+        let discovery =
+            Discovery { source, node_client, store, factory, contracts: RwLock::new(contracts) };
+        Ok(Self { discovery: Arc::new(discovery) })
+    }
+}
+
+impl Discovery {
+    // Decodes the created contract address of a factory event log
+    fn created_contract(&self, log: &Log) -> Result<Contract> {
+        let decoded = self.factory.event.decode_log(log.data())?;
+        let mut indexed = decoded.indexed.into_iter();
+        let mut body = decoded.body.into_iter();
+        let value = self
+            .factory
+            .event
+            .inputs
+            .iter()
+            .map(|input| if input.indexed { indexed.next() } else { body.next() })
+            .nth(self.factory.argument_index)
+            .flatten();
+        let contract_address = match value {
+            Some(DynSolValue::Address(address)) => address,
+            other => return Err(eyre!("created contract is not an address: {other:?}")),
+        };
+        Ok(Contract {
+            contract_address: contract_address.to_vec(),
+            factory_address: self.factory.address.to_vec(),
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+        })
+    }
+
+    // Registers the contracts created by the factory within the given block range
+    async fn discover(
+        &self,
+        from_block_number: BlockNumberOrTag,
+        to_block_number: BlockNumberOrTag,
+    ) -> Result<()> {
+        let logs = self
+            .node_client
+            .get_logs(
+                ValueOrArray::Value(self.factory.address),
+                [self.factory.event.signature()],
+                Default::default(),
+                from_block_number,
+                to_block_number,
+            )
+            .await?;
+
+        let mut contracts = vec![];
+        for log in logs.iter().filter(|log| log.block_number.is_some()) {
+            match self.created_contract(log) {
+                Ok(contract) => contracts.push(contract),
+                Err(e) => tracing::error!("Skip: Failed to decode factory log: {log:?} - {e:?}"),
+            }
+        }
+        if contracts.is_empty() {
+            return Ok(());
+        }
+
+        // NOTE: Contracts discovered on a block later reorganized are kept,
+        // they only widen the address filter.
+        self.store.insert_contracts_batch(&contracts).await?;
+        let mut known_contracts = self.contracts.write().await;
+        for contract in contracts {
+            tracing::info!("Discovered: {contract:?}");
+            known_contracts.insert(Address::from_slice(&contract.contract_address));
+        }
+        Ok(())
+    }
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Log>> {
+        self.discover(filter.from_block_number, filter.to_block_number).await?;
+
+        let mut addresses: Vec<Address> = match &filter.addresses {
+            ValueOrArray::Value(address) => vec![*address],
+            ValueOrArray::Array(addresses) => addresses.clone(),
+        };
+        addresses.extend(self.contracts.read().await.iter());
+        // No address would watch every contract on chain
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut logs = vec![];
+        for batch in addresses.chunks(MAX_ADDRESSES_PER_FILTER) {
+            let filter =
+                ChunkFilter { addresses: ValueOrArray::Array(batch.to_vec()), ..filter.clone() };
+            logs.extend(self.source.chunk(filter).await?);
+        }
+        // Logs of different batches are interleaved
+        if addresses.len() > MAX_ADDRESSES_PER_FILTER {
+            logs.sort_by_key(|log| (log.block_number, log.log_index));
+        }
+        Ok(logs)
+    }
+
+    // Block the stream may poll up to: the configured tip, less the confirmations
+    async fn tip_block_number(&self, filter: &StreamFilter) -> Result<BlockNumber> {
+        let tip_block_number = match filter.tip {
+            BlockTip::Latest => self.node_client.get_latest_block_number().await?,
+            tip => tip.get_block(&self.node_client).await?.number(),
+        };
+        Ok(tip_block_number.saturating_sub(filter.confirmations))
+    }
+}
+
+#[async_trait::async_trait]
+impl Source for FactorySource {
+    type Item = Log;
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        self.discovery.chunk(filter).await
+    }
+
+    // The address filter of a node log subscription is fixed: the stream polls chunks
+    // up to the configured tip instead, so that newly discovered contracts are watched.
+    async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        let from_block_number = match filter.from_block_number.as_number() {
+            Some(block_number) => block_number,
+            None => self.discovery.tip_block_number(&filter).await?,
+        };
+
+        let discovery = Arc::clone(&self.discovery);
+        let logs_stream = stream::unfold(
+            (discovery, filter, from_block_number),
+            |(discovery, filter, from_block_number)| async move {
+                loop {
+                    tokio::time::sleep(filter.poll_interval).await;
+                    let tip_block_number = match discovery.tip_block_number(&filter).await {
+                        Ok(block_number) => block_number,
+                        Err(e) => {
+                            tracing::error!("Factory stream failed to get tip block: {e:?}");
+                            continue;
+                        }
+                    };
+                    if tip_block_number < from_block_number {
+                        continue;
+                    }
+                    let to_block_number = from_block_number
+                        .saturating_add(STREAM_CHUNK_SIZE - 1)
+                        .min(tip_block_number);
+                    let chunk_filter = ChunkFilter {
+                        addresses: filter.addresses.clone(),
+                        event: filter.event.clone(),
+                        topics: filter.topics.clone(),
+                        from_block_number: from_block_number.into(),
+                        to_block_number: to_block_number.into(),
+                    };
+                    match discovery.chunk(chunk_filter).await {
+                        Ok(logs) => {
+                            let next_block_number = to_block_number + 1;
+                            return Some((logs, (discovery, filter, next_block_number)));
+                        }
+                        Err(e) => {
+                            tracing::error!("Factory stream failed to source chunk: {e:?}");
+                        }
+                    }
+                }
+            },
+        )
+        .flat_map(stream::iter);
+
+        Ok(Box::pin(logs_stream))
+    }
+}
@@ -1,33 +1,101 @@
 use std::time::Duration;
 
-use alloy::{eips::BlockNumberOrTag, primitives::Address, rpc::types::ValueOrArray};
+use alloy::{
+    eips::BlockNumberOrTag,
+    json_abi::Event,
+    primitives::{Address, B256},
+    rpc::types::{Log, ValueOrArray},
+};
+
+use crate::tip::BlockTip;
 
 #[derive(Debug, Clone)]
 pub enum EventType {
+    // ERC-20 transfers: from and to indexed, amount in data
     Transfer,
+    // ERC-721 transfers: from, to and token id indexed
+    NftTransfer,
+    // ERC-20 and ERC-721 transfers together, told apart by their number of topics
+    AnyTransfer,
+    // ERC-1155 single transfers: operator, from and to indexed, id and value in data
+    TransferSingle,
+    // ERC-1155 batch transfers: operator, from and to indexed, ids and values in data
+    TransferBatch,
+    // ERC-20 approvals: owner and spender indexed, amount in data
+    Approval,
+    // Events declared by a contract ABI, decoded dynamically
+    Abi(Vec<Event>),
 }
 
 impl EventType {
-    // Here we have declared a string slice initialized with a string literal.
-    // String literals have a static lifetime, which means the string is guaranteed
-    // to be valid for the duration of the entire program.
-    pub fn as_str(&self) -> &'static str {
+    // Event signatures used to filter logs by their first topic
+    pub fn signatures(&self) -> Vec<String> {
         match self {
-            EventType::Transfer => "Transfer(address,address,uint256)",
+            EventType::Transfer | EventType::NftTransfer | EventType::AnyTransfer => {
+                vec!["Transfer(address,address,uint256)".to_string()]
+            }
+            EventType::TransferSingle => {
+                vec!["TransferSingle(address,address,address,uint256,uint256)".to_string()]
+            }
+            EventType::TransferBatch => {
+                vec!["TransferBatch(address,address,address,uint256[],uint256[])".to_string()]
+            }
+            EventType::Approval => vec!["Approval(address,address,uint256)".to_string()],
+            EventType::Abi(events) => events.iter().map(Event::signature).collect(),
         }
     }
+
+    // Number of topics expected on matching logs, when the signature alone is ambiguous
+    pub fn topics_len(&self) -> Option<usize> {
+        match self {
+            EventType::Transfer | EventType::Approval => Some(3),
+            EventType::NftTransfer | EventType::TransferSingle | EventType::TransferBatch => {
+                Some(4)
+            }
+            EventType::AnyTransfer | EventType::Abi(_) => None,
+        }
+    }
+}
+
+// Indexed topics following the event signature (e.g. `from` and `to` of transfers).
+// A log matches when each of its topics is one of the given values, an empty topic matches any.
+#[derive(Debug, Clone, Default)]
+pub struct Topics {
+    pub topic1: Vec<B256>,
+    pub topic2: Vec<B256>,
+    pub topic3: Vec<B256>,
+}
+
+impl Topics {
+    pub fn to_array(&self) -> [Vec<B256>; 3] {
+        [self.topic1.clone(), self.topic2.clone(), self.topic3.clone()]
+    }
+
+    pub fn matches(&self, log: &Log) -> bool {
+        [&self.topic1, &self.topic2, &self.topic3].into_iter().enumerate().all(|(index, values)| {
+            values.is_empty()
+                || log.topics().get(index + 1).is_some_and(|topic| values.contains(topic))
+        })
+    }
 }
 
+#[derive(Debug, Clone)]
 pub struct ChunkFilter {
     pub addresses: ValueOrArray<Address>,
     pub event: EventType,
+    pub topics: Topics,
     pub from_block_number: BlockNumberOrTag,
     pub to_block_number: BlockNumberOrTag,
 }
 
+#[derive(Debug, Clone)]
 pub struct StreamFilter {
     pub addresses: ValueOrArray<Address>,
     pub event: EventType,
+    pub topics: Topics,
     pub from_block_number: BlockNumberOrTag,
     pub poll_interval: Duration,
+    // Chain tip followed by polling streams, and the blocks they stay behind it
+    pub tip: BlockTip,
+    pub confirmations: u64,
 }
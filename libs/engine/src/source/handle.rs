@@ -1,4 +1,4 @@
-use alloy::primitives::BlockNumber;
+use alloy::primitives::{BlockHash, BlockNumber};
 use eyre::Result;
 use futures_util::stream::BoxStream;
 
@@ -6,6 +6,12 @@ use crate::source::filter::{ChunkFilter, StreamFilter};
 
 pub trait SourceInput {
     fn block_number(&self) -> Option<BlockNumber>;
+    fn block_hash(&self) -> Option<BlockHash>;
+
+    // Timestamp of the block, when the source provides it (saves a block request)
+    fn block_timestamp(&self) -> Option<u64> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
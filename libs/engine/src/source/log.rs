@@ -1,6 +1,9 @@
 use crate::source::filter::{ChunkFilter, StreamFilter};
 use crate::source::handle::{Source, SourceInput};
-use alloy::{primitives::BlockNumber, rpc::types::Log};
+use alloy::{
+    primitives::{BlockHash, BlockNumber},
+    rpc::types::Log,
+};
 use chain::rpc::NodeClient;
 use eyre::Result;
 use futures_util::{
@@ -16,6 +19,14 @@ impl SourceInput for Log {
     fn block_number(&self) -> Option<BlockNumber> {
         self.block_number
     }
+
+    fn block_hash(&self) -> Option<BlockHash> {
+        self.block_hash
+    }
+
+    fn block_timestamp(&self) -> Option<u64> {
+        self.block_timestamp
+    }
 }
 
 #[async_trait::async_trait]
@@ -23,11 +34,13 @@ impl Source for LogSource {
     type Item = Log;
 
     async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        let topics_len = filter.event.topics_len();
         let logs_chunk = self
             .node_client
             .get_logs(
                 filter.addresses,
-                filter.event.as_str(),
+                filter.event.signatures(),
+                filter.topics.to_array(),
                 filter.from_block_number,
                 filter.to_block_number,
             )
@@ -38,17 +51,22 @@ impl Source for LogSource {
             // once their tx is included in a block, at which point `block_number` will be set.
             // We skip them (ignore) here to process only confirmed logs in backfill mode.
             .filter(|log| log.block_number.is_some())
+            // NOTE: ERC-20 and ERC-721 share the same transfer signature,
+            // they are told apart by their number of indexed topics.
+            .filter(|log| has_topics_len(log, topics_len))
             .collect();
 
         Ok(logs_chunk)
     }
 
     async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        let topics_len = filter.event.topics_len();
         let logs_stream = self
             .node_client
             .watch_logs(
                 filter.addresses,
-                filter.event.as_str(),
+                filter.event.signatures(),
+                filter.topics.to_array(),
                 filter.from_block_number,
                 filter.poll_interval,
             )
@@ -58,10 +76,25 @@ impl Source for LogSource {
             // Pending logs are re-emitted (with the same tx hash and log index)
             // once the tx is included in a block, at which point `block_number` will be set.
             // We skip it (ignore) now and process it only after confirmation.
-            .filter_map(|log| {
-                future::ready(if log.block_number().is_some() { Some(log) } else { None })
+            // NOTE: Logs flagged as `removed` belong to a block dropped by a chain reorganization.
+            // We skip them (ignore) too, since the engine rolls back orphaned blocks on its own.
+            .filter_map(move |log| {
+                future::ready(
+                    if log.block_number().is_some()
+                        && !log.removed
+                        && has_topics_len(&log, topics_len)
+                    {
+                        Some(log)
+                    } else {
+                        None
+                    },
+                )
             });
 
         Ok(Box::pin(logs_stream))
     }
 }
+
+pub(crate) fn has_topics_len(log: &Log, topics_len: Option<usize>) -> bool {
+    topics_len.is_none_or(|topics_len| log.topics().len() == topics_len)
+}
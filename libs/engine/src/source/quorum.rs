@@ -0,0 +1,155 @@
+use crate::source::filter::{ChunkFilter, StreamFilter};
+use crate::source::handle::{Source, SourceInput};
+use alloy::primitives::{Address, B256, BlockHash, Bytes, TxHash};
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use futures_util::{future::join_all, stream::BoxStream};
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+// Inputs cross-checked by a quorum, through the fields every provider must agree on
+pub trait QuorumInput: SourceInput {
+    type Key: PartialEq + Debug;
+
+    fn quorum_key(&self) -> Self::Key;
+}
+
+// Fields of a log that identify it on chain. Optional fields that providers may or may not
+// fill in (e.g. `blockTimestamp`, `removed`) are left out.
+#[derive(Debug, PartialEq)]
+pub struct LogKey {
+    pub block_hash: Option<BlockHash>,
+    pub transaction_hash: Option<TxHash>,
+    pub log_index: Option<u64>,
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+}
+
+impl QuorumInput for Log {
+    type Key = LogKey;
+
+    fn quorum_key(&self) -> LogKey {
+        LogKey {
+            block_hash: self.block_hash,
+            transaction_hash: self.transaction_hash,
+            log_index: self.log_index,
+            address: self.address(),
+            topics: self.topics().to_vec(),
+            data: self.data().data.clone(),
+        }
+    }
+}
+
+// Cross-checks the chunks of several sources (e.g. log sources of distinct RPC providers):
+// a chunk is only accepted once `quorum` sources returned the same inputs (same log keys,
+// same block hashes). Discrepancies are reported and the chunk is fetched again, at most
+// `retries` times. Live inputs are streamed from the first source.
+pub struct QuorumSource<E> {
+    pub sources: Vec<Arc<dyn Source<Item = E>>>,
+    pub quorum: usize,
+    pub retries: usize,
+    pub retry_delay: Duration,
+}
+
+#[async_trait::async_trait]
+impl<E> Source for QuorumSource<E>
+where
+    E: QuorumInput + Debug + Send + Sync + 'static,
+{
+    type Item = E;
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        let mut attempt = 0;
+        loop {
+            let chunks =
+                join_all(self.sources.iter().map(|source| source.chunk(filter.clone()))).await;
+            if let Some(agreed) = agreed_chunk(chunks, self.quorum) {
+                return Ok(agreed);
+            }
+            if attempt == self.retries {
+                return Err(eyre!(
+                    "Sources disagree on chunk from {:?} to {:?} after {:?} attempts",
+                    filter.from_block_number,
+                    filter.to_block_number,
+                    attempt + 1
+                ));
+            }
+            attempt += 1;
+            tracing::warn!(
+                "Quorum retrying chunk from {:?} to {:?} in {:?} (attempt {attempt:?})",
+                filter.from_block_number,
+                filter.to_block_number,
+                self.retry_delay
+            );
+            tokio::time::sleep(self.retry_delay).await;
+        }
+    }
+
+    async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        let source = self.sources.first().ok_or_else(|| eyre!("Quorum has no source"))?;
+        source.stream(filter).await
+    }
+}
+
+// The chunk returned by at least `quorum` sources, reporting the sources that differ from
+// the most agreed one otherwise
+fn agreed_chunk<E>(mut chunks: Vec<Result<Vec<E>>>, quorum: usize) -> Option<Vec<E>>
+where
+    E: QuorumInput + Debug,
+{
+    let keys: Vec<Option<Vec<E::Key>>> = chunks
+        .iter()
+        .map(|chunk| chunk.as_ref().ok().map(|chunk| chunk.iter().map(E::quorum_key).collect()))
+        .collect();
+    let agreements: Vec<usize> = keys
+        .iter()
+        .map(|chunk| match chunk {
+            Some(chunk) => keys.iter().filter(|other| other.as_ref() == Some(chunk)).count(),
+            None => 0,
+        })
+        .collect();
+    let (best, &agreement) = agreements.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    if agreement >= quorum.max(1) {
+        return chunks.swap_remove(best).ok();
+    }
+
+    tracing::error!("Quorum not reached: {agreement:?} of {:?} sources agree", chunks.len());
+    let reference = chunks[best].as_ref().ok();
+    for (index, (chunk, chunk_keys)) in chunks.iter().zip(&keys).enumerate() {
+        match (chunk, reference) {
+            (Err(e), _) => tracing::error!("Quorum source {index:?} failed: {e:?}"),
+            (Ok(chunk), Some(reference)) if chunk_keys != &keys[best] => {
+                tracing::error!(
+                    "Quorum source {index:?} differs from source {best:?}: {}",
+                    discrepancy(reference, chunk)
+                );
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// Describes the first input differing between two chunks
+fn discrepancy<E>(reference: &[E], chunk: &[E]) -> String
+where
+    E: QuorumInput + Debug,
+{
+    let position = reference
+        .iter()
+        .zip(chunk)
+        .position(|(expected, input)| expected.quorum_key() != input.quorum_key());
+    match position {
+        Some(position) => {
+            let (expected, input) = (&reference[position], &chunk[position]);
+            format!(
+                "input {position:?} at block {:?} ({:?}) instead of block {:?} ({:?}): {input:?}",
+                input.block_number(),
+                input.block_hash(),
+                expected.block_number(),
+                expected.block_hash()
+            )
+        }
+        None => format!("{:?} inputs instead of {:?}", chunk.len(), reference.len()),
+    }
+}
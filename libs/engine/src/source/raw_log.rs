@@ -0,0 +1,63 @@
+use crate::source::filter::{ChunkFilter, StreamFilter};
+use crate::source::handle::Source;
+use crate::source::log::has_topics_len;
+use alloy::{
+    primitives::{Address, keccak256},
+    rpc::types::{Log, ValueOrArray},
+};
+use eyre::Result;
+use futures_util::stream::{self, BoxStream};
+use store::raw_log::store::Store;
+
+// Sources logs from the raw log archive instead of the node (replay)
+pub struct RawLogSource {
+    pub store: Store,
+}
+
+fn address_bytes(addresses: ValueOrArray<Address>) -> Vec<Vec<u8>> {
+    match addresses {
+        ValueOrArray::Value(address) => vec![address.to_vec()],
+        ValueOrArray::Array(addresses) => {
+            addresses.into_iter().map(|address| address.to_vec()).collect()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Source for RawLogSource {
+    type Item = Log;
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        let topics_len = filter.event.topics_len();
+        let topic0s: Vec<Vec<u8>> = filter
+            .event
+            .signatures()
+            .iter()
+            .map(|signature| keccak256(signature).to_vec())
+            .collect();
+        let from_block_number = filter.from_block_number.as_number().unwrap_or_default();
+        let to_block_number = filter.to_block_number.as_number().unwrap_or(i64::MAX as u64);
+
+        let logs_chunk = self
+            .store
+            .get_raw_logs_between_block_numbers(
+                &address_bytes(filter.addresses),
+                &topic0s,
+                from_block_number,
+                to_block_number,
+            )
+            .await?
+            .iter()
+            .map(Log::from)
+            .filter(|log| has_topics_len(log, topics_len))
+            .filter(|log| filter.topics.matches(log))
+            .collect();
+
+        Ok(logs_chunk)
+    }
+
+    // The archive only holds logs already sourced from the node, there is nothing to watch
+    async fn stream(&self, _filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        Ok(Box::pin(stream::empty()))
+    }
+}
@@ -0,0 +1,137 @@
+use crate::source::filter::{ChunkFilter, StreamFilter};
+use crate::source::handle::{Source, SourceInput};
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, BlockNumber},
+    rpc::types::ValueOrArray,
+};
+use eyre::Result;
+use futures_util::{
+    StreamExt, future,
+    stream::{self, BoxStream},
+};
+use std::sync::Arc;
+use tokio::sync::watch;
+
+// Sources inputs from an inner source for the addresses currently watched.
+// Addresses may change while running: the stream is then re-subscribed
+// from the last block it produced, with the new addresses.
+// A re-subscribed stream only reports the changes following its installation (log filters):
+// the inputs in between are caught up with a chunk up to the latest block, ahead of it.
+pub struct WatchListSource<E> {
+    pub source: Arc<dyn Source<Item = E>>,
+    pub addresses: watch::Receiver<Vec<Address>>,
+}
+
+struct Subscription<E> {
+    source: Arc<dyn Source<Item = E>>,
+    addresses: watch::Receiver<Vec<Address>>,
+    // Set once the watch list is dropped (engine shut down)
+    closed: bool,
+    filter: StreamFilter,
+    stream: BoxStream<'static, E>,
+    last_block_number: Option<BlockNumber>,
+}
+
+impl<E> Subscription<E>
+where
+    E: SourceInput + Send + Sync + 'static,
+{
+    async fn next(mut self) -> Option<(E, Self)> {
+        loop {
+            tokio::select! {
+                input = self.stream.next() => {
+                    let input = input?;
+                    self.last_block_number = input.block_number().or(self.last_block_number);
+                    return Some((input, self));
+                }
+                changed = self.addresses.changed(), if !self.closed => {
+                    if changed.is_err() {
+                        self.closed = true;
+                        continue;
+                    }
+                    // Inputs may be missed without the catch up: end the stream (engine stops)
+                    if let Err(e) = self.resubscribe().await {
+                        tracing::error!("Watch list failed to catch up the re-subscription: {e:?}");
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn resubscribe(&mut self) -> Result<()> {
+        let addresses = self.addresses.borrow_and_update().clone();
+        tracing::info!("Watch list changed, re-subscribing: {addresses:?}");
+        if let Some(block_number) = self.last_block_number {
+            self.filter.from_block_number = BlockNumberOrTag::Number(block_number);
+        }
+        self.filter.addresses = ValueOrArray::Array(addresses);
+        let stream = match self.source.stream(self.filter.clone()).await {
+            Ok(stream) => stream,
+            // keep the previous subscription, until the next change
+            Err(e) => {
+                tracing::error!("Watch list failed to re-subscribe: {e:?}");
+                return Ok(());
+            }
+        };
+
+        // Catch up once the stream is installed, so that no block is left in between
+        let chunk_filter = ChunkFilter {
+            addresses: self.filter.addresses.clone(),
+            event: self.filter.event.clone(),
+            topics: self.filter.topics.clone(),
+            from_block_number: self.filter.from_block_number,
+            to_block_number: BlockNumberOrTag::Latest,
+        };
+        let inputs = self.source.chunk(chunk_filter).await?;
+        // Streamed inputs of the caught up blocks are duplicates
+        let caught_up_block_number = inputs.iter().filter_map(|input| input.block_number()).max();
+        tracing::info!(
+            "Watch list caught up {:?} inputs up to: {caught_up_block_number:?}",
+            inputs.len()
+        );
+        let stream = stream.filter(move |input| {
+            future::ready(caught_up_block_number.is_none_or(|caught_up_block_number| {
+                input
+                    .block_number()
+                    .is_none_or(|block_number| block_number > caught_up_block_number)
+            }))
+        });
+        self.stream = Box::pin(stream::iter(inputs).chain(stream));
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> Source for WatchListSource<E>
+where
+    E: SourceInput + Send + Sync + 'static,
+{
+    type Item = E;
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        let addresses = self.addresses.borrow().clone();
+        let filter = ChunkFilter { addresses: ValueOrArray::Array(addresses), ..filter };
+        self.source.chunk(filter).await
+    }
+
+    async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        let mut addresses = self.addresses.clone();
+        let filter = StreamFilter {
+            addresses: ValueOrArray::Array(addresses.borrow_and_update().clone()),
+            ..filter
+        };
+        let stream = self.source.stream(filter.clone()).await?;
+
+        let subscription = Subscription {
+            source: Arc::clone(&self.source),
+            addresses,
+            closed: false,
+            filter,
+            stream,
+            last_block_number: None,
+        };
+        Ok(Box::pin(stream::unfold(subscription, Subscription::next)))
+    }
+}
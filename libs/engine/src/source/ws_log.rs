@@ -0,0 +1,212 @@
+use crate::source::filter::{ChunkFilter, StreamFilter};
+use crate::source::handle::Source;
+use crate::source::log::{LogSource, has_topics_len};
+use alloy::{eips::BlockNumberOrTag, primitives::BlockNumber, rpc::types::Log};
+use chain::rpc::NodeClient;
+use eyre::Result;
+use futures_util::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use std::collections::{BTreeMap, VecDeque};
+
+// Number of blocks gap-filled per `eth_getLogs` request
+const GAP_FILL_CHUNK_SIZE: u64 = 1_000;
+
+// Maximum number of doubled poll intervals waited between resubscribe attempts
+const MAX_BACKOFF_EXPONENT: u32 = 6;
+
+// Sources logs pushed by the node (`eth_subscribe`) over a WebSocket connection.
+// Chain heads (`newHeads`) drive the stream: pushed logs are released in order once the head of
+// their block is received, and blocks missed while disconnected are gap-filled (`eth_getLogs`)
+// before pushed logs are released again.
+pub struct WsLogSource {
+    pub node_client: NodeClient,
+}
+
+#[async_trait::async_trait]
+impl Source for WsLogSource {
+    type Item = Log;
+
+    async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Self::Item>> {
+        LogSource { node_client: self.node_client.clone() }.chunk(filter).await
+    }
+
+    async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Self::Item>> {
+        // Logs are streamed from the given block on (included), as done by polling
+        let last_block_number = match filter.from_block_number {
+            BlockNumberOrTag::Number(block_number) => Some(block_number.saturating_sub(1)),
+            _ => None,
+        };
+        let subscription = Subscription {
+            node_client: self.node_client.clone(),
+            filter,
+            last_block_number,
+            logs: None,
+            heads: None,
+            pending: BTreeMap::new(),
+            ready: VecDeque::new(),
+        };
+        Ok(Box::pin(stream::unfold(subscription, Subscription::next)))
+    }
+}
+
+struct Subscription {
+    node_client: NodeClient,
+    filter: StreamFilter,
+    // Last block whose logs have all been released
+    last_block_number: Option<BlockNumber>,
+    logs: Option<BoxStream<'static, Log>>,
+    heads: Option<BoxStream<'static, BlockNumber>>,
+    // Pushed logs waiting for the head of their block, ordered by (block number, log index)
+    pending: BTreeMap<(BlockNumber, u64), Log>,
+    // Logs released, in order
+    ready: VecDeque<Log>,
+}
+
+enum Pushed {
+    Log(Option<Log>),
+    Head(Option<BlockNumber>),
+}
+
+impl Subscription {
+    async fn next(mut self) -> Option<(Log, Self)> {
+        loop {
+            if let Some(log) = self.ready.pop_front() {
+                return Some((log, self));
+            }
+            let (Some(logs), Some(heads)) = (self.logs.as_mut(), self.heads.as_mut()) else {
+                self.resubscribe().await;
+                continue;
+            };
+            let pushed = tokio::select! {
+                log = logs.next() => Pushed::Log(log),
+                head = heads.next() => Pushed::Head(head),
+            };
+            match pushed {
+                Pushed::Log(Some(log)) => self.push(log),
+                Pushed::Head(Some(head_block_number)) => {
+                    if let Err(e) = self.release(head_block_number).await {
+                        tracing::error!("WebSocket failed to gap-fill: {e:?}");
+                        self.unsubscribe();
+                    }
+                }
+                Pushed::Log(None) | Pushed::Head(None) => {
+                    tracing::warn!("WebSocket subscription ended");
+                    self.unsubscribe();
+                }
+            }
+        }
+    }
+
+    // Subscribes to logs and heads until it succeeds, then gap-fills up to the current head
+    async fn resubscribe(&mut self) {
+        let mut attempt: u32 = 0;
+        loop {
+            if attempt > 0 {
+                let backoff =
+                    self.filter.poll_interval * 2u32.pow(attempt.min(MAX_BACKOFF_EXPONENT));
+                tracing::warn!("WebSocket resubscribing in {backoff:?} (attempt {attempt:?})");
+                tokio::time::sleep(backoff).await;
+            }
+            attempt += 1;
+            match self.subscribe().await {
+                Ok(()) => return,
+                Err(e) => {
+                    tracing::error!("WebSocket failed to subscribe: {e:?}");
+                    self.unsubscribe();
+                }
+            }
+        }
+    }
+
+    async fn subscribe(&mut self) -> Result<()> {
+        // Subscribed first, so that no log is missed while gap-filling
+        self.heads = Some(self.node_client.subscribe_block_numbers().await?);
+        self.logs = Some(
+            self.node_client
+                .subscribe_logs(
+                    self.filter.addresses.clone(),
+                    self.filter.event.signatures(),
+                    self.filter.topics.to_array(),
+                )
+                .await?,
+        );
+        let head_block_number = self.node_client.get_latest_block_number().await?;
+        tracing::info!("WebSocket subscribed at head block number: {head_block_number:?}");
+        match self.last_block_number {
+            Some(_) => self.gap_fill(head_block_number).await,
+            None => {
+                self.last_block_number = Some(head_block_number);
+                Ok(())
+            }
+        }
+    }
+
+    fn unsubscribe(&mut self) {
+        self.logs = None;
+        self.heads = None;
+    }
+
+    fn push(&mut self, log: Log) {
+        // NOTE: Pending logs (not yet mined) are pushed again once mined, and `removed`
+        // logs belong to reorganized blocks, rolled back by the engine on its own.
+        let topics_len = self.filter.event.topics_len();
+        let (Some(block_number), Some(log_index)) = (log.block_number, log.log_index) else {
+            return;
+        };
+        if log.removed || !has_topics_len(&log, topics_len) {
+            return;
+        }
+        self.pending.insert((block_number, log_index), log);
+    }
+
+    // Releases the pushed logs up to the given head, gap-filling the blocks
+    // whose head was missed (e.g. while the connection was down)
+    async fn release(&mut self, head_block_number: BlockNumber) -> Result<()> {
+        let last_block_number = self.last_block_number.unwrap_or(head_block_number);
+        if head_block_number > last_block_number + 1 {
+            tracing::warn!(
+                "WebSocket missed heads from {:?} to {:?}",
+                last_block_number + 1,
+                head_block_number - 1
+            );
+            self.gap_fill(head_block_number - 1).await?;
+        }
+        let pending = self.pending.split_off(&(head_block_number + 1, 0));
+        let released = std::mem::replace(&mut self.pending, pending);
+        self.ready.extend(released.into_values());
+        self.last_block_number = Some(head_block_number);
+        Ok(())
+    }
+
+    // Fetches the logs after the last released block up to the given block (included).
+    // Fetched logs replace the pushed ones of the same blocks.
+    async fn gap_fill(&mut self, to_block_number: BlockNumber) -> Result<()> {
+        let Some(last_block_number) = self.last_block_number else {
+            return Ok(());
+        };
+        let mut chunk_from_block = last_block_number + 1;
+        while chunk_from_block <= to_block_number {
+            let chunk_to_block =
+                chunk_from_block.saturating_add(GAP_FILL_CHUNK_SIZE - 1).min(to_block_number);
+            let filter = ChunkFilter {
+                addresses: self.filter.addresses.clone(),
+                event: self.filter.event.clone(),
+                topics: self.filter.topics.clone(),
+                from_block_number: BlockNumberOrTag::Number(chunk_from_block),
+                to_block_number: BlockNumberOrTag::Number(chunk_to_block),
+            };
+            let logs = LogSource { node_client: self.node_client.clone() }.chunk(filter).await?;
+            tracing::info!(
+                "WebSocket gap-filled {:?} logs from {chunk_from_block:?} to {chunk_to_block:?}",
+                logs.len()
+            );
+            self.ready.extend(logs);
+            self.last_block_number = Some(chunk_to_block);
+            chunk_from_block = chunk_to_block + 1;
+        }
+        self.pending = self.pending.split_off(&(to_block_number + 1, 0));
+        Ok(())
+    }
+}
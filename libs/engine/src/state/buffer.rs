@@ -0,0 +1,76 @@
+use crate::state::{
+    event::{self, Event, Events},
+    outcome::Outcome,
+};
+use alloy::primitives::BlockNumber;
+use std::collections::VecDeque;
+
+// Holds back outcome events until their block is deep enough in the chain
+#[derive(Debug)]
+pub struct ConfirmationBuffer<T> {
+    confirmations: u64,
+    // Events waiting for confirmation, in ascending block number order
+    pending: VecDeque<(BlockNumber, Event<T>)>,
+    // Events to forward on next release regardless of the chain head
+    ready: Vec<Event<T>>,
+}
+
+impl<T: Outcome> ConfirmationBuffer<T> {
+    pub fn new(confirmations: u64) -> Self {
+        Self { confirmations, pending: VecDeque::new(), ready: vec![] }
+    }
+
+    pub fn push(&mut self, events: Events<T>) {
+        let Events(events_vec) = events;
+        for event in events_vec {
+            match event {
+                Event::Skip => {
+                    // just ignore
+                }
+                Event::Element(e) => {
+                    self.pending.push_back((e.block_number(), Event::Element(e)));
+                }
+                Event::Many(elements) => {
+                    for e in elements {
+                        self.pending.push_back((e.block_number(), Event::Element(Box::new(e))));
+                    }
+                }
+                Event::Checkpoint(block) => {
+                    self.pending.push_back((block.number(), Event::Checkpoint(block)));
+                }
+                Event::Block(block) => {
+                    let block_number = block.block_number as BlockNumber;
+                    self.pending.push_back((block_number, Event::Block(block)));
+                }
+                Event::Rollback(block_number) => {
+                    // Orphaned events never reach the sink
+                    self.pending
+                        .retain(|(pending_block_number, _)| *pending_block_number <= block_number);
+                    self.ready.push(Event::Rollback(block_number));
+                }
+            }
+        }
+    }
+
+    // Drains the events confirmed by the given chain head block number
+    pub fn release(&mut self, head_block_number: BlockNumber) -> Events<T> {
+        let mut released: Vec<Event<T>> = std::mem::take(&mut self.ready);
+        while let Some((block_number, _)) = self.pending.front() {
+            if block_number.saturating_add(self.confirmations) > head_block_number {
+                break;
+            }
+            if let Some((_, event)) = self.pending.pop_front() {
+                released.push(event);
+            }
+        }
+        event::batch_events(Events(released))
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.ready.is_empty()
+    }
+}
@@ -1,11 +1,16 @@
-use alloy::rpc::types::Block;
+use alloy::{primitives::BlockNumber, rpc::types::Block};
+use store::block::model::BlockHeader;
 
 #[derive(Debug)]
 pub enum Event<T> {
     Skip,
     Element(Box<T>),
     Checkpoint(Box<Block>),
+    // Header of a newly handled block, recorded along with its outcomes
+    Block(Box<BlockHeader>),
     Many(Vec<T>),
+    // Chain reorganization: discard everything above the common ancestor block number
+    Rollback(BlockNumber),
 }
 
 #[derive(Debug)]
@@ -39,6 +44,14 @@ pub fn batch_events<T>(events: Events<T>) -> Events<T> {
                 flush(&mut outcomes, &mut buffer);
                 outcomes.push(Event::Checkpoint(block));
             }
+            Event::Block(block) => {
+                // no need to flush, blocks are recorded ahead of their outcomes
+                outcomes.push(Event::Block(block));
+            }
+            Event::Rollback(block_number) => {
+                flush(&mut outcomes, &mut buffer);
+                outcomes.push(Event::Rollback(block_number));
+            }
         }
     }
 
@@ -6,16 +6,26 @@ use crate::{
         outcome::Outcome,
     },
 };
-use alloy::{primitives::BlockNumber, rpc::types::Block};
+use alloy::{
+    primitives::{BlockHash, BlockNumber},
+    rpc::types::Block,
+};
 use chain::rpc::NodeClient;
-use eyre::Result;
-use std::fmt::Debug;
+use eyre::{Result, eyre};
+use std::{collections::VecDeque, fmt::Debug};
+use store::block::model::BlockHeader;
+
+// Number of recently handled blocks kept in memory to look up a common ancestor on reorgs.
+// It bounds the maximum reorg depth the engine is able to recover from.
+const MAX_TRACKED_BLOCKS: usize = 128;
 
 #[derive(Debug, Clone)]
 pub struct State {
     block_counter: u64,
     checkpoint_counter: u64,
     current_block_number: u64,
+    // Recently handled blocks (number, hash), in ascending block number order
+    tracked_blocks: VecDeque<(BlockNumber, BlockHash)>,
 }
 
 pub async fn init_state(
@@ -34,12 +44,51 @@ pub async fn init_state(
         },
     };
 
-    Ok(State::new(checkpoint_number))
+    let mut state = State::new(checkpoint_number);
+
+    // Seed tracked blocks with the persisted checkpoints,
+    // so that a reorg happened while the engine was down can be detected
+    let mut checkpoints = checkpointer.get_last_checkpoints(MAX_TRACKED_BLOCKS as u64).await?;
+    checkpoints.reverse();
+    for checkpoint in checkpoints {
+        let block_number = checkpoint.block_number as u64;
+        if block_number <= checkpoint_number {
+            state.track_block(block_number, BlockHash::from_slice(&checkpoint.block_hash));
+        }
+    }
+
+    Ok(state)
+}
+
+// Header of the block of an input: built from its timestamp when the source provides it,
+// requested from the node otherwise
+pub(crate) async fn block_header<E: SourceInput>(
+    input: &E,
+    node_client: &NodeClient,
+) -> Result<Option<BlockHeader>> {
+    let (Some(block_number), Some(block_hash)) = (input.block_number(), input.block_hash()) else {
+        return Ok(None);
+    };
+    match input.block_timestamp() {
+        // NOTE: The parent hash is not provided along with logs, left empty
+        Some(timestamp) => Ok(Some(BlockHeader {
+            block_number: block_number as i64,
+            block_hash: block_hash.to_vec(),
+            parent_hash: vec![],
+            timestamp: timestamp as i64,
+        })),
+        None => Ok(node_client.get_block_by_hash(block_hash).await?.map(|block| (&block).into())),
+    }
 }
 
 impl State {
     pub fn new(current_block_number: u64) -> Self {
-        Self { block_counter: 0, checkpoint_counter: 0, current_block_number }
+        Self {
+            block_counter: 0,
+            checkpoint_counter: 0,
+            current_block_number,
+            tracked_blocks: VecDeque::new(),
+        }
     }
 
     pub fn get_checkpoint_counter(&self) -> u64 {
@@ -56,6 +105,7 @@ impl State {
             None => Ok(Event::Skip),
             Some(checkpoint_block) => {
                 tracing::info!("Logic Checkpointing: {in_memory_block_number:?}");
+                self.track_block(checkpoint_block.number(), checkpoint_block.hash());
                 self.increment_checkpoint_counter();
                 self.reset_block_counter();
                 let checkpoint_event = Event::Checkpoint(Box::new(checkpoint_block));
@@ -105,27 +155,101 @@ impl State {
                 if t.block_number() == self.current_block_number {
                     let outcome_event = Event::Element(Box::new(t));
                     Ok(Events(vec![outcome_event]))
-                }
-                // TODO! handle fork (reorg)
-                else {
-                    self.set_current_block_number(t.block_number());
+                } else {
+                    // Every N blocks, produce a checkpoint event (skip first iteration).
+                    // The checkpoint is the block handled so far: a new block means all of
+                    // its outcomes were produced, ahead of the checkpoint event
                     self.increment_block_counter();
-
-                    let outcome_event = Event::Element(Box::new(t));
-                    // Every N blocks, produce a checkpoint event (skip first iteration)
                     let do_checkpoint =
                         self.block_counter > 0 && self.block_counter == checkpoint_interval;
-                    if !do_checkpoint {
-                        Ok(Events(vec![outcome_event]))
-                    } else {
-                        let checkpoint_event = self.flush_checkpoint(node_client).await?;
-                        Ok(Events(vec![checkpoint_event, outcome_event]))
+                    let mut events = vec![];
+                    if do_checkpoint {
+                        events.push(self.flush_checkpoint(node_client).await?);
                     }
+                    self.set_current_block_number(t.block_number());
+                    self.track_block(t.block_number(), t.block_hash());
+
+                    // Record the header (timestamp) of every block holding outcomes
+                    let block_event = match block_header(&input, node_client).await? {
+                        Some(block_header) => Event::Block(Box::new(block_header)),
+                        None => Event::Skip,
+                    };
+                    let outcome_event = Event::Element(Box::new(t));
+                    events.extend([block_event, outcome_event]);
+                    Ok(Events(events))
                 }
             }
         }
     }
 
+    // Detects whether the chain reorganized below the given input.
+    // Returns the common ancestor block number to roll back to, if any.
+    pub async fn find_reorg<E>(
+        &self,
+        input: &E,
+        node_client: &NodeClient,
+    ) -> Result<Option<BlockNumber>>
+    where
+        E: SourceInput,
+    {
+        match self.tracked_blocks.back() {
+            None => Ok(None),
+            Some((tip_number, tip_hash))
+                if input.block_number() == Some(*tip_number)
+                    && input.block_hash() == Some(*tip_hash) =>
+            {
+                Ok(None)
+            }
+            Some(_) => self.find_common_ancestor(node_client).await,
+        }
+    }
+
+    // Walks back the tracked blocks until one of them is still part of the canonical chain.
+    // Returns `None` when the latest tracked block is canonical (no reorg).
+    pub async fn find_common_ancestor(
+        &self,
+        node_client: &NodeClient,
+    ) -> Result<Option<BlockNumber>> {
+        for (index, (block_number, block_hash)) in self.tracked_blocks.iter().rev().enumerate() {
+            let canonical_hash =
+                node_client.get_block_by_number(*block_number).await?.map(|block| block.hash());
+            if canonical_hash == Some(*block_hash) {
+                if index == 0 {
+                    return Ok(None);
+                }
+                tracing::warn!("Logic detected reorg, common ancestor: {block_number:?}");
+                return Ok(Some(*block_number));
+            }
+        }
+        match self.tracked_blocks.front() {
+            None => Ok(None),
+            Some((block_number, _)) => {
+                Err(eyre!("Reorg deeper than the oldest tracked block: {block_number:?}"))
+            }
+        }
+    }
+
+    // Resets the state to the common ancestor and produces the rollback event
+    pub fn roll_back<T>(&mut self, ancestor_block_number: BlockNumber) -> Event<T> {
+        tracing::warn!("Logic rolling back to: {ancestor_block_number:?}");
+        self.set_current_block_number(ancestor_block_number);
+        self.tracked_blocks.retain(|(block_number, _)| *block_number <= ancestor_block_number);
+        self.reset_block_counter();
+        Event::Rollback(ancestor_block_number)
+    }
+
+    fn track_block(&mut self, block_number: BlockNumber, block_hash: BlockHash) {
+        if let Some((last_block_number, _)) = self.tracked_blocks.back()
+            && *last_block_number >= block_number
+        {
+            self.tracked_blocks.retain(|(n, _)| *n < block_number);
+        }
+        self.tracked_blocks.push_back((block_number, block_hash));
+        if self.tracked_blocks.len() > MAX_TRACKED_BLOCKS {
+            self.tracked_blocks.pop_front();
+        }
+    }
+
     fn increment_block_counter(&mut self) {
         self.block_counter += 1;
     }
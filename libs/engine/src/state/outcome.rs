@@ -1,12 +1,121 @@
-use alloy::primitives::BlockNumber;
-use store::transfer::model::Transfer;
+use alloy::primitives::{Address, BlockHash, BlockNumber, TxHash};
+use store::{
+    approval::model::Approval, event_log::model::EventLog, nft_transfer::model::NftTransfer,
+    transfer::model::Transfer, transfer_batch::model::TransferBatch,
+    transfer_single::model::TransferSingle,
+};
 
 pub trait Outcome {
     fn block_number(&self) -> BlockNumber;
+    fn block_hash(&self) -> BlockHash;
+    fn contract_address(&self) -> Address;
+    fn transaction_hash(&self) -> TxHash;
 }
 
 impl Outcome for Transfer {
     fn block_number(&self) -> BlockNumber {
         self.block_number as u64
     }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.transaction_hash)
+    }
+}
+
+impl Outcome for EventLog {
+    fn block_number(&self) -> BlockNumber {
+        self.block_number as u64
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.transaction_hash)
+    }
+}
+
+impl Outcome for NftTransfer {
+    fn block_number(&self) -> BlockNumber {
+        self.block_number as u64
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.transaction_hash)
+    }
+}
+
+impl Outcome for TransferSingle {
+    fn block_number(&self) -> BlockNumber {
+        self.block_number as u64
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.transaction_hash)
+    }
+}
+
+impl Outcome for TransferBatch {
+    fn block_number(&self) -> BlockNumber {
+        self.block_number as u64
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.transaction_hash)
+    }
+}
+
+impl Outcome for Approval {
+    fn block_number(&self) -> BlockNumber {
+        self.block_number as u64
+    }
+
+    fn block_hash(&self) -> BlockHash {
+        BlockHash::from_slice(&self.block_hash)
+    }
+
+    fn contract_address(&self) -> Address {
+        Address::from_slice(&self.contract_address)
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        TxHash::from_slice(&self.transaction_hash)
+    }
 }
@@ -0,0 +1,25 @@
+use alloy::rpc::types::Block;
+use chain::rpc::NodeClient;
+use eyre::{Result, eyre};
+
+// Block tag the engine treats as the chain tip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTip {
+    // Index up to the latest block (reorgs are handled by the engine)
+    Latest,
+    // Index up to the latest block considered safe by the node
+    Safe,
+    // Index up to the latest finalized block (no reorg can happen)
+    Finalized,
+}
+
+impl BlockTip {
+    pub async fn get_block(&self, node_client: &NodeClient) -> Result<Block> {
+        let block = match self {
+            BlockTip::Latest => node_client.get_latest_block().await?,
+            BlockTip::Safe => node_client.get_latest_safe_block().await?,
+            BlockTip::Finalized => node_client.get_latest_finalized_block().await?,
+        };
+        block.ok_or_else(|| eyre!("{self:?} block not found"))
+    }
+}
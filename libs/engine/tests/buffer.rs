@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::B256;
+    use engine::state::buffer::ConfirmationBuffer;
+    use engine::state::event::{Event, Events};
+    use store::transfer::model::Transfer;
+
+    fn transfer(block_number: i64, log_index: i64) -> Transfer {
+        Transfer {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            transaction_hash: B256::repeat_byte(log_index as u8).to_vec(),
+            log_index,
+            contract_address: vec![0xAA; 20],
+            from_address: vec![0xBB; 32],
+            to_address: vec![0xCC; 32],
+            amount: vec![0x01],
+            block_timestamp: None,
+            chain_id: None,
+        }
+    }
+
+    fn released_transfers(Events(events): Events<Transfer>) -> Vec<Transfer> {
+        events
+            .into_iter()
+            .flat_map(|event| match event {
+                Event::Many(transfers) => transfers,
+                Event::Element(transfer) => vec![*transfer],
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_release_confirmed_blocks_only() {
+        let mut buffer = ConfirmationBuffer::new(3);
+        buffer.push(Events(vec![
+            Event::Element(Box::new(transfer(10, 0))),
+            Event::Element(Box::new(transfer(10, 1))),
+            Event::Element(Box::new(transfer(11, 0))),
+        ]));
+
+        // head is not deep enough for any block
+        let released = released_transfers(buffer.release(12));
+        assert!(released.is_empty());
+        assert_eq!(buffer.len(), 3);
+
+        // head confirms block 10 only
+        let released = released_transfers(buffer.release(13));
+        assert_eq!(released, vec![transfer(10, 0), transfer(10, 1)]);
+        assert_eq!(buffer.len(), 1);
+
+        // head confirms block 11
+        let released = released_transfers(buffer.release(14));
+        assert_eq!(released, vec![transfer(11, 0)]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_rollback_discards_unconfirmed_blocks() {
+        let mut buffer = ConfirmationBuffer::new(3);
+        buffer.push(Events(vec![
+            Event::Element(Box::new(transfer(10, 0))),
+            Event::Element(Box::new(transfer(11, 0))),
+            Event::Element(Box::new(transfer(12, 0))),
+        ]));
+        buffer.push(Events(vec![Event::Rollback(10)]));
+
+        let Events(released) = buffer.release(13);
+        assert!(matches!(released.first(), Some(Event::Rollback(10))));
+        let released = released_transfers(Events(released));
+        assert_eq!(released, vec![transfer(10, 0)]);
+        assert!(buffer.is_empty());
+    }
+}
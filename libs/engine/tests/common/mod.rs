@@ -0,0 +1,90 @@
+#![allow(dead_code)]
+
+use alloy::{signers::local::PrivateKeySigner, transports::http::reqwest::Url};
+use chain::retry::RetryPolicy;
+use chain::rpc::NodeClient;
+use eyre::Result;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+// JSON-RPC error code of methods the node does not serve
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+// JSON-RPC error code of internal node errors
+pub const INTERNAL_ERROR: i64 = -32603;
+
+// Result of a JSON-RPC request, or the code and message of its error
+pub type Response = std::result::Result<Value, (i64, String)>;
+
+type Handler = dyn Fn(&str, &Value) -> Response + Send + Sync;
+
+// Serves JSON-RPC requests over HTTP, answered by the handler from their method and params
+pub async fn spawn_node<F>(handler: F) -> Result<NodeClient>
+where
+    F: Fn(&str, &Value) -> Response + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = Url::parse(&format!("http://{}", listener.local_addr()?))?;
+    let handler: Arc<Handler> = Arc::new(handler);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve(stream, Arc::clone(&handler)));
+        }
+    });
+    // Errors are returned at once, not retried
+    let retry_policy = RetryPolicy { max_retries: 0, ..RetryPolicy::default() };
+    Ok(NodeClient::new(url, PrivateKeySigner::random()).with_retry_policy(retry_policy))
+}
+
+// Block number of a hex quantity (e.g. a `eth_getBlockByNumber` param)
+pub fn block_number(param: &Value) -> Option<u64> {
+    param.as_str().and_then(|number| u64::from_str_radix(number.trim_start_matches("0x"), 16).ok())
+}
+
+async fn serve(stream: TcpStream, handler: Arc<Handler>) -> Result<()> {
+    let mut stream = BufReader::new(stream);
+    loop {
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if stream.read_line(&mut line).await? == 0 {
+                return Ok(());
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse()?;
+            }
+        }
+        let mut body = vec![0; content_length];
+        stream.read_exact(&mut body).await?;
+        let request: Value = serde_json::from_slice(&body)?;
+        let method = request["method"].as_str().unwrap_or_default();
+        let response = match handler(method, &request["params"]) {
+            Ok(result) => serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": result,
+            }),
+            Err((code, message)) => serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": code, "message": message },
+            }),
+        }
+        .to_string();
+        let http_response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+            response.len()
+        );
+        stream.get_mut().write_all(http_response.as_bytes()).await?;
+    }
+}
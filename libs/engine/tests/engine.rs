@@ -4,10 +4,12 @@ mod tests {
     use engine::checkpointer::Checkpointer;
     use engine::engine::Engine;
     use engine::sink::{handle::Sink, transfer::TransferSink};
-    use engine::source::filter::EventType;
+    use engine::source::filter::{EventType, Topics};
     use engine::source::handle::Source;
     use engine::source::log::LogSource;
+    use engine::tip::BlockTip;
     use eyre::Result;
+    use store::block::store::Store as BlockStore;
     use store::checkpoint::store::Store as CheckpointStore;
     use store::client::Client;
     use store::transfer::model::Transfer;
@@ -42,7 +44,8 @@ mod tests {
         let db_url = "sqlite::memory:";
         let client = Client::init(db_url).await?;
         let checkpoint_store = CheckpointStore::new(client.clone());
-        let checkpointer = Arc::new(Checkpointer::new(checkpoint_store));
+        let block_store = BlockStore::new(client.clone());
+        let checkpointer = Arc::new(Checkpointer::new(checkpoint_store, block_store));
         let transfer_store = TransferStore::new(client.clone());
         let transfer_sink: Arc<dyn Sink<Item = Transfer>> =
             Arc::new(TransferSink { store: TransferStore::new(client.clone()) });
@@ -106,9 +109,12 @@ mod tests {
         let args = engine::args::Args {
             addresses: ValueOrArray::Value(*contract.address()),
             event: EventType::Transfer,
+            topics: Topics::default(),
             from_block: Some(start_block.number()),
             checkpoint_interval: 4,
             backfill_checkpoint_interval: None,
+            confirmations: 0,
+            tip: BlockTip::Latest,
             poll_interval: Duration::from_millis(100),
         };
         let engine = Engine::start(
@@ -160,9 +166,12 @@ mod tests {
         let args = engine::args::Args {
             addresses: ValueOrArray::Value(*contract.address()),
             event: EventType::Transfer,
+            topics: Topics::default(),
             from_block: None,
             checkpoint_interval: 1,
             backfill_checkpoint_interval: None,
+            confirmations: 0,
+            tip: BlockTip::Latest,
             poll_interval: Duration::from_millis(100),
         };
         let restarted_engine = Engine::start(
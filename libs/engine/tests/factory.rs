@@ -0,0 +1,237 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use alloy::eips::BlockNumberOrTag;
+    use alloy::json_abi::Event;
+    use alloy::primitives::{Address, B256, BlockNumber, Bytes, LogData};
+    use alloy::rpc::types::{Block, Log, ValueOrArray};
+    use chain::rpc::NodeClient;
+    use engine::source::factory::{Factory, FactorySource};
+    use engine::source::filter::{ChunkFilter, EventType, StreamFilter, Topics};
+    use engine::source::handle::Source;
+    use engine::tip::BlockTip;
+    use eyre::Result;
+    use futures_util::StreamExt;
+    use futures_util::stream::{self, BoxStream};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use store::client::Client;
+    use store::contract::store::Store as ContractStore;
+
+    const SAFE_BLOCK_NUMBER: BlockNumber = 30;
+    const LATEST_BLOCK_NUMBER: BlockNumber = 100;
+
+    fn factory_address() -> Address {
+        Address::repeat_byte(0xFA)
+    }
+
+    fn factory() -> Result<Factory> {
+        Ok(Factory {
+            address: factory_address(),
+            event: Event::parse("event ContractCreated(address indexed contract_address)")?,
+            argument_index: 0,
+        })
+    }
+
+    fn log(address: Address, block_number: BlockNumber, log_index: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: LogData::new_unchecked(vec![B256::repeat_byte(0xDD)], Bytes::new()),
+            },
+            block_number: Some(block_number),
+            block_hash: Some(B256::repeat_byte(block_number as u8)),
+            transaction_hash: Some(B256::repeat_byte(0xF0)),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    // Factory log creating the given contract
+    fn created(contract_address: Address, block_number: BlockNumber) -> Result<Log> {
+        let mut log = log(factory_address(), block_number, 0);
+        let topics = vec![factory()?.event.selector(), contract_address.into_word()];
+        log.inner.data = LogData::new_unchecked(topics, Bytes::new());
+        Ok(log)
+    }
+
+    // Serves the factory logs (`eth_getLogs`) and the chain tips
+    async fn spawn_node(factory_logs: Vec<Log>) -> Result<NodeClient> {
+        common::spawn_node(move |method, params| match method {
+            "eth_getLogs" => {
+                let from = common::block_number(&params[0]["fromBlock"]).unwrap_or_default();
+                let to = common::block_number(&params[0]["toBlock"]).unwrap_or(u64::MAX);
+                let logs: Vec<&Log> = factory_logs
+                    .iter()
+                    .filter(|log| (from..=to).contains(&log.block_number.unwrap_or_default()))
+                    .collect();
+                Ok(serde_json::json!(logs))
+            }
+            "eth_getBlockByNumber" if params[0] == "safe" => {
+                let mut block: Block = Block::default();
+                block.header.inner.number = SAFE_BLOCK_NUMBER;
+                Ok(serde_json::json!(block))
+            }
+            "eth_blockNumber" => Ok(serde_json::json!(format!("{LATEST_BLOCK_NUMBER:#x}"))),
+            _ => Err((common::METHOD_NOT_FOUND, "not found".to_string())),
+        })
+        .await
+    }
+
+    // In-memory logs filtered by address and block range, recording the chunk filters
+    struct MemorySource {
+        logs: Vec<Log>,
+        filters: Mutex<Vec<ChunkFilter>>,
+    }
+
+    impl MemorySource {
+        fn new(logs: Vec<Log>) -> Arc<Self> {
+            Arc::new(Self { logs, filters: Mutex::new(vec![]) })
+        }
+
+        // Addresses and block range of the recorded chunk filters
+        fn filters(&self) -> Vec<(Vec<Address>, u64, u64)> {
+            self.filters
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|filter| {
+                    let addresses = match &filter.addresses {
+                        ValueOrArray::Value(address) => vec![*address],
+                        ValueOrArray::Array(addresses) => addresses.clone(),
+                    };
+                    let from = filter.from_block_number.as_number().unwrap_or_default();
+                    let to = filter.to_block_number.as_number().unwrap_or_default();
+                    (addresses, from, to)
+                })
+                .collect()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Source for MemorySource {
+        type Item = Log;
+
+        async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Log>> {
+            self.filters.lock().unwrap().push(filter.clone());
+            let (addresses, from, to) = self.filters().pop().unwrap_or_default();
+            Ok(self
+                .logs
+                .iter()
+                .filter(|log| addresses.contains(&log.address()))
+                .filter(|log| (from..=to).contains(&log.block_number.unwrap_or_default()))
+                .cloned()
+                .collect())
+        }
+
+        async fn stream(&self, _filter: StreamFilter) -> Result<BoxStream<'static, Log>> {
+            Ok(Box::pin(stream::pending()))
+        }
+    }
+
+    async fn init_factory_source(
+        source: &Arc<MemorySource>,
+        node_client: NodeClient,
+        client: &Client,
+    ) -> Result<FactorySource> {
+        let source = Arc::clone(source) as Arc<dyn Source<Item = Log>>;
+        FactorySource::init(source, node_client, ContractStore::new(client.clone()), factory()?)
+            .await
+    }
+
+    fn chunk_filter(addresses: Vec<Address>, from: BlockNumber, to: BlockNumber) -> ChunkFilter {
+        ChunkFilter {
+            addresses: ValueOrArray::Array(addresses),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block_number: BlockNumberOrTag::Number(from),
+            to_block_number: BlockNumberOrTag::Number(to),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contracts_discovered_per_chunk() -> Result<()> {
+        let watched = Address::repeat_byte(0x0A);
+        let (first, second) = (Address::repeat_byte(0xC1), Address::repeat_byte(0xC2));
+        let node_client = spawn_node(vec![created(first, 5)?, created(second, 15)?]).await?;
+        let client = Client::init("sqlite::memory:").await?;
+        let source =
+            MemorySource::new(vec![log(watched, 3, 0), log(first, 6, 0), log(second, 16, 0)]);
+        let factory_source = init_factory_source(&source, node_client.clone(), &client).await?;
+
+        // Each chunk watches the contracts created up to its last block, from their creation on
+        let logs = factory_source.chunk(chunk_filter(vec![watched], 0, 9)).await?;
+        assert_eq!(logs, vec![log(watched, 3, 0), log(first, 6, 0)]);
+        let logs = factory_source.chunk(chunk_filter(vec![watched], 10, 19)).await?;
+        assert_eq!(logs, vec![log(second, 16, 0)]);
+        assert_eq!(
+            source.filters(),
+            vec![(vec![watched, first], 0, 9), (vec![watched, first, second], 10, 19)]
+        );
+
+        // Discovered contracts are resumed on restart
+        let source = MemorySource::new(vec![]);
+        let factory_source = init_factory_source(&source, node_client, &client).await?;
+        factory_source.chunk(chunk_filter(vec![watched], 20, 29)).await?;
+        assert_eq!(source.filters(), vec![(vec![watched, first, second], 20, 29)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_addresses_split_per_filter() -> Result<()> {
+        let node_client = spawn_node(vec![]).await?;
+        let client = Client::init("sqlite::memory:").await?;
+        let addresses: Vec<Address> =
+            (1..=1_500u32).map(|index| Address::left_padding_from(&index.to_be_bytes())).collect();
+        let source = MemorySource::new(vec![
+            log(addresses[0], 12, 0),
+            log(addresses[1_200], 11, 1),
+            log(addresses[1], 11, 0),
+        ]);
+        let factory_source = init_factory_source(&source, node_client, &client).await?;
+
+        // Addresses are spread over filters of at most 1,000 addresses,
+        // and the logs of every filter merged back in chain order
+        let logs = factory_source.chunk(chunk_filter(addresses.clone(), 0, 19)).await?;
+        assert_eq!(
+            logs,
+            vec![log(addresses[1], 11, 0), log(addresses[1_200], 11, 1), log(addresses[0], 12, 0)]
+        );
+        assert_eq!(
+            source.filters(),
+            vec![(addresses[..1_000].to_vec(), 0, 19), (addresses[1_000..].to_vec(), 0, 19)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_follows_configured_tip() -> Result<()> {
+        let watched = Address::repeat_byte(0x0A);
+        let node_client = spawn_node(vec![]).await?;
+        let client = Client::init("sqlite::memory:").await?;
+        let source = MemorySource::new(vec![log(watched, 25, 0), log(watched, 29, 0)]);
+        let factory_source = init_factory_source(&source, node_client, &client).await?;
+
+        // The stream polls up to the safe block less the confirmations, not the latest one
+        let filter = StreamFilter {
+            addresses: ValueOrArray::Array(vec![watched]),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block_number: BlockNumberOrTag::Number(20),
+            poll_interval: Duration::from_millis(1),
+            tip: BlockTip::Safe,
+            confirmations: 2,
+        };
+        let mut stream = factory_source.stream(filter).await?;
+        assert_eq!(stream.next().await, Some(log(watched, 25, 0)));
+        let pending = tokio::time::timeout(Duration::from_millis(100), stream.next()).await;
+        assert!(pending.is_err());
+        assert_eq!(source.filters()[0], (vec![watched], 20, SAFE_BLOCK_NUMBER - 2));
+
+        Ok(())
+    }
+}
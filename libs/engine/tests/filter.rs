@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, B256, Bytes, LogData};
+    use alloy::rpc::types::Log;
+    use engine::sink::any_transfer::AnyTransfer;
+    use engine::source::filter::Topics;
+
+    fn transfer_log(from: Address, to: Address) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(
+                    vec![B256::repeat_byte(0xDD), from.into_word(), to.into_word()],
+                    Bytes::new(),
+                ),
+            },
+            block_number: Some(100),
+            block_hash: Some(B256::repeat_byte(0x01)),
+            transaction_hash: Some(B256::repeat_byte(0xF0)),
+            log_index: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_topics_match_any_of_their_values() {
+        let treasury = Address::repeat_byte(0x01);
+        let other = Address::repeat_byte(0x02);
+
+        // Empty topics match any log
+        assert!(Topics::default().matches(&transfer_log(other, other)));
+
+        let to_treasury = Topics { topic2: vec![treasury.into_word()], ..Default::default() };
+        assert!(to_treasury.matches(&transfer_log(other, treasury)));
+        assert!(!to_treasury.matches(&transfer_log(treasury, other)));
+
+        // Missing topics never match
+        let topic3 = Topics { topic3: vec![treasury.into_word()], ..Default::default() };
+        assert!(!topic3.matches(&transfer_log(treasury, treasury)));
+    }
+
+    #[test]
+    fn test_any_transfer_detected_by_topics_len() {
+        let erc20_log = transfer_log(Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        assert!(matches!(AnyTransfer::try_from(erc20_log.clone()), Ok(AnyTransfer::Fungible(_))));
+
+        let mut topics = erc20_log.topics().to_vec();
+        topics.push(B256::with_last_byte(42));
+        let mut erc721_log = erc20_log.clone();
+        erc721_log.inner.data = LogData::new_unchecked(topics, Bytes::new());
+        assert!(matches!(AnyTransfer::try_from(erc721_log), Ok(AnyTransfer::NonFungible(_))));
+
+        let mut other_log = erc20_log;
+        other_log.inner.data = LogData::new_unchecked(vec![B256::repeat_byte(0xDD)], Bytes::new());
+        assert!(AnyTransfer::try_from(other_log).is_err());
+    }
+}
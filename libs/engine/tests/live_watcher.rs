@@ -0,0 +1,149 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use alloy::primitives::{Address, B256, BlockNumber, Bytes, LogData, TxHash, U256};
+    use alloy::rpc::types::{Block, Log, ValueOrArray};
+    use engine::args::Args;
+    use engine::live_watcher;
+    use engine::source::filter::{ChunkFilter, EventType, StreamFilter, Topics};
+    use engine::source::handle::Source;
+    use engine::state::event::{Event, Events};
+    use engine::state::logic::State;
+    use engine::tip::BlockTip;
+    use eyre::Result;
+    use futures_util::stream::{self, BoxStream};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+    use store::transfer::model::Transfer;
+    use tokio::sync::{Mutex, broadcast, mpsc};
+
+    fn log(block_number: BlockNumber) -> Log {
+        let topics = vec![
+            B256::repeat_byte(0xDD),
+            Address::repeat_byte(0x0A).into_word(),
+            Address::repeat_byte(0x0B).into_word(),
+        ];
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(
+                    topics,
+                    Bytes::from(U256::from(1).to_be_bytes::<32>()),
+                ),
+            },
+            block_number: Some(block_number),
+            block_hash: Some(B256::repeat_byte(block_number as u8)),
+            // Headers are taken from the logs, not fetched
+            block_timestamp: Some(block_number),
+            transaction_hash: Some(TxHash::repeat_byte(0xF1)),
+            log_index: Some(0),
+            ..Default::default()
+        }
+    }
+
+    // In-memory logs filtered by block range
+    struct MemorySource {
+        logs: Vec<Log>,
+    }
+
+    #[async_trait::async_trait]
+    impl Source for MemorySource {
+        type Item = Log;
+
+        async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Log>> {
+            let from = filter.from_block_number.as_number().unwrap_or_default();
+            let to = filter.to_block_number.as_number().unwrap_or_default();
+            Ok(self
+                .logs
+                .iter()
+                .filter(|log| (from..=to).contains(&log.block_number.unwrap_or_default()))
+                .cloned()
+                .collect())
+        }
+
+        async fn stream(&self, _filter: StreamFilter) -> Result<BoxStream<'static, Log>> {
+            Ok(Box::pin(stream::pending()))
+        }
+    }
+
+    fn args() -> Args {
+        Args {
+            addresses: ValueOrArray::Value(Address::repeat_byte(0xAA)),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block: None,
+            checkpoint_interval: 10,
+            backfill_checkpoint_interval: None,
+            confirmations: 0,
+            tip: BlockTip::Safe,
+            poll_interval: Duration::from_millis(10),
+        }
+    }
+
+    // Block numbers of the outcomes received until the timeout
+    async fn outcomes(
+        rx: &mut mpsc::Receiver<Result<Events<Transfer>>>,
+        timeout: Duration,
+    ) -> Vec<BlockNumber> {
+        let mut block_numbers = vec![];
+        let _ = tokio::time::timeout(timeout, async {
+            while let Some(events) = rx.recv().await {
+                let Ok(Events(events)) = events else { continue };
+                for event in events {
+                    if let Event::Many(transfers) = event {
+                        block_numbers.extend(transfers.iter().map(|t| t.block_number as u64));
+                    }
+                }
+            }
+        })
+        .await;
+        block_numbers
+    }
+
+    #[tokio::test]
+    async fn test_tip_follower_holds_outcomes_until_tip() -> Result<()> {
+        let safe_block_number = Arc::new(AtomicU64::new(10));
+        let safe_block_number_for_node = Arc::clone(&safe_block_number);
+        let node_client = common::spawn_node(move |method, params| match method {
+            "eth_getBlockByNumber" if params[0] == "safe" => {
+                let mut block: Block = Block::default();
+                block.header.inner.number = safe_block_number_for_node.load(Ordering::SeqCst);
+                Ok(serde_json::json!(block))
+            }
+            _ => Err((common::METHOD_NOT_FOUND, "not found".to_string())),
+        })
+        .await?;
+        let source: Arc<dyn Source<Item = Log>> =
+            Arc::new(MemorySource { logs: vec![log(12), log(14)] });
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let state = Arc::new(Mutex::new(State::new(10)));
+        let handle = live_watcher::spawn(
+            &args(),
+            tx,
+            shutdown_tx.clone(),
+            state,
+            Arc::new(node_client),
+            source,
+        )
+        .await?;
+
+        // Outcomes above the safe block are held back
+        assert!(outcomes(&mut rx, Duration::from_millis(200)).await.is_empty());
+
+        // Outcomes are released as the safe block passes them, and only those
+        safe_block_number.store(13, Ordering::SeqCst);
+        assert_eq!(outcomes(&mut rx, Duration::from_millis(200)).await, vec![12]);
+        safe_block_number.store(14, Ordering::SeqCst);
+        assert_eq!(outcomes(&mut rx, Duration::from_millis(200)).await, vec![14]);
+
+        shutdown_tx.send(())?;
+        handle.await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use alloy::primitives::{Address, B256, BlockNumber, Bytes, LogData, U256};
+    use alloy::rpc::types::{Block, Log};
+    use chain::rpc::NodeClient;
+    use engine::checkpointer::Checkpointer;
+    use engine::consumer;
+    use engine::sink::balance::BalanceSink;
+    use engine::state::event::{Event, Events};
+    use engine::state::logic::{self, State};
+    use eyre::Result;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use store::checkpoint::model::Checkpoint;
+    use store::client::Client;
+    use store::transfer::model::Transfer;
+    use tokio::sync::{broadcast, mpsc};
+
+    fn hash(block_number: BlockNumber, fork: u8) -> B256 {
+        B256::repeat_byte(block_number as u8 ^ fork)
+    }
+
+    // Serves `eth_getBlockByNumber` from the given canonical chain
+    async fn spawn_node(chain: HashMap<BlockNumber, B256>) -> Result<NodeClient> {
+        common::spawn_node(move |method, params| {
+            let block = (method == "eth_getBlockByNumber")
+                .then(|| common::block_number(&params[0]))
+                .flatten()
+                .and_then(|block_number| {
+                    chain.get(&block_number).map(|block_hash| {
+                        let mut block: Block = Block::default();
+                        block.header.hash = *block_hash;
+                        block.header.inner.number = block_number;
+                        block
+                    })
+                });
+            Ok(serde_json::json!(block))
+        })
+        .await
+    }
+
+    // State tracking blocks 10 to 12 of the original chain, as checkpointed
+    async fn tracking_state() -> Result<State> {
+        let client = Client::init("sqlite::memory:").await?;
+        let checkpoint_store = store::checkpoint::store::Store::new(client.clone());
+        for block_number in 10..=12 {
+            checkpoint_store
+                .insert_checkpoint(&Checkpoint {
+                    block_number: block_number as i64,
+                    block_hash: hash(block_number, 0).to_vec(),
+                    parent_hash: hash(block_number - 1, 0).to_vec(),
+                    chain_id: None,
+                })
+                .await?;
+        }
+        let checkpointer =
+            Checkpointer::new(checkpoint_store, store::block::store::Store::new(client));
+        logic::init_state(&Block::default(), Some(12), &checkpointer).await
+    }
+
+    // Canonical chain forked (hashes changed) from the given block number on
+    fn forked_chain(fork_block_number: BlockNumber) -> HashMap<BlockNumber, B256> {
+        (0..=13)
+            .map(|block_number| {
+                let fork = if block_number >= fork_block_number { 0xFF } else { 0 };
+                (block_number, hash(block_number, fork))
+            })
+            .collect()
+    }
+
+    fn log(block_number: BlockNumber, block_hash: B256) -> Log {
+        Log { block_number: Some(block_number), block_hash: Some(block_hash), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_find_reorg() -> Result<()> {
+        let state = tracking_state().await?;
+
+        // Inputs of the latest tracked block are not checked against the node
+        let node_client = spawn_node(HashMap::new()).await?;
+        assert_eq!(state.find_reorg(&log(12, hash(12, 0)), &node_client).await?, None);
+
+        // Inputs of a new block are, the tracked blocks being still canonical
+        let node_client = spawn_node(forked_chain(13)).await?;
+        assert_eq!(state.find_reorg(&log(13, hash(13, 0xFF)), &node_client).await?, None);
+
+        // Tracked blocks were reorganized below the input
+        let node_client = spawn_node(forked_chain(11)).await?;
+        assert_eq!(state.find_reorg(&log(13, hash(13, 0xFF)), &node_client).await?, Some(10));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_common_ancestor() -> Result<()> {
+        let state = tracking_state().await?;
+
+        for (fork_block_number, ancestor_block_number) in
+            [(13, None), (12, Some(11)), (11, Some(10))]
+        {
+            let node_client = spawn_node(forked_chain(fork_block_number)).await?;
+            assert_eq!(state.find_common_ancestor(&node_client).await?, ancestor_block_number);
+        }
+
+        // Every tracked block was reorganized: the engine can not recover on its own
+        let node_client = spawn_node(forked_chain(10)).await?;
+        let error = state.find_common_ancestor(&node_client).await.unwrap_err();
+        assert!(error.to_string().contains("Reorg deeper than the oldest tracked block: 10"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_roll_back_trims_tracked_blocks() -> Result<()> {
+        let mut state = tracking_state().await?;
+
+        let event: Event<()> = state.roll_back(10);
+        assert!(matches!(event, Event::Rollback(10)));
+        assert_eq!(state.get_current_block_number(), 10);
+
+        // Blocks above the ancestor are no longer tracked: the reorganized chain is not
+        // looked up past it, the ancestor being the latest tracked block
+        let node_client = spawn_node(forked_chain(11)).await?;
+        assert_eq!(state.find_common_ancestor(&node_client).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_roll_forward_records_log_block_timestamp() -> Result<()> {
+        let mut state = State::new(12);
+        // The node knows no block: the header is built from the log alone
+        let node_client = spawn_node(HashMap::new()).await?;
+
+        let topics =
+            vec![B256::repeat_byte(0xDD), B256::repeat_byte(0xEE), B256::repeat_byte(0xBB)];
+        let log = Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(topics, Bytes::from(vec![0x01; 32])),
+            },
+            block_timestamp: Some(1_700_000_000),
+            transaction_hash: Some(B256::repeat_byte(0xCC)),
+            log_index: Some(0),
+            ..log(13, hash(13, 0))
+        };
+        let Events(events) = state.roll_forward::<Log, Transfer>(log, 100, &node_client).await?;
+
+        match events.first() {
+            Some(Event::Block(block)) => {
+                assert_eq!(block.block_number, 13);
+                assert_eq!(block.block_hash, hash(13, 0).to_vec());
+                assert_eq!(block.timestamp, 1_700_000_000);
+            }
+            other => panic!("Expected a block event, got: {other:?}"),
+        }
+        assert!(matches!(events.get(1), Some(Event::Element(_))));
+
+        Ok(())
+    }
+
+    fn transfer_log(
+        block_number: BlockNumber,
+        log_index: u64,
+        from: Address,
+        to: Address,
+        amount: u64,
+    ) -> Log {
+        let topics = vec![B256::repeat_byte(0xDD), from.into_word(), to.into_word()];
+        let data = Bytes::from(U256::from(amount).to_be_bytes::<32>());
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(topics, data),
+            },
+            block_timestamp: Some(1_700_000_000 + block_number),
+            transaction_hash: Some(B256::repeat_byte(log_index as u8)),
+            log_index: Some(log_index),
+            ..log(block_number, hash(block_number, 0))
+        }
+    }
+
+    async fn balance_at(
+        balance_store: &store::balance::store::Store,
+        holder: Address,
+        block_number: BlockNumber,
+    ) -> Result<U256> {
+        // holders are stored as indexed topics (32 bytes)
+        let contract_address = Address::repeat_byte(0xAA);
+        let balance = balance_store
+            .get_balance_at_block_number(
+                contract_address.as_slice(),
+                holder.into_word().as_slice(),
+                block_number,
+            )
+            .await?;
+        Ok(U256::from_be_slice(&balance.balance))
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_snapshots_include_checkpoint_block() -> Result<()> {
+        let client = Client::init("sqlite::memory:").await?;
+        let balance_store = store::balance::store::Store::new(client.clone());
+        let checkpointer = Checkpointer::new(
+            store::checkpoint::store::Store::new(client.clone()),
+            store::block::store::Store::new(client.clone()),
+        )
+        .with_balance_snapshots(store::balance::store::Store::new(client.clone()));
+        let sink = Arc::new(BalanceSink { store: store::balance::store::Store::new(client) });
+        let node_client = spawn_node(forked_chain(0xFF)).await?;
+
+        let (alice, bob) = (Address::repeat_byte(0x0A), Address::repeat_byte(0x0B));
+        let inputs = vec![
+            transfer_log(10, 0, Address::ZERO, alice, 100),
+            transfer_log(10, 1, alice, bob, 30),
+            transfer_log(11, 2, alice, bob, 10),
+            transfer_log(12, 3, bob, alice, 5),
+        ];
+
+        // Checkpoint every block, in live mode: one input at a time
+        let mut state = State::new(9);
+        let (tx, rx) = mpsc::channel(16);
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let consumer = consumer::spawn(rx, shutdown_tx, Arc::new(checkpointer), sink).await;
+        for input in inputs {
+            tx.send(state.roll_forward::<Log, Transfer>(input, 1, &node_client).await).await?;
+        }
+        drop(tx);
+        consumer.await?;
+
+        // Block 12 is not complete yet, not checkpointed
+        assert!(balance_at(&balance_store, alice, 12).await.is_err());
+
+        // Snapshots of blocks 10 and 11 hold their own transfers
+        assert_eq!(balance_at(&balance_store, alice, 10).await?, U256::from(70));
+        assert_eq!(balance_at(&balance_store, bob, 10).await?, U256::from(30));
+        assert_eq!(balance_at(&balance_store, alice, 11).await?, U256::from(60));
+        assert_eq!(balance_at(&balance_store, bob, 11).await?, U256::from(40));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use alloy::eips::BlockNumberOrTag;
+    use alloy::primitives::{Address, B256, Bytes, LogData};
+    use alloy::rpc::types::{Log, ValueOrArray};
+    use engine::source::filter::{ChunkFilter, EventType, StreamFilter, Topics};
+    use engine::source::handle::Source;
+    use engine::source::quorum::QuorumSource;
+    use eyre::Result;
+    use futures_util::stream::{self, BoxStream};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    // In-memory logs, the last one being left out of the first `incomplete` chunks
+    struct MemorySource {
+        logs: Vec<Log>,
+        incomplete: usize,
+        calls: AtomicUsize,
+    }
+
+    impl MemorySource {
+        fn new(logs: Vec<Log>, incomplete: usize) -> Arc<Self> {
+            Arc::new(Self { logs, incomplete, calls: AtomicUsize::new(0) })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Source for MemorySource {
+        type Item = Log;
+
+        async fn chunk(&self, _filter: ChunkFilter) -> Result<Vec<Log>> {
+            let mut logs = self.logs.clone();
+            if self.calls.fetch_add(1, Ordering::Relaxed) < self.incomplete {
+                logs.pop();
+            }
+            Ok(logs)
+        }
+
+        async fn stream(&self, _filter: StreamFilter) -> Result<BoxStream<'static, Log>> {
+            Ok(Box::pin(stream::iter(self.logs.clone())))
+        }
+    }
+
+    fn log(block_number: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0x01),
+                data: LogData::new_unchecked(vec![B256::repeat_byte(0xDD)], Bytes::new()),
+            },
+            block_number: Some(block_number),
+            block_hash: Some(B256::repeat_byte(block_number as u8)),
+            ..Default::default()
+        }
+    }
+
+    fn chunk_filter() -> ChunkFilter {
+        ChunkFilter {
+            addresses: ValueOrArray::Value(Address::repeat_byte(0x01)),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block_number: BlockNumberOrTag::Number(0),
+            to_block_number: BlockNumberOrTag::Number(100),
+        }
+    }
+
+    fn quorum(sources: Vec<Arc<MemorySource>>) -> QuorumSource<Log> {
+        let quorum = sources.len();
+        QuorumSource {
+            sources: sources
+                .into_iter()
+                .map(|source| source as Arc<dyn Source<Item = Log>>)
+                .collect(),
+            quorum,
+            retries: 2,
+            retry_delay: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chunk_retried_until_sources_agree() -> Result<()> {
+        let logs = vec![log(10), log(11), log(12)];
+        let honest = MemorySource::new(logs.clone(), 0);
+        let lagging = MemorySource::new(logs.clone(), 2);
+        let source = quorum(vec![honest.clone(), lagging.clone()]);
+
+        // The lagging provider misses a log twice, the third attempt agrees
+        assert_eq!(source.chunk(chunk_filter()).await?, logs);
+        assert_eq!(honest.calls.load(Ordering::Relaxed), 3);
+        assert_eq!(lagging.calls.load(Ordering::Relaxed), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunk_rejected_when_sources_disagree() -> Result<()> {
+        let logs = vec![log(10), log(11)];
+        let mut forged = logs.clone();
+        forged[1].block_hash = Some(B256::repeat_byte(0xFF));
+        let source = quorum(vec![MemorySource::new(logs, 0), MemorySource::new(forged.clone(), 0)]);
+        assert!(source.chunk(chunk_filter()).await.is_err());
+
+        // A majority is enough when the quorum allows it
+        let source = QuorumSource {
+            quorum: 2,
+            ..quorum(vec![
+                MemorySource::new(vec![log(10), log(11)], 0),
+                MemorySource::new(forged, 0),
+                MemorySource::new(vec![log(10), log(11)], 0),
+            ])
+        };
+        assert_eq!(source.chunk(chunk_filter()).await?, vec![log(10), log(11)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunk_agreed_on_log_keys() -> Result<()> {
+        // Providers filling in optional fields differently still agree
+        let logs = vec![log(10), log(11)];
+        let mut with_timestamps = logs.clone();
+        for log in &mut with_timestamps {
+            log.block_timestamp = Some(1_700_000_000);
+        }
+        let source =
+            quorum(vec![MemorySource::new(logs.clone(), 0), MemorySource::new(with_timestamps, 0)]);
+        let agreed = source.chunk(chunk_filter()).await?;
+        let block_numbers: Vec<_> = agreed.iter().map(|log| log.block_number).collect();
+        assert_eq!(block_numbers, vec![Some(10), Some(11)]);
+
+        // Logs differing by their data do not
+        let mut forged = logs.clone();
+        forged[0].inner.data =
+            LogData::new_unchecked(vec![B256::repeat_byte(0xDD)], Bytes::from(vec![0x01]));
+        let source = quorum(vec![MemorySource::new(logs, 0), MemorySource::new(forged, 0)]);
+        assert!(source.chunk(chunk_filter()).await.is_err());
+
+        Ok(())
+    }
+}
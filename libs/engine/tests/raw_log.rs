@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, B256, Bytes, LogData, keccak256};
+    use alloy::rpc::types::{Log, ValueOrArray};
+    use engine::args::Args;
+    use engine::replay;
+    use engine::sink::handle::Sink;
+    use engine::sink::raw_log::{Archived, RawLogSink};
+    use engine::sink::transfer::TransferSink;
+    use engine::source::filter::{EventType, Topics};
+    use engine::source::raw_log::RawLogSource;
+    use engine::tip::BlockTip;
+    use eyre::Result;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use store::client::Client;
+    use store::transfer::model::Transfer;
+
+    fn transfer_log(log_index: u64, topics: Vec<B256>) -> Log {
+        contract_transfer_log(Address::repeat_byte(0xAA), log_index, topics)
+    }
+
+    fn contract_transfer_log(address: Address, log_index: u64, topics: Vec<B256>) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: LogData::new_unchecked(topics, Bytes::from(vec![0x01; 32])),
+            },
+            block_number: Some(100),
+            block_hash: Some(B256::repeat_byte(0x01)),
+            transaction_hash: Some(B256::repeat_byte(0xF0)),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_undecoded_logs_are_archived() -> Result<()> {
+        let client = Client::init("sqlite::memory:").await?;
+        let transfer_store = store::transfer::store::Store::new(client.clone());
+        let raw_log_store = store::raw_log::store::Store::new(client.clone());
+        let sink = RawLogSink::new(Arc::new(TransferSink { store: transfer_store }), raw_log_store);
+
+        let signature = B256::repeat_byte(0xDD);
+        let erc20 = transfer_log(0, vec![signature, B256::ZERO, B256::repeat_byte(0xBB)]);
+        // Same signature, the token id being indexed (ERC-721): not an ERC-20 transfer
+        let erc721 =
+            transfer_log(1, vec![signature, B256::ZERO, B256::repeat_byte(0xBB), B256::ZERO]);
+
+        let archived: Vec<Archived<Transfer>> = vec![erc20.try_into()?, erc721.try_into()?];
+        assert!(archived[0].outcome.is_some());
+        assert!(archived[1].outcome.is_none());
+        sink.process_batch(&archived).await?;
+
+        let raw_logs = store::raw_log::store::Store::new(client.clone())
+            .get_raw_logs_between_block_numbers(&[], &[], 0, 100)
+            .await?;
+        assert_eq!(raw_logs.len(), 2);
+        let transfers =
+            store::transfer::store::Store::new(client).get_transfers_from_block_number(0).await?;
+        assert_eq!(transfers.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replay_rolls_back_replayed_addresses_only() -> Result<()> {
+        let client = Client::init("sqlite::memory:").await?;
+        let transfer_store = store::transfer::store::Store::new(client.clone());
+        let raw_log_store = store::raw_log::store::Store::new(client.clone());
+        let sink = RawLogSink::new(Arc::new(TransferSink { store: transfer_store }), raw_log_store);
+
+        let signature = keccak256("Transfer(address,address,uint256)");
+        let topics = vec![signature, B256::ZERO, B256::repeat_byte(0xBB)];
+        let replayed = Address::repeat_byte(0xAA);
+        let archived: Vec<Archived<Transfer>> = vec![
+            contract_transfer_log(replayed, 0, topics.clone()).try_into()?,
+            contract_transfer_log(Address::repeat_byte(0xAB), 1, topics).try_into()?,
+        ];
+        sink.process_batch(&archived).await?;
+
+        let args = Args {
+            addresses: ValueOrArray::Value(replayed),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block: Some(100),
+            checkpoint_interval: 1,
+            backfill_checkpoint_interval: None,
+            confirmations: 0,
+            tip: BlockTip::Latest,
+            poll_interval: Duration::ZERO,
+        };
+        let raw_log_store = store::raw_log::store::Store::new(client.clone());
+        let contract_addresses = vec![replayed.to_vec()];
+        let to_block = raw_log_store.get_last_raw_log_block_number(&contract_addresses).await?;
+        assert_eq!(to_block, Some(100));
+
+        let source = Arc::new(RawLogSource { store: raw_log_store });
+        let sink =
+            Arc::new(TransferSink { store: store::transfer::store::Store::new(client.clone()) });
+        assert_eq!(replay::replay(&args, 100, source, sink).await?, 1);
+
+        // The transfer of the contract left out of the replay is kept
+        let transfers =
+            store::transfer::store::Store::new(client).get_transfers_from_block_number(0).await?;
+        assert_eq!(transfers.len(), 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use alloy::primitives::{Address, B256, Bytes, LogData, TxHash, U256, keccak256};
+    use alloy::rpc::types::Log;
+    use engine::sink::{handle::Sink, token::TokenMetadataSink, transfer::TransferSink};
+    use eyre::Result;
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use store::client::Client;
+    use store::token::{model::Token, store::Store as TokenStore};
+    use store::transfer::{model::Transfer, store::Store as TransferStore};
+
+    // Execution reverted, as reported by geth
+    const EXECUTION_REVERTED: i64 = 3;
+
+    fn erc20() -> Address {
+        Address::repeat_byte(0xAA)
+    }
+
+    // Contract implementing `symbol()` only, reverting on the other getters
+    fn non_erc20() -> Address {
+        Address::repeat_byte(0xBB)
+    }
+
+    fn transfer(contract_address: Address, log_index: u64) -> Result<Transfer> {
+        let topics = vec![
+            B256::repeat_byte(0xDD),
+            Address::repeat_byte(0x0A).into_word(),
+            Address::repeat_byte(0x0B).into_word(),
+        ];
+        Log {
+            inner: alloy::primitives::Log {
+                address: contract_address,
+                data: LogData::new_unchecked(
+                    topics,
+                    Bytes::from(U256::from(1).to_be_bytes::<32>()),
+                ),
+            },
+            block_number: Some(100),
+            block_hash: Some(B256::repeat_byte(0x01)),
+            transaction_hash: Some(TxHash::repeat_byte(0xF1)),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+        .try_into()
+    }
+
+    fn selector(signature: &str) -> Bytes {
+        Bytes::copy_from_slice(&keccak256(signature)[..4])
+    }
+
+    // ABI encoded string returned by a getter
+    fn abi_string(value: &str) -> Bytes {
+        let mut encoded = U256::from(32).to_be_bytes::<32>().to_vec();
+        encoded.extend(U256::from(value.len()).to_be_bytes::<32>());
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.resize(value.len().div_ceil(32) * 32, 0);
+        encoded.extend(bytes);
+        encoded.into()
+    }
+
+    // Answers `eth_call` on the getters of both contracts
+    fn call(params: &Value) -> common::Response {
+        let call = &params[0];
+        let to: Address = serde_json::from_value(call["to"].clone())
+            .map_err(|e| (common::INTERNAL_ERROR, e.to_string()))?;
+        let input = call.get("input").unwrap_or(&call["data"]).clone();
+        let input: Bytes =
+            serde_json::from_value(input).map_err(|e| (common::INTERNAL_ERROR, e.to_string()))?;
+        let result = if input == selector("name()") && to == erc20() {
+            abi_string("Zama")
+        } else if input == selector("symbol()") {
+            abi_string(if to == erc20() { "ZAMA" } else { "NOPE" })
+        } else if input == selector("decimals()") && to == erc20() {
+            Bytes::from(U256::from(18).to_be_bytes::<32>())
+        } else {
+            return Err((EXECUTION_REVERTED, "execution reverted".to_string()));
+        };
+        Ok(json!(result))
+    }
+
+    async fn token_sink(
+        client: &Client,
+        calls: &Arc<AtomicUsize>,
+    ) -> Result<TokenMetadataSink<Transfer>> {
+        let calls = Arc::clone(calls);
+        let node_client = common::spawn_node(move |method, params| match method {
+            "eth_call" => {
+                calls.fetch_add(1, Ordering::Relaxed);
+                call(params)
+            }
+            _ => Err((common::METHOD_NOT_FOUND, "not found".to_string())),
+        })
+        .await?;
+        Ok(TokenMetadataSink::new(
+            Arc::new(TransferSink { store: TransferStore::new(client.clone()) }),
+            TokenStore::new(client.clone()),
+            node_client,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata_fetched_once_per_contract() -> Result<()> {
+        let client = Client::init("sqlite::memory:").await?;
+        let token_store = TokenStore::new(client.clone());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sink = token_sink(&client, &calls).await?;
+
+        // Metadata is fetched when a contract is first seen, one call per getter
+        sink.process(&transfer(erc20(), 0)?).await?;
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        let token = Token {
+            contract_address: erc20().to_vec(),
+            name: Some("Zama".to_string()),
+            symbol: Some("ZAMA".to_string()),
+            decimals: Some(18),
+        };
+        assert_eq!(token_store.get_token(erc20().as_slice()).await?, Some(token));
+
+        // Known contracts are not fetched again, stored ones neither after a restart
+        sink.process_batch(&[transfer(erc20(), 1)?, transfer(erc20(), 2)?]).await?;
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        let restarted = token_sink(&client, &calls).await?;
+        restarted.process(&transfer(erc20(), 3)?).await?;
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata_of_reverting_getters() -> Result<()> {
+        let client = Client::init("sqlite::memory:").await?;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sink = token_sink(&client, &calls).await?;
+
+        // Reverting getters leave their metadata out, without failing the processing
+        sink.process(&transfer(non_erc20(), 0)?).await?;
+        let token = Token {
+            contract_address: non_erc20().to_vec(),
+            name: None,
+            symbol: Some("NOPE".to_string()),
+            decimals: None,
+        };
+        let token_store = TokenStore::new(client);
+        assert_eq!(token_store.get_token(non_erc20().as_slice()).await?, Some(token));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use alloy::primitives::{Address, B256, Bytes, LogData, TxHash, U256};
+    use alloy::rpc::types::Log;
+    use engine::sink::{handle::Sink, transaction::TransactionSink, transfer::TransferSink};
+    use eyre::Result;
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use store::client::Client;
+    use store::transaction::store::Store as TransactionStore;
+    use store::transfer::{model::Transfer, store::Store as TransferStore};
+
+    const BLOCK_NUMBER: u64 = 100;
+
+    fn block_hash() -> B256 {
+        B256::repeat_byte(0x01)
+    }
+
+    fn receipt(transaction_hash: TxHash) -> Value {
+        json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": transaction_hash,
+            "transactionIndex": "0x0",
+            "blockHash": block_hash(),
+            "blockNumber": format!("{BLOCK_NUMBER:#x}"),
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "from": Address::repeat_byte(0x0F),
+            "to": Address::repeat_byte(0xAA),
+            "contractAddress": null,
+        })
+    }
+
+    fn transfer(transaction_hash: TxHash, log_index: u64) -> Result<Transfer> {
+        let topics = vec![
+            B256::repeat_byte(0xDD),
+            Address::repeat_byte(0x0A).into_word(),
+            Address::repeat_byte(0x0B).into_word(),
+        ];
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(
+                    topics,
+                    Bytes::from(U256::from(1).to_be_bytes::<32>()),
+                ),
+            },
+            block_number: Some(BLOCK_NUMBER),
+            block_hash: Some(block_hash()),
+            transaction_hash: Some(transaction_hash),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+        .try_into()
+    }
+
+    // Node answering receipts requests of the block with `eth_getBlockReceipts`, or the error
+    // queued for it, counting requests per method
+    struct Node {
+        requests: Mutex<HashMap<String, usize>>,
+        block_receipts_errors: Mutex<Vec<i64>>,
+    }
+
+    impl Node {
+        fn requests(&self, method: &str) -> usize {
+            self.requests.lock().unwrap().get(method).copied().unwrap_or_default()
+        }
+
+        fn answer(&self, method: &str, params: &Value) -> common::Response {
+            *self.requests.lock().unwrap().entry(method.to_string()).or_default() += 1;
+            let transaction_hashes = [TxHash::repeat_byte(0xF1), TxHash::repeat_byte(0xF2)];
+            match method {
+                "eth_getBlockReceipts" => match self.block_receipts_errors.lock().unwrap().pop() {
+                    Some(code) => Err((code, "failed".to_string())),
+                    None => Ok(json!(transaction_hashes.map(receipt))),
+                },
+                "eth_getTransactionReceipt" => {
+                    let transaction_hash: TxHash = serde_json::from_value(params[0].clone())
+                        .map_err(|e| (common::INTERNAL_ERROR, e.to_string()))?;
+                    Ok(receipt(transaction_hash))
+                }
+                _ => Err((common::METHOD_NOT_FOUND, "not found".to_string())),
+            }
+        }
+    }
+
+    async fn transaction_sink(
+        block_receipts_errors: Vec<i64>,
+    ) -> Result<(TransactionSink<Transfer>, TransactionStore, Arc<Node>)> {
+        let node = Arc::new(Node {
+            requests: Mutex::new(HashMap::new()),
+            block_receipts_errors: Mutex::new(block_receipts_errors),
+        });
+        let node_for_client = Arc::clone(&node);
+        let node_client =
+            common::spawn_node(move |method, params| node_for_client.answer(method, params))
+                .await?;
+
+        let client = Client::init("sqlite::memory:").await?;
+        let sink = TransactionSink::new(
+            Arc::new(TransferSink { store: TransferStore::new(client.clone()) }),
+            TransactionStore::new(client.clone()),
+            node_client,
+        );
+        Ok((sink, TransactionStore::new(client), node))
+    }
+
+    #[tokio::test]
+    async fn test_receipts_fetched_once_per_block() -> Result<()> {
+        let (sink, store, node) = transaction_sink(vec![]).await?;
+
+        // Live mode: the outcomes of a block are processed one by one
+        sink.process(&transfer(TxHash::repeat_byte(0xF1), 0)?).await?;
+        sink.process(&transfer(TxHash::repeat_byte(0xF1), 1)?).await?;
+        sink.process(&transfer(TxHash::repeat_byte(0xF2), 2)?).await?;
+        assert_eq!(node.requests("eth_getBlockReceipts"), 1);
+
+        let transactions = store.get_transactions_from_block_number(BLOCK_NUMBER).await?;
+        assert_eq!(transactions.len(), 2);
+
+        // Rolled back transactions are fetched again
+        sink.rollback(BLOCK_NUMBER - 1, &[]).await?;
+        sink.process(&transfer(TxHash::repeat_byte(0xF1), 0)?).await?;
+        assert_eq!(node.requests("eth_getBlockReceipts"), 2);
+        let transactions = store.get_transactions_from_block_number(BLOCK_NUMBER).await?;
+        assert_eq!(transactions.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_fallback_on_method_not_found_only() -> Result<()> {
+        // Errors are popped: an internal error first, then a missing method
+        let (sink, _, node) =
+            transaction_sink(vec![common::METHOD_NOT_FOUND, common::INTERNAL_ERROR]).await?;
+
+        // Internal errors fail the processing, block receipts are still requested afterwards
+        assert!(sink.process(&transfer(TxHash::repeat_byte(0xF1), 0)?).await.is_err());
+        assert_eq!(node.requests("eth_getTransactionReceipt"), 0);
+
+        // A missing method falls back to the receipts of every transaction, for good
+        sink.process(&transfer(TxHash::repeat_byte(0xF1), 0)?).await?;
+        assert_eq!(node.requests("eth_getBlockReceipts"), 2);
+        assert_eq!(node.requests("eth_getTransactionReceipt"), 1);
+
+        sink.process(&transfer(TxHash::repeat_byte(0xF2), 1)?).await?;
+        assert_eq!(node.requests("eth_getBlockReceipts"), 2);
+        assert_eq!(node.requests("eth_getTransactionReceipt"), 2);
+
+        Ok(())
+    }
+}
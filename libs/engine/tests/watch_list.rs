@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use alloy::eips::BlockNumberOrTag;
+    use alloy::primitives::{Address, B256, Bytes, LogData};
+    use alloy::rpc::types::{Log, ValueOrArray};
+    use engine::source::filter::{ChunkFilter, EventType, StreamFilter, Topics};
+    use engine::source::handle::Source;
+    use engine::source::watch_list::WatchListSource;
+    use engine::tip::BlockTip;
+    use eyre::Result;
+    use futures_util::StreamExt;
+    use futures_util::stream::{self, BoxStream};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+    use tokio::sync::watch;
+
+    // In-memory logs, filtered by address and block number
+    struct MemorySource {
+        logs: Vec<Log>,
+    }
+
+    impl MemorySource {
+        fn select(&self, addresses: &ValueOrArray<Address>, from: BlockNumberOrTag) -> Vec<Log> {
+            let from = from.as_number().unwrap_or_default();
+            self.logs
+                .iter()
+                .filter(|log| match addresses {
+                    ValueOrArray::Value(address) => log.address() == *address,
+                    ValueOrArray::Array(addresses) => addresses.contains(&log.address()),
+                })
+                .filter(|log| log.block_number.unwrap_or_default() >= from)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Source for MemorySource {
+        type Item = Log;
+
+        async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Log>> {
+            Ok(self.select(&filter.addresses, filter.from_block_number))
+        }
+
+        async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Log>> {
+            let logs = self.select(&filter.addresses, filter.from_block_number);
+            Ok(Box::pin(stream::iter(logs).chain(stream::pending())))
+        }
+    }
+
+    // Log filter: the stream only reports the logs from the chain head it is installed at on
+    struct FilterSource {
+        source: MemorySource,
+        head: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl Source for FilterSource {
+        type Item = Log;
+
+        async fn chunk(&self, filter: ChunkFilter) -> Result<Vec<Log>> {
+            let head = self.head.load(Ordering::SeqCst);
+            let logs = self.source.select(&filter.addresses, filter.from_block_number);
+            Ok(logs
+                .into_iter()
+                .filter(|log| log.block_number.unwrap_or_default() <= head)
+                .collect())
+        }
+
+        async fn stream(&self, filter: StreamFilter) -> Result<BoxStream<'static, Log>> {
+            let head = self.head.load(Ordering::SeqCst);
+            let logs = self.source.select(&filter.addresses, BlockNumberOrTag::Number(head));
+            Ok(Box::pin(stream::iter(logs).chain(stream::pending())))
+        }
+    }
+
+    fn log(address: Address, block_number: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: LogData::new_unchecked(vec![B256::repeat_byte(0xDD)], Bytes::new()),
+            },
+            block_number: Some(block_number),
+            ..Default::default()
+        }
+    }
+
+    fn stream_filter() -> StreamFilter {
+        StreamFilter {
+            addresses: ValueOrArray::Array(vec![]),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block_number: BlockNumberOrTag::Number(0),
+            poll_interval: Duration::from_millis(10),
+            tip: BlockTip::Latest,
+            confirmations: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_resubscribes_on_watch_list_change() -> Result<()> {
+        let watched = Address::repeat_byte(0x01);
+        let added = Address::repeat_byte(0x02);
+        let source = MemorySource { logs: vec![log(watched, 10), log(added, 5), log(added, 12)] };
+
+        let (addresses_tx, addresses_rx) = watch::channel(vec![watched]);
+        let watch_list = WatchListSource { source: Arc::new(source), addresses: addresses_rx };
+
+        let chunk_filter = ChunkFilter {
+            addresses: ValueOrArray::Array(vec![]),
+            event: EventType::Transfer,
+            topics: Topics::default(),
+            from_block_number: BlockNumberOrTag::Number(0),
+            to_block_number: BlockNumberOrTag::Number(20),
+        };
+        assert_eq!(watch_list.chunk(chunk_filter).await?.len(), 1);
+
+        let mut stream = watch_list.stream(stream_filter()).await?;
+        assert_eq!(stream.next().await.map(|log| log.address()), Some(watched));
+
+        // Re-subscribed from the last streamed block: earlier logs are left to backfill
+        addresses_tx.send_modify(|addresses| addresses.push(added));
+        let next = tokio::time::timeout(Duration::from_secs(1), stream.next()).await?;
+        assert_eq!(next.map(|log| (log.address(), log.block_number)), Some((watched, Some(10))));
+        let next = tokio::time::timeout(Duration::from_secs(1), stream.next()).await?;
+        assert_eq!(next.map(|log| (log.address(), log.block_number)), Some((added, Some(12))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resubscription_catches_up_blocks_not_polled() -> Result<()> {
+        let watched = Address::repeat_byte(0x01);
+        let added = Address::repeat_byte(0x02);
+        let logs = vec![log(watched, 10), log(added, 11), log(added, 12), log(added, 13)];
+        let source = Arc::new(FilterSource { source: MemorySource { logs }, head: 9.into() });
+
+        let (addresses_tx, addresses_rx) = watch::channel(vec![watched]);
+        let watch_list = WatchListSource { source: source.clone(), addresses: addresses_rx };
+        let mut stream = watch_list.stream(stream_filter()).await?;
+        assert_eq!(stream.next().await.map(|log| log.address()), Some(watched));
+
+        // Blocks 11 and 12 are mined before the filter is installed again
+        source.head.store(12, Ordering::SeqCst);
+        addresses_tx.send_modify(|addresses| addresses.push(added));
+
+        let mut streamed = vec![];
+        for _ in 0..4 {
+            let next = tokio::time::timeout(Duration::from_secs(1), stream.next()).await?;
+            streamed.extend(next.map(|log| (log.address(), log.block_number.unwrap_or_default())));
+        }
+        // The last streamed block is caught up again, the filter reported block 12 only once
+        assert_eq!(streamed, vec![(watched, 10), (added, 11), (added, 12), (added, 13)]);
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod tests {
+    use alloy::eips::BlockNumberOrTag;
+    use alloy::primitives::{Address, B256, BlockNumber, Bytes, LogData};
+    use alloy::rpc::types::{Header, Log, ValueOrArray};
+    use alloy::signers::local::PrivateKeySigner;
+    use chain::rpc::NodeClient;
+    use engine::source::filter::{EventType, StreamFilter, Topics};
+    use engine::source::handle::Source;
+    use engine::source::ws_log::WsLogSource;
+    use engine::tip::BlockTip;
+    use eyre::Result;
+    use futures_util::StreamExt;
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio::sync::broadcast;
+
+    const HEADS_SUBSCRIPTION: &str = "0x1";
+    const LOGS_SUBSCRIPTION: &str = "0x2";
+
+    // Notification pushed by the node to the connected client
+    #[derive(Debug, Clone)]
+    enum Push {
+        Log(Box<Log>),
+        Head(BlockNumber),
+        // Drops the connection, the client reconnecting on its own
+        Disconnect,
+    }
+
+    // Node served over IPC, answering subscriptions, the chain head and the logs of the chain
+    struct Node {
+        head: AtomicU64,
+        logs: Vec<Log>,
+        // `newHeads` subscriptions received, over every connection
+        subscriptions: AtomicUsize,
+        pushes: broadcast::Sender<Push>,
+    }
+
+    impl Node {
+        fn answer(&self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+            match method {
+                "eth_subscribe" if params[0] == "newHeads" => {
+                    self.subscriptions.fetch_add(1, Ordering::SeqCst);
+                    Ok(json!(HEADS_SUBSCRIPTION))
+                }
+                "eth_subscribe" => Ok(json!(LOGS_SUBSCRIPTION)),
+                "eth_blockNumber" => Ok(json!(format!("{:#x}", self.head.load(Ordering::SeqCst)))),
+                "eth_getLogs" => {
+                    let block_number = |param: &Value| {
+                        param.as_str().and_then(|number| {
+                            u64::from_str_radix(number.trim_start_matches("0x"), 16).ok()
+                        })
+                    };
+                    let from = block_number(&params[0]["fromBlock"]).unwrap_or_default();
+                    let to = block_number(&params[0]["toBlock"]).unwrap_or(u64::MAX);
+                    let logs: Vec<&Log> = self
+                        .logs
+                        .iter()
+                        .filter(|log| (from..=to).contains(&log.block_number.unwrap_or_default()))
+                        .collect();
+                    Ok(json!(logs))
+                }
+                _ => Err((-32601, "not found".to_string())),
+            }
+        }
+
+        async fn wait_for_subscriptions(&self, subscriptions: usize) -> Result<()> {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while self.subscriptions.load(Ordering::SeqCst) < subscriptions {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await?;
+            Ok(())
+        }
+    }
+
+    async fn serve(node: Arc<Node>, stream: UnixStream) -> Result<()> {
+        let mut pushes = node.pushes.subscribe();
+        let (mut reader, mut writer) = stream.into_split();
+        let mut buffer = vec![];
+        let mut chunk = [0u8; 4096];
+        loop {
+            let message = tokio::select! {
+                read = reader.read(&mut chunk) => {
+                    let read = read?;
+                    if read == 0 {
+                        return Ok(());
+                    }
+                    buffer.extend_from_slice(&chunk[..read]);
+                    // Requests are concatenated JSON values, possibly split over reads
+                    let mut requests = serde_json::Deserializer::from_slice(&buffer).into_iter();
+                    let mut responses = vec![];
+                    while let Some(Ok(request)) = requests.next() {
+                        let request: Value = request;
+                        let method = request["method"].as_str().unwrap_or_default();
+                        responses.push(match node.answer(method, &request["params"]) {
+                            Ok(result) => {
+                                json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+                            }
+                            Err((code, message)) => json!({
+                                "jsonrpc": "2.0",
+                                "id": request["id"],
+                                "error": { "code": code, "message": message },
+                            }),
+                        });
+                    }
+                    let parsed = requests.byte_offset();
+                    buffer.drain(..parsed);
+                    responses.iter().map(Value::to_string).collect::<String>()
+                }
+                push = pushes.recv() => {
+                    let (subscription, result) = match push? {
+                        Push::Log(log) => (LOGS_SUBSCRIPTION, json!(*log)),
+                        Push::Head(block_number) => {
+                            let mut header: Header = Header::default();
+                            header.inner.number = block_number;
+                            (HEADS_SUBSCRIPTION, json!(header))
+                        }
+                        Push::Disconnect => return Ok(()),
+                    };
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": "eth_subscription",
+                        "params": { "subscription": subscription, "result": result },
+                    })
+                    .to_string()
+                }
+            };
+            writer.write_all(message.as_bytes()).await?;
+        }
+    }
+
+    async fn spawn_node(head: BlockNumber, logs: Vec<Log>) -> Result<(NodeClient, Arc<Node>)> {
+        let path = std::env::temp_dir().join(format!("ws-log-{}.ipc", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        let node = Arc::new(Node {
+            head: AtomicU64::new(head),
+            logs,
+            subscriptions: AtomicUsize::new(0),
+            pushes: broadcast::channel(16).0,
+        });
+        let node_for_server = Arc::clone(&node);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(Arc::clone(&node_for_server), stream));
+            }
+        });
+        let node_client = NodeClient::connect_ipc(path, PrivateKeySigner::random()).await?;
+        Ok((node_client, node))
+    }
+
+    fn log(block_number: BlockNumber) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(vec![B256::repeat_byte(0xDD)], Bytes::new()),
+            },
+            block_number: Some(block_number),
+            block_hash: Some(B256::repeat_byte(block_number as u8)),
+            transaction_hash: Some(B256::repeat_byte(0xF0)),
+            log_index: Some(0),
+            ..Default::default()
+        }
+    }
+
+    fn stream_filter(from_block_number: BlockNumber) -> StreamFilter {
+        StreamFilter {
+            addresses: ValueOrArray::Value(Address::repeat_byte(0xAA)),
+            event: EventType::AnyTransfer,
+            topics: Topics::default(),
+            from_block_number: BlockNumberOrTag::Number(from_block_number),
+            poll_interval: Duration::from_millis(10),
+            tip: BlockTip::Latest,
+            confirmations: 0,
+        }
+    }
+
+    async fn next(stream: &mut (impl StreamExt<Item = Log> + Unpin)) -> Result<Option<Log>> {
+        Ok(tokio::time::timeout(Duration::from_secs(5), stream.next()).await?)
+    }
+
+    #[tokio::test]
+    async fn test_logs_released_on_heads_and_gap_filled_after_reconnect() -> Result<()> {
+        let (node_client, node) = spawn_node(10, vec![log(10), log(11), log(12), log(13)]).await?;
+        let source = WsLogSource { node_client };
+        let mut stream = source.stream(stream_filter(10)).await?;
+
+        // Blocks up to the head at subscription are gap-filled
+        assert_eq!(next(&mut stream).await?, Some(log(10)));
+
+        // Pushed logs are held back until the head of their block
+        node.pushes.send(Push::Log(Box::new(log(11))))?;
+        let held = tokio::time::timeout(Duration::from_millis(200), stream.next()).await;
+        assert!(held.is_err());
+        node.pushes.send(Push::Head(11))?;
+        assert_eq!(next(&mut stream).await?, Some(log(11)));
+
+        // Blocks mined while disconnected are gap-filled once the next head is received
+        node.pushes.send(Push::Disconnect)?;
+        node.head.store(14, Ordering::SeqCst);
+        node.wait_for_subscriptions(2).await?;
+        node.pushes.send(Push::Head(14))?;
+        assert_eq!(next(&mut stream).await?, Some(log(12)));
+        assert_eq!(next(&mut stream).await?, Some(log(13)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+-- Indexes: transfers
+
+CREATE INDEX IF NOT EXISTS idx_transfers_block_number
+    ON transfers (chain_id, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_transfers_block_hash
+    ON transfers (block_hash);
+
+CREATE INDEX IF NOT EXISTS idx_transfers_contract_address_block_number
+    ON transfers (chain_id, contract_address, block_number);
+
+-------------------------------------------------------------
+
+-- Indexes: supply_changes
+
+CREATE INDEX IF NOT EXISTS idx_supply_changes_contract_address_block_number
+    ON supply_changes (chain_id, contract_address, block_number);
+
+-------------------------------------------------------------
+
+-- Indexes: balance_snapshots
+
+CREATE INDEX IF NOT EXISTS idx_balance_snapshots_block_number
+    ON balance_snapshots (chain_id, job, block_number);
+
+-------------------------------------------------------------
+
+-- Indexes: nft_transfers
+
+CREATE INDEX IF NOT EXISTS idx_nft_transfers_block_number
+    ON nft_transfers (chain_id, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_nft_transfers_block_hash
+    ON nft_transfers (block_hash);
+
+CREATE INDEX IF NOT EXISTS idx_nft_transfers_contract_address_block_number
+    ON nft_transfers (chain_id, contract_address, block_number);
+
+-------------------------------------------------------------
+
+-- Indexes: transfer_singles
+
+CREATE INDEX IF NOT EXISTS idx_transfer_singles_block_number
+    ON transfer_singles (chain_id, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_transfer_singles_block_hash
+    ON transfer_singles (block_hash);
+
+-------------------------------------------------------------
+
+-- Indexes: transfer_batches
+
+CREATE INDEX IF NOT EXISTS idx_transfer_batches_block_number
+    ON transfer_batches (chain_id, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_transfer_batches_block_hash
+    ON transfer_batches (block_hash);
+
+-------------------------------------------------------------
+
+-- Indexes: approvals
+
+CREATE INDEX IF NOT EXISTS idx_approvals_block_number
+    ON approvals (chain_id, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_approvals_block_hash
+    ON approvals (block_hash);
+
+-------------------------------------------------------------
+
+-- Indexes: allowances
+
+CREATE INDEX IF NOT EXISTS idx_allowances_block_number
+    ON allowances (chain_id, block_number);
+
+-------------------------------------------------------------
+
+-- Indexes: contracts
+
+CREATE INDEX IF NOT EXISTS idx_contracts_factory_address
+    ON contracts (chain_id, factory_address);
+
+-------------------------------------------------------------
+
+-- Indexes: checkpoints
+
+CREATE INDEX IF NOT EXISTS idx_checkpoints_block_number
+    ON checkpoints (chain_id, job, block_number DESC);
+
+CREATE INDEX IF NOT EXISTS idx_checkpoints_block_hash
+    ON checkpoints (chain_id, job, block_hash DESC);
+
+-------------------------------------------------------------
+
+-- Indexes: blocks
+
+CREATE INDEX IF NOT EXISTS idx_blocks_timestamp
+    ON blocks (timestamp);
+
+-------------------------------------------------------------
+
+-- Indexes: transactions
+
+CREATE INDEX IF NOT EXISTS idx_transactions_block_number
+    ON transactions (chain_id, block_number);
+
+-------------------------------------------------------------
+
+-- Indexes: raw_logs
+
+CREATE INDEX IF NOT EXISTS idx_raw_logs_block_number
+    ON raw_logs (chain_id, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_raw_logs_contract_address_block_number
+    ON raw_logs (chain_id, contract_address, block_number);
@@ -2,6 +2,10 @@
 
 CREATE TABLE IF NOT EXISTS transfers (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
     block_number        INTEGER NOT NULL,
     block_hash          BLOB NOT NULL,
     transaction_hash    BLOB NOT NULL,
@@ -12,14 +16,238 @@ CREATE TABLE IF NOT EXISTS transfers (
     amount              BLOB NOT NULL,
 
     -- Constraint to ensure no duplicate logs
-    UNIQUE(transaction_hash, log_index)
+    UNIQUE(chain_id, transaction_hash, log_index)
 );
 
-CREATE INDEX IF NOT EXISTS idx_transfers_block_number
-    ON transfers (block_number);
+-------------------------------------------------------------
+
+-- Table: supply_changes (mints and burns, derived from transfers)
+
+CREATE TABLE IF NOT EXISTS supply_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    amount              BLOB NOT NULL,
+
+    -- Constraint to ensure no duplicate logs
+    UNIQUE(chain_id, transaction_hash, log_index)
+);
+
+-------------------------------------------------------------
+
+-- Table: balances (running balance per token and holder, derived from transfers)
+
+CREATE TABLE IF NOT EXISTS balances (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job folding its transfers into the balance
+    job                 TEXT NOT NULL DEFAULT 'default',
+    contract_address    BLOB NOT NULL,
+    holder_address      BLOB NOT NULL,
+    balance             BLOB NOT NULL,
+
+    -- Each holder has a single balance per token and job
+    UNIQUE(chain_id, job, contract_address, holder_address)
+);
+
+-------------------------------------------------------------
+
+-- Table: balance_snapshots (balances at the checkpoints of a job, folded from its transfers)
+
+CREATE TABLE IF NOT EXISTS balance_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job whose transfers are folded, the only one rolling the snapshot back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    -- Checkpoint the snapshot was taken at
+    block_number        INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    holder_address      BLOB NOT NULL,
+    balance             BLOB NOT NULL,
+
+    -- Only holders moved since the previous snapshot have a row, once per checkpoint
+    UNIQUE(chain_id, job, contract_address, holder_address, block_number)
+);
+
+-------------------------------------------------------------
+
+-- Table: nft_transfers
+
+CREATE TABLE IF NOT EXISTS nft_transfers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    from_address        BLOB NOT NULL,
+    to_address          BLOB NOT NULL,
+    token_id            BLOB NOT NULL,
+
+    -- Constraint to ensure no duplicate logs
+    UNIQUE(chain_id, transaction_hash, log_index)
+);
+
+-------------------------------------------------------------
+
+-- Table: transfer_singles
+
+CREATE TABLE IF NOT EXISTS transfer_singles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    operator_address    BLOB NOT NULL,
+    from_address        BLOB NOT NULL,
+    to_address          BLOB NOT NULL,
+    token_id            BLOB NOT NULL,
+    amount              BLOB NOT NULL,
 
-CREATE INDEX IF NOT EXISTS idx_transfers_block_hash
-    ON transfers (block_hash);
+    -- Constraint to ensure no duplicate logs
+    UNIQUE(chain_id, transaction_hash, log_index)
+);
+
+-------------------------------------------------------------
+
+-- Table: transfer_batches (one row per token id / amount pair of a batch)
+
+CREATE TABLE IF NOT EXISTS transfer_batches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+    batch_index         INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    operator_address    BLOB NOT NULL,
+    from_address        BLOB NOT NULL,
+    to_address          BLOB NOT NULL,
+    token_id            BLOB NOT NULL,
+    amount              BLOB NOT NULL,
+
+    -- Constraint to ensure no duplicate pairs
+    UNIQUE(chain_id, transaction_hash, log_index, batch_index)
+);
+
+-------------------------------------------------------------
+
+-- Table: approvals
+
+CREATE TABLE IF NOT EXISTS approvals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    owner_address       BLOB NOT NULL,
+    spender_address     BLOB NOT NULL,
+    amount              BLOB NOT NULL,
+
+    -- Constraint to ensure no duplicate logs
+    UNIQUE(chain_id, transaction_hash, log_index)
+);
+
+-------------------------------------------------------------
+
+-- Table: allowances (latest approval per token, owner and spender)
+
+CREATE TABLE IF NOT EXISTS allowances (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    contract_address    BLOB NOT NULL,
+    owner_address       BLOB NOT NULL,
+    spender_address     BLOB NOT NULL,
+    amount              BLOB NOT NULL,
+    block_number        INTEGER NOT NULL,
+    log_index           INTEGER NOT NULL,
+
+    -- Each (token, owner, spender) holds a single allowance
+    UNIQUE(chain_id, contract_address, owner_address, spender_address)
+);
+
+-------------------------------------------------------------
+
+-- Table: tokens (metadata of indexed contracts)
+
+CREATE TABLE IF NOT EXISTS tokens (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    contract_address    BLOB NOT NULL,
+    name                TEXT,
+    symbol              TEXT,
+    decimals            INTEGER,
+
+    -- Each contract metadata is fetched once per chain
+    UNIQUE(chain_id, contract_address)
+);
+
+-------------------------------------------------------------
+
+-- Table: contracts (discovered from factory creation events)
+
+CREATE TABLE IF NOT EXISTS contracts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    contract_address    BLOB NOT NULL,
+    factory_address     BLOB NOT NULL,
+    block_number        INTEGER NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+
+    -- Each contract is created only once per chain
+    UNIQUE(chain_id, contract_address)
+);
+
+-------------------------------------------------------------
+
+-- Table: watched_addresses (added to or removed from the watch list of a running job)
+
+CREATE TABLE IF NOT EXISTS watched_addresses (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id                INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job whose watch list changed
+    job                     TEXT NOT NULL DEFAULT 'default',
+    address                 BLOB NOT NULL,
+    -- False once removed, overriding the addresses the job was started with
+    watched                 BOOLEAN NOT NULL,
+    -- Next block to backfill (the backfill checkpoint), up to the block the address was added at
+    backfill_from_block     INTEGER,
+    backfill_to_block       INTEGER,
+
+    -- Only the latest change of an address is kept
+    UNIQUE(chain_id, job, address)
+);
 
 -------------------------------------------------------------
 
@@ -27,16 +255,83 @@ CREATE INDEX IF NOT EXISTS idx_transfers_block_hash
 
 CREATE TABLE IF NOT EXISTS checkpoints (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id        INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job the checkpoint is the cursor of
+    job             TEXT NOT NULL DEFAULT 'default',
     block_number    INTEGER NOT NULL,
     block_hash      BLOB NOT NULL,
     parent_hash     BLOB NOT NULL,
 
-    -- Each block should appear only once
-    UNIQUE(block_number, block_hash)
+    -- Each block should appear only once per chain and job
+    UNIQUE(chain_id, job, block_number, block_hash)
+);
+
+-------------------------------------------------------------
+
+-- Table: blocks (headers of blocks containing indexed logs)
+
+CREATE TABLE IF NOT EXISTS blocks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id        INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job recording the header, rolled back along with its outcomes
+    job             TEXT NOT NULL DEFAULT 'default',
+    block_number    INTEGER NOT NULL,
+    block_hash      BLOB NOT NULL,
+    parent_hash     BLOB NOT NULL,
+    timestamp       INTEGER NOT NULL,
+
+    -- Each block should appear only once per chain and job
+    UNIQUE(chain_id, job, block_number, block_hash)
+);
+
+-------------------------------------------------------------
+
+-- Table: transactions (receipts of transactions emitting indexed logs)
+
+CREATE TABLE IF NOT EXISTS transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    tx_from             BLOB NOT NULL,
+    tx_to               BLOB,
+    gas_used            INTEGER NOT NULL,
+    effective_gas_price BLOB NOT NULL,
+    status              BOOLEAN NOT NULL,
+
+    -- Each transaction should appear only once per chain
+    UNIQUE(chain_id, transaction_hash)
 );
 
-CREATE INDEX IF NOT EXISTS idx_checkpoints_block_number
-    ON checkpoints (block_number DESC);
+-------------------------------------------------------------
 
-CREATE INDEX IF NOT EXISTS idx_checkpoints_block_hash
-    ON checkpoints (block_hash DESC);
+-- Table: raw_logs (undecoded logs, archived for re-decoding)
+
+CREATE TABLE IF NOT EXISTS raw_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    block_timestamp     INTEGER,
+    transaction_hash    BLOB NOT NULL,
+    transaction_index   INTEGER,
+    log_index           INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL,
+    topic0              BLOB,
+    topic1              BLOB,
+    topic2              BLOB,
+    topic3              BLOB,
+    data                BLOB NOT NULL,
+
+    -- Each log should appear only once per chain
+    UNIQUE(chain_id, transaction_hash, log_index)
+);
@@ -0,0 +1,42 @@
+use crate::approval::model::Approval;
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use std::convert::TryFrom;
+
+// ERC-20 `Approval(address,address,uint256)` indexes `owner` and `spender`:
+// 3 topics (signature, owner, spender) and the amount in data.
+const ERC20_APPROVAL_TOPICS: usize = 3;
+
+impl TryFrom<Log> for Approval {
+    type Error = eyre::Report;
+
+    fn try_from(log: Log) -> Result<Self> {
+        let nbr_of_topics = log.topics().len();
+        if nbr_of_topics != ERC20_APPROVAL_TOPICS {
+            return Err(eyre!("not an ERC-20 approval: {nbr_of_topics:?} topics"));
+        }
+        Ok(Self {
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+            contract_address: log.address().to_vec(),
+            owner_address: log
+                .topics()
+                .get(1)
+                .ok_or_else(|| eyre!("missing owner"))?
+                .as_slice()
+                .to_vec(),
+            spender_address: log
+                .topics()
+                .get(2)
+                .ok_or_else(|| eyre!("missing spender"))?
+                .as_slice()
+                .to_vec(),
+            amount: log.data().data.to_vec(),
+        })
+    }
+}
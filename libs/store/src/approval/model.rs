@@ -0,0 +1,24 @@
+use sqlx::FromRow;
+
+#[derive(Clone, FromRow, PartialEq, PartialOrd)]
+pub struct Approval {
+    pub block_number: i64,
+    pub block_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub log_index: i64,
+    pub contract_address: Vec<u8>,
+    pub owner_address: Vec<u8>,
+    pub spender_address: Vec<u8>,
+    pub amount: Vec<u8>,
+}
+
+// Latest allowance per (token, owner, spender), derived from approvals
+#[derive(Clone, FromRow, PartialEq, PartialOrd)]
+pub struct Allowance {
+    pub contract_address: Vec<u8>,
+    pub owner_address: Vec<u8>,
+    pub spender_address: Vec<u8>,
+    pub amount: Vec<u8>,
+    pub block_number: i64,
+    pub log_index: i64,
+}
@@ -0,0 +1,34 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{
+    approval::model::{Allowance, Approval},
+    utils,
+};
+
+impl Debug for Approval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Approval")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("log_index", &self.log_index)
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("owner_address", &utils::bytes_to_address(&self.owner_address[..]))
+            .field("spender_address", &utils::bytes_to_address(&self.spender_address[..]))
+            .field("amount", &utils::bytes_to_u256(&self.amount))
+            .finish()
+    }
+}
+
+impl Debug for Allowance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Allowance")
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("owner_address", &utils::bytes_to_address(&self.owner_address[..]))
+            .field("spender_address", &utils::bytes_to_address(&self.spender_address[..]))
+            .field("amount", &utils::bytes_to_u256(&self.amount))
+            .field("block_number", &self.block_number)
+            .field("log_index", &self.log_index)
+            .finish()
+    }
+}
@@ -0,0 +1,252 @@
+use crate::approval::model::{Allowance, Approval};
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::utils::contract_filter;
+use alloy::primitives::{BlockNumber, U256};
+use eyre::Result;
+use sqlx::{Error, Sqlite, Transaction};
+
+// Approvals and allowances of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // APPROVAL LOGS
+    // ---------------------------
+
+    pub async fn insert_approval(&self, log: &Approval) -> Result<(), Error> {
+        self.insert_approvals_batch(std::slice::from_ref(log)).await
+    }
+
+    /// Inserts multiple approvals in batches, respecting SQLite's max variable limit.
+    /// Allowances are updated within the same transaction.
+    pub async fn insert_approvals_batch(&self, approvals: &[Approval]) -> Result<(), Error> {
+        if approvals.is_empty() {
+            return Ok(());
+        }
+
+        // SQLite variable limit = 999 by default
+        const COLS: usize = 10;
+        const SQLITE_MAX_VARIABLES: usize = 999;
+        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        for batch in approvals.chunks(max_rows_per_batch) {
+            let values_placeholders = (0..batch.len())
+                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            // SQLite skips rows that violate the constraint, keeps the rest.
+            let mut query = String::from(
+                "INSERT OR IGNORE INTO approvals (
+                    chain_id, job, block_number, block_hash, transaction_hash, log_index,
+                    contract_address, owner_address, spender_address, amount
+                ) VALUES ",
+            );
+            query.push_str(&values_placeholders);
+
+            let mut q = sqlx::query(&query);
+            for log in batch {
+                q = q
+                    .bind(self.chain_id)
+                    .bind(&self.job)
+                    .bind(log.block_number)
+                    .bind(&log.block_hash)
+                    .bind(&log.transaction_hash)
+                    .bind(log.log_index)
+                    .bind(&log.contract_address)
+                    .bind(&log.owner_address)
+                    .bind(&log.spender_address)
+                    .bind(&log.amount);
+            }
+            q.execute(&mut *tx).await?;
+
+            upsert_allowances(&mut tx, self.chain_id, batch).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_approvals_from_block_number(
+        &self,
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<Approval>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, owner_address, spender_address, amount
+            FROM approvals
+            WHERE chain_id = ? AND block_number >= ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(logs)
+    }
+
+    /// Removes the approvals of the job above the given block number, of the given contracts
+    /// only if any, and restores the allowances they had overridden.
+    pub async fn delete_approvals_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let mut tx = self.client.pool().begin().await?;
+
+        let query = format!(
+            "DELETE FROM approvals WHERE chain_id = ? AND job = ? AND block_number > ?{}",
+            contract_filter(contract_addresses)
+        );
+        let mut q =
+            sqlx::query(&query).bind(self.chain_id).bind(&self.job).bind(block_number as i64);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        let result = q.execute(&mut *tx).await?;
+
+        let query = format!(
+            "DELETE FROM allowances WHERE chain_id = ? AND block_number > ?{}",
+            contract_filter(contract_addresses)
+        );
+        let mut q = sqlx::query(&query).bind(self.chain_id).bind(block_number as i64);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        q.execute(&mut *tx).await?;
+
+        // Allowances left untouched are still the latest ones, only restore missing ones
+        // (from the approvals of every job)
+        let query = r#"
+            INSERT OR IGNORE INTO allowances (
+                chain_id, contract_address, owner_address, spender_address,
+                amount, block_number, log_index
+            )
+            SELECT
+                chain_id, contract_address, owner_address, spender_address,
+                amount, block_number, log_index
+            FROM (
+                SELECT *, ROW_NUMBER() OVER (
+                    PARTITION BY contract_address, owner_address, spender_address
+                    ORDER BY block_number DESC, log_index DESC
+                ) AS row_number
+                FROM approvals
+                WHERE chain_id = ?
+            )
+            WHERE row_number = 1
+            "#;
+        sqlx::query(query).bind(self.chain_id).execute(&mut *tx).await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ---------------------------
+    // ALLOWANCES
+    // ---------------------------
+
+    pub async fn get_allowances_from_block_number(
+        &self,
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<Allowance>, Error> {
+        let query = r#"
+            SELECT
+                contract_address, owner_address, spender_address, amount, block_number, log_index
+            FROM allowances
+            WHERE chain_id = ? AND block_number >= ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let allowances = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(allowances)
+    }
+
+    // Allowances set to the max uint256, commonly used as an "infinite" approval
+    pub async fn get_unlimited_allowances_from_block_number(
+        &self,
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<Allowance>, Error> {
+        let query = r#"
+            SELECT
+                contract_address, owner_address, spender_address, amount, block_number, log_index
+            FROM allowances
+            WHERE chain_id = ? AND block_number >= ? AND amount = ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let allowances = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .bind(U256::MAX.to_be_bytes::<32>().to_vec())
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(allowances)
+    }
+}
+
+// Keeps the latest approval (by block number and log index) per (chain, token, owner, spender)
+async fn upsert_allowances(
+    tx: &mut Transaction<'_, Sqlite>,
+    chain_id: i64,
+    approvals: &[Approval],
+) -> Result<(), Error> {
+    let values_placeholders =
+        (0..approvals.len()).map(|_| "(?, ?, ?, ?, ?, ?, ?)").collect::<Vec<_>>().join(", ");
+
+    let mut query = String::from(
+        "INSERT INTO allowances (
+            chain_id, contract_address, owner_address, spender_address,
+            amount, block_number, log_index
+        ) VALUES ",
+    );
+    query.push_str(&values_placeholders);
+    query.push_str(
+        " ON CONFLICT (chain_id, contract_address, owner_address, spender_address) DO UPDATE SET
+            amount = excluded.amount,
+            block_number = excluded.block_number,
+            log_index = excluded.log_index
+        WHERE (excluded.block_number, excluded.log_index)
+            > (allowances.block_number, allowances.log_index)",
+    );
+
+    let mut q = sqlx::query(&query);
+    for log in approvals {
+        q = q
+            .bind(chain_id)
+            .bind(&log.contract_address)
+            .bind(&log.owner_address)
+            .bind(&log.spender_address)
+            .bind(&log.amount)
+            .bind(log.block_number)
+            .bind(log.log_index);
+    }
+    q.execute(&mut **tx).await?;
+
+    Ok(())
+}
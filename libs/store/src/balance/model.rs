@@ -0,0 +1,10 @@
+use sqlx::FromRow;
+
+// Running balance of a holder, derived from transfers.
+// Stored as a signed 256-bit integer, since indexing may start after a holder received tokens.
+#[derive(Clone, FromRow, PartialEq, PartialOrd)]
+pub struct Balance {
+    pub contract_address: Vec<u8>,
+    pub holder_address: Vec<u8>,
+    pub balance: Vec<u8>,
+}
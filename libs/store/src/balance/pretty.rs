@@ -0,0 +1,13 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{balance::model::Balance, utils};
+
+impl Debug for Balance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Balance")
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("holder_address", &utils::bytes_to_address(&self.holder_address[..]))
+            .field("balance", &utils::bytes_to_i256(&self.balance))
+            .finish()
+    }
+}
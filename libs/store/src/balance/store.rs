@@ -0,0 +1,453 @@
+use crate::balance::model::Balance;
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::transfer::{model::Transfer, store as transfer_store};
+use crate::utils;
+use alloy::primitives::{BlockNumber, I256, U256};
+use eyre::{Result, eyre};
+use sqlx::{Error, Sqlite, Transaction};
+use std::collections::BTreeMap;
+
+// Balance deltas keyed by (contract, holder)
+type Deltas = BTreeMap<(Vec<u8>, Vec<u8>), I256>;
+
+// Transfers are persisted along with the balances they update, in the same transaction
+#[derive(Clone)]
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // TRANSFER LOGS
+    // ---------------------------
+
+    pub async fn insert_transfer(&self, log: &Transfer) -> Result<(), Error> {
+        self.insert_transfers_batch(std::slice::from_ref(log)).await
+    }
+
+    /// Inserts multiple transfers and applies them to the balances.
+    /// Duplicated transfers are ignored, and so are their balance updates.
+    pub async fn insert_transfers_batch(&self, transfers: &[Transfer]) -> Result<(), Error> {
+        if transfers.is_empty() {
+            return Ok(());
+        }
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        let inserted =
+            transfer_store::insert_transfers(&mut tx, self.chain_id, &self.job, transfers).await?;
+        apply_deltas(&mut tx, self.chain_id, &self.job, deltas(&inserted, false)).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Deletes the transfers of the job above the given block number, of the given contracts
+    /// only if any, and reverts them from the balances. Balance snapshots above it are dropped.
+    pub async fn delete_transfers_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let mut tx = self.client.pool().begin().await?;
+        let deleted = transfer_store::delete_transfers_after(
+            &mut tx,
+            self.chain_id,
+            &self.job,
+            block_number,
+            contract_addresses,
+        )
+        .await?;
+        apply_deltas(&mut tx, self.chain_id, &self.job, deltas(&deleted, true)).await?;
+
+        // Snapshots of every contract are dropped: the next one folds all transfers
+        // since the previous snapshot, replayed ones included
+        let query = r#"
+            DELETE FROM balance_snapshots
+            WHERE chain_id = ? AND job = ? AND block_number > ?
+            "#;
+        sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block_number as i64)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(deleted.len() as u64)
+    }
+
+    // ---------------------------
+    // BALANCES
+    // ---------------------------
+
+    pub async fn get_balances(&self) -> Result<Vec<Balance>, Error> {
+        let query = r#"
+            SELECT contract_address, holder_address, balance
+            FROM balances
+            WHERE chain_id = ? AND job = ?
+            ORDER BY contract_address ASC, holder_address ASC
+            "#;
+        let balances = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(balances)
+    }
+
+    pub async fn get_balance(
+        &self,
+        contract_address: &[u8],
+        holder_address: &[u8],
+    ) -> Result<Option<Balance>, Error> {
+        let query = r#"
+            SELECT contract_address, holder_address, balance
+            FROM balances
+            WHERE chain_id = ? AND job = ? AND contract_address = ? AND holder_address = ?
+            "#;
+        let balance = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(contract_address)
+            .bind(holder_address)
+            .fetch_optional(self.client.pool())
+            .await?;
+
+        Ok(balance)
+    }
+
+    // ---------------------------
+    // BALANCE SNAPSHOTS
+    // ---------------------------
+
+    /// Snapshots the balances of the job at the given checkpoint, folded from its transfers.
+    /// Only the holders moved since the previous snapshot get a row.
+    pub async fn snapshot_balances(&self, block_number: BlockNumber) -> Result<u64, Error> {
+        let mut tx = self.client.pool().begin().await?;
+
+        let query = r#"
+            SELECT MAX(block_number)
+            FROM balance_snapshots
+            WHERE chain_id = ? AND job = ? AND block_number < ?
+            "#;
+        let previous_block_number: Option<i64> = sqlx::query_scalar(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block_number as i64)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, from_address, to_address, amount
+            FROM transfers
+            WHERE chain_id = ? AND job = ? AND block_number > ? AND block_number <= ?
+            "#;
+        let transfers: Vec<Transfer> = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(previous_block_number.unwrap_or(-1))
+            .bind(block_number as i64)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let mut rows_affected = 0;
+        for ((contract_address, holder_address), delta) in deltas(&transfers, false) {
+            let query = r#"
+                SELECT balance
+                FROM balance_snapshots
+                WHERE chain_id = ? AND job = ? AND contract_address = ? AND holder_address = ?
+                ORDER BY block_number DESC
+                LIMIT 1
+                "#;
+            let balance: Option<Vec<u8>> = sqlx::query_scalar(query)
+                .bind(self.chain_id)
+                .bind(&self.job)
+                .bind(&contract_address)
+                .bind(&holder_address)
+                .fetch_optional(&mut *tx)
+                .await?;
+            let balance = to_i256(balance.as_deref()).wrapping_add(delta);
+
+            let query = r#"
+                INSERT OR IGNORE INTO balance_snapshots (
+                    chain_id, job, block_number, contract_address, holder_address, balance
+                ) VALUES (?, ?, ?, ?, ?, ?)
+                "#;
+            let result = sqlx::query(query)
+                .bind(self.chain_id)
+                .bind(&self.job)
+                .bind(block_number as i64)
+                .bind(&contract_address)
+                .bind(&holder_address)
+                .bind(balance.into_raw().to_be_bytes::<32>().to_vec())
+                .execute(&mut *tx)
+                .await?;
+            rows_affected += result.rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(rows_affected)
+    }
+
+    // ---------------------------
+    // HISTORICAL BALANCES
+    // ---------------------------
+
+    /// Balance of a holder at the given block, folded from the transfers of the job
+    /// following its nearest balance snapshot.
+    /// Fails above the last checkpoint of the job, its transfers being possibly incomplete.
+    pub async fn get_balance_at_block_number(
+        &self,
+        contract_address: &[u8],
+        holder_address: &[u8],
+        block_number: BlockNumber,
+    ) -> Result<Balance> {
+        self.ensure_checkpointed(block_number).await?;
+        let snapshot_block_number = self.nearest_snapshot(contract_address, block_number).await?;
+
+        let query = r#"
+            SELECT balance
+            FROM balance_snapshots
+            WHERE chain_id = ? AND job = ? AND contract_address = ? AND holder_address = ?
+                AND block_number <= ?
+            ORDER BY block_number DESC
+            LIMIT 1
+            "#;
+        let balance: Option<Vec<u8>> = sqlx::query_scalar(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(contract_address)
+            .bind(holder_address)
+            .bind(block_number as i64)
+            .fetch_optional(self.client.pool())
+            .await?;
+
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, from_address, to_address, amount
+            FROM transfers
+            WHERE chain_id = ? AND job = ? AND contract_address = ?
+                AND block_number > ? AND block_number <= ?
+                AND (from_address = ? OR to_address = ?)
+            "#;
+        let transfers: Vec<Transfer> = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(contract_address)
+            .bind(snapshot_block_number.unwrap_or(-1))
+            .bind(block_number as i64)
+            .bind(holder_address)
+            .bind(holder_address)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        let key = (contract_address.to_vec(), holder_address.to_vec());
+        let delta = deltas(&transfers, false).remove(&key).unwrap_or_default();
+        let balance = to_i256(balance.as_deref()).wrapping_add(delta);
+
+        Ok(Balance {
+            contract_address: contract_address.to_vec(),
+            holder_address: holder_address.to_vec(),
+            balance: balance.into_raw().to_be_bytes::<32>().to_vec(),
+        })
+    }
+
+    /// Top holders (by descending balance) at the given block, folded from the transfers
+    /// of the job following its nearest balance snapshot.
+    /// Fails above the last checkpoint of the job, its transfers being possibly incomplete.
+    pub async fn get_top_holders_at_block_number(
+        &self,
+        contract_address: &[u8],
+        block_number: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<Balance>> {
+        self.ensure_checkpointed(block_number).await?;
+        let snapshot_block_number = self.nearest_snapshot(contract_address, block_number).await?;
+
+        // Latest snapshotted balance of every holder
+        let query = r#"
+            SELECT contract_address, holder_address, balance
+            FROM (
+                SELECT *, ROW_NUMBER() OVER (
+                    PARTITION BY holder_address
+                    ORDER BY block_number DESC
+                ) AS row_number
+                FROM balance_snapshots
+                WHERE chain_id = ? AND job = ? AND contract_address = ? AND block_number <= ?
+            )
+            WHERE row_number = 1
+            "#;
+        let snapshot: Vec<Balance> = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(contract_address)
+            .bind(block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, from_address, to_address, amount
+            FROM transfers
+            WHERE chain_id = ? AND job = ? AND contract_address = ?
+                AND block_number > ? AND block_number <= ?
+            "#;
+        let transfers: Vec<Transfer> = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(contract_address)
+            .bind(snapshot_block_number.unwrap_or(-1))
+            .bind(block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        let mut balances = deltas(&transfers, false);
+        for Balance { contract_address, holder_address, balance } in snapshot {
+            let delta = balances.entry((contract_address, holder_address)).or_default();
+            *delta = delta.wrapping_add(to_i256(Some(&balance)));
+        }
+        let mut balances: Vec<_> =
+            balances.into_iter().filter(|(_, balance)| balance.is_positive()).collect();
+        balances.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Ok(balances
+            .into_iter()
+            .take(limit)
+            .map(|((contract_address, holder_address), balance)| Balance {
+                contract_address,
+                holder_address,
+                balance: balance.into_raw().to_be_bytes::<32>().to_vec(),
+            })
+            .collect())
+    }
+
+    // Transfers of the job are only complete up to its last checkpoint
+    async fn ensure_checkpointed(&self, block_number: BlockNumber) -> Result<()> {
+        let query = r#"
+            SELECT MAX(block_number)
+            FROM checkpoints
+            WHERE chain_id = ? AND job = ?
+            "#;
+        let last_checkpoint: Option<i64> = sqlx::query_scalar(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .fetch_one(self.client.pool())
+            .await?;
+
+        match last_checkpoint {
+            Some(last_checkpoint) if block_number as i64 <= last_checkpoint => Ok(()),
+            _ => Err(eyre!(
+                "Block number {block_number} is above the last checkpoint of job {}: {last_checkpoint:?}",
+                self.job
+            )),
+        }
+    }
+
+    // Block number of the latest balance snapshot of the token at or below the given block
+    async fn nearest_snapshot(
+        &self,
+        contract_address: &[u8],
+        block_number: BlockNumber,
+    ) -> Result<Option<i64>, Error> {
+        let query = r#"
+            SELECT MAX(block_number)
+            FROM balance_snapshots
+            WHERE chain_id = ? AND job = ? AND contract_address = ? AND block_number <= ?
+            "#;
+        sqlx::query_scalar(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(contract_address)
+            .bind(block_number as i64)
+            .fetch_one(self.client.pool())
+            .await
+    }
+}
+
+// Sums up transfers per holder: credits `to`, debits `from` (reversed when reverting).
+// The zero address is the source of mints and the destination of burns, it holds no balance.
+fn deltas(transfers: &[Transfer], revert: bool) -> Deltas {
+    let mut deltas = Deltas::new();
+    for transfer in transfers {
+        // NOTE: Malformed amounts (longer than a word) do not move balances
+        let amount = U256::try_from_be_slice(&transfer.amount).unwrap_or_default();
+        let amount = I256::from_raw(amount);
+        let amount = if revert { amount.wrapping_neg() } else { amount };
+        if !utils::is_zero_address(&transfer.from_address) {
+            let key = (transfer.contract_address.clone(), transfer.from_address.clone());
+            let delta = deltas.entry(key).or_default();
+            *delta = delta.wrapping_sub(amount);
+        }
+        if !utils::is_zero_address(&transfer.to_address) {
+            let key = (transfer.contract_address.clone(), transfer.to_address.clone());
+            let delta = deltas.entry(key).or_default();
+            *delta = delta.wrapping_add(amount);
+        }
+    }
+    deltas
+}
+
+async fn apply_deltas(
+    tx: &mut Transaction<'_, Sqlite>,
+    chain_id: i64,
+    job: &str,
+    deltas: Deltas,
+) -> Result<(), Error> {
+    for ((contract_address, holder_address), delta) in deltas {
+        let query = r#"
+            SELECT balance
+            FROM balances
+            WHERE chain_id = ? AND job = ? AND contract_address = ? AND holder_address = ?
+            "#;
+        let balance: Option<Vec<u8>> = sqlx::query_scalar(query)
+            .bind(chain_id)
+            .bind(job)
+            .bind(&contract_address)
+            .bind(&holder_address)
+            .fetch_optional(&mut **tx)
+            .await?;
+        let balance = to_i256(balance.as_deref()).wrapping_add(delta);
+
+        let query = r#"
+            INSERT INTO balances (chain_id, job, contract_address, holder_address, balance)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (chain_id, job, contract_address, holder_address) DO UPDATE SET
+                balance = excluded.balance
+            "#;
+        sqlx::query(query)
+            .bind(chain_id)
+            .bind(job)
+            .bind(&contract_address)
+            .bind(&holder_address)
+            .bind(balance.into_raw().to_be_bytes::<32>().to_vec())
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    Ok(())
+}
+
+// Stored balance as a signed integer, missing ones being zero
+fn to_i256(balance: Option<&[u8]>) -> I256 {
+    balance.and_then(U256::try_from_be_slice).map(I256::from_raw).unwrap_or_default()
+}
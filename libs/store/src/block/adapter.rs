@@ -0,0 +1,13 @@
+use crate::block::model::BlockHeader;
+use alloy::rpc::types::Block;
+
+impl From<&Block> for BlockHeader {
+    fn from(block: &Block) -> Self {
+        BlockHeader {
+            block_number: block.number() as i64,
+            block_hash: block.hash().to_vec(),
+            parent_hash: block.header.parent_hash.to_vec(),
+            timestamp: block.header.timestamp as i64,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use sqlx::FromRow;
+
+// Header of a block containing indexed logs
+#[derive(Clone, FromRow, PartialEq)]
+pub struct BlockHeader {
+    pub block_number: i64,
+    pub block_hash: Vec<u8>,
+    // Empty when recorded from the timestamp of the block logs, without requesting the block
+    pub parent_hash: Vec<u8>,
+    pub timestamp: i64,
+}
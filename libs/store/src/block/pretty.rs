@@ -0,0 +1,14 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{block::model::BlockHeader, utils};
+
+impl Debug for BlockHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("BlockHeader")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("parent_hash", &utils::bytes_to_hex(&self.parent_hash))
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::block::model::BlockHeader;
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use sqlx::Error;
+
+#[derive(Clone)]
+// Block headers recorded by a single job on a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // BLOCKS
+    // ---------------------------
+
+    pub async fn insert_block(&self, block: &BlockHeader) -> Result<(), Error> {
+        let query = r#"
+            INSERT OR IGNORE INTO blocks (
+                chain_id, job, block_number, block_hash, parent_hash, timestamp
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            "#;
+        sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block.block_number)
+            .bind(&block.block_hash)
+            .bind(&block.parent_hash)
+            .bind(block.timestamp)
+            .execute(self.client.pool())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_block_by_number(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockHeader>, Error> {
+        let query = r#"
+            SELECT block_number, block_hash, parent_hash, timestamp
+            FROM blocks
+            WHERE chain_id = ? AND job = ? AND block_number = ?
+            "#;
+        let block = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block_number as i64)
+            .fetch_optional(self.client.pool())
+            .await?;
+
+        Ok(block)
+    }
+
+    pub async fn delete_blocks_after_block_number(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<u64, Error> {
+        let query = r#"
+            DELETE FROM blocks
+            WHERE chain_id = ? AND job = ? AND block_number > ?
+            "#;
+        let result = sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block_number as i64)
+            .execute(self.client.pool())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
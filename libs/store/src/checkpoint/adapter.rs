@@ -9,6 +9,7 @@ impl From<&Block> for Checkpoint {
             block_number: block_number as i64,
             block_hash: block_hash.to_vec(),
             parent_hash: block.header.parent_hash.to_vec(),
+            chain_id: None,
         }
     }
 }
@@ -5,4 +5,7 @@ pub struct Checkpoint {
     pub block_number: i64,
     pub block_hash: Vec<u8>,
     pub parent_hash: Vec<u8>,
+    // Chain of the checkpointed block, set by the store it is persisted with
+    #[sqlx(default)]
+    pub chain_id: Option<i64>,
 }
@@ -8,6 +8,7 @@ impl Debug for Checkpoint {
             .field("block_number", &self.block_number)
             .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
             .field("parent_hash", &utils::bytes_to_hex(&self.parent_hash))
+            .field("chain_id", &self.chain_id)
             .finish()
     }
 }
@@ -1,17 +1,28 @@
 use crate::checkpoint::model::Checkpoint;
-use crate::client::Client;
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
 use alloy::primitives::{BlockHash, BlockNumber};
 use eyre::Result;
 use sqlx::Error;
 
+// Checkpoints of a single indexing job on a single chain, each job having its own cursor
 #[derive(Clone)]
 pub struct Store {
     client: Client,
+    job: String,
+    chain_id: i64,
 }
 
 impl Store {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self { client, job: DEFAULT_JOB.to_string(), chain_id: DEFAULT_CHAIN_ID as i64 }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
     }
 
     // ---------------------------
@@ -20,10 +31,12 @@ impl Store {
 
     pub async fn insert_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), Error> {
         let query = r#"
-            INSERT OR IGNORE INTO checkpoints (block_number, block_hash, parent_hash)
-            VALUES (?, ?, ?)
+            INSERT OR IGNORE INTO checkpoints (chain_id, job, block_number, block_hash, parent_hash)
+            VALUES (?, ?, ?, ?, ?)
             "#;
         sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
             .bind(checkpoint.block_number)
             .bind(&checkpoint.block_hash)
             .bind(&checkpoint.parent_hash)
@@ -34,27 +47,52 @@ impl Store {
 
     pub async fn get_last_checkpoint(&self) -> Result<Option<Checkpoint>, Error> {
         let query = r#"
-            SELECT block_number, block_hash, parent_hash
+            SELECT block_number, block_hash, parent_hash, chain_id
             FROM checkpoints
+            WHERE chain_id = ? AND job = ?
             ORDER BY id DESC
             LIMIT 1
             "#;
-        let checkpoint = sqlx::query_as(query).fetch_optional(self.client.pool()).await?;
+        let checkpoint = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .fetch_optional(self.client.pool())
+            .await?;
 
         Ok(checkpoint)
     }
 
+    pub async fn get_last_checkpoints(&self, limit: u64) -> Result<Vec<Checkpoint>, Error> {
+        let query = r#"
+            SELECT block_number, block_hash, parent_hash, chain_id
+            FROM checkpoints
+            WHERE chain_id = ? AND job = ?
+            ORDER BY block_number DESC
+            LIMIT ?
+            "#;
+        let checkpoints = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(limit as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(checkpoints)
+    }
+
     pub async fn get_checkpoint_by_number(
         &self,
         block_number: BlockNumber,
     ) -> Result<Option<Checkpoint>, Error> {
         let query = r#"
-            SELECT block_number, block_hash, parent_hash
+            SELECT block_number, block_hash, parent_hash, chain_id
             FROM checkpoints
-            WHERE block_number = ?
+            WHERE chain_id = ? AND job = ? AND block_number = ?
             LIMIT 1
             "#;
         let checkpoint = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
             .bind(block_number as i64)
             .fetch_optional(self.client.pool())
             .await?;
@@ -67,14 +105,36 @@ impl Store {
         block_hash: BlockHash,
     ) -> Result<Option<Checkpoint>, Error> {
         let query = r#"
-            SELECT block_number, block_hash, parent_hash
+            SELECT block_number, block_hash, parent_hash, chain_id
             FROM checkpoints
-            WHERE block_hash = ?
+            WHERE chain_id = ? AND job = ? AND block_hash = ?
             LIMIT 1
             "#;
-        let checkpoint =
-            sqlx::query_as(query).bind(&block_hash[..]).fetch_optional(self.client.pool()).await?;
+        let checkpoint = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(&block_hash[..])
+            .fetch_optional(self.client.pool())
+            .await?;
 
         Ok(checkpoint)
     }
+
+    pub async fn delete_checkpoints_after_block_number(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<u64, Error> {
+        let query = r#"
+            DELETE FROM checkpoints
+            WHERE chain_id = ? AND job = ? AND block_number > ?
+            "#;
+        let result = sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block_number as i64)
+            .execute(self.client.pool())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
 }
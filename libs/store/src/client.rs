@@ -1,6 +1,13 @@
+use crate::migration;
 use eyre::Result;
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
 
+// Chain of the rows stored before chain ids were recorded (Ethereum mainnet)
+pub const DEFAULT_CHAIN_ID: u64 = 1;
+
+// Job of the rows stored before jobs were recorded, and of the stores given none
+pub const DEFAULT_JOB: &str = "default";
+
 #[derive(Clone)]
 pub struct Client {
     pool: SqlitePool,
@@ -17,7 +24,7 @@ impl Client {
         let client = Client::new(database_url).await?;
 
         // Run migrations or create tables on startup
-        sqlx::query(include_str!("../resources/create_tables.sql")).execute(client.pool()).await?;
+        migration::migrate(client.pool()).await?;
 
         Ok(client)
     }
@@ -0,0 +1,11 @@
+use sqlx::FromRow;
+
+// Contract discovered from a factory creation event
+#[derive(Clone, FromRow, PartialEq)]
+pub struct Contract {
+    pub contract_address: Vec<u8>,
+    pub factory_address: Vec<u8>,
+    pub block_number: i64,
+    pub transaction_hash: Vec<u8>,
+    pub log_index: i64,
+}
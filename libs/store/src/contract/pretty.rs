@@ -0,0 +1,15 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{contract::model::Contract, utils};
+
+impl Debug for Contract {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Contract")
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address))
+            .field("factory_address", &utils::bytes_to_address(&self.factory_address))
+            .field("block_number", &self.block_number)
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("log_index", &self.log_index)
+            .finish()
+    }
+}
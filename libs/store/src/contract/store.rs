@@ -0,0 +1,100 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID};
+use crate::contract::model::Contract;
+use eyre::Result;
+use sqlx::Error;
+
+// Discovered contracts of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64 }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    // ---------------------------
+    // DISCOVERED CONTRACTS
+    // ---------------------------
+
+    /// Inserts multiple contracts in batches, respecting SQLite's max variable limit.
+    /// Contracts already discovered are ignored.
+    pub async fn insert_contracts_batch(&self, contracts: &[Contract]) -> Result<(), Error> {
+        if contracts.is_empty() {
+            return Ok(());
+        }
+
+        // SQLite variable limit = 999 by default
+        const COLS: usize = 6;
+        const SQLITE_MAX_VARIABLES: usize = 999;
+        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        for batch in contracts.chunks(max_rows_per_batch) {
+            let values_placeholders =
+                (0..batch.len()).map(|_| "(?, ?, ?, ?, ?, ?)").collect::<Vec<_>>().join(", ");
+
+            // SQLite skips rows that violate the constraint, keeps the rest.
+            let mut query = String::from(
+                "INSERT OR IGNORE INTO contracts (
+                    chain_id, contract_address, factory_address,
+                    block_number, transaction_hash, log_index
+                ) VALUES ",
+            );
+            query.push_str(&values_placeholders);
+
+            let mut q = sqlx::query(&query);
+            for contract in batch {
+                q = q
+                    .bind(self.chain_id)
+                    .bind(&contract.contract_address)
+                    .bind(&contract.factory_address)
+                    .bind(contract.block_number)
+                    .bind(&contract.transaction_hash)
+                    .bind(contract.log_index);
+            }
+            q.execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_contracts(&self) -> Result<Vec<Contract>, Error> {
+        let query = r#"
+            SELECT contract_address, factory_address, block_number, transaction_hash, log_index
+            FROM contracts
+            WHERE chain_id = ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let contracts =
+            sqlx::query_as(query).bind(self.chain_id).fetch_all(self.client.pool()).await?;
+
+        Ok(contracts)
+    }
+
+    pub async fn get_contracts_by_factory(
+        &self,
+        factory_address: &[u8],
+    ) -> Result<Vec<Contract>, Error> {
+        let query = r#"
+            SELECT contract_address, factory_address, block_number, transaction_hash, log_index
+            FROM contracts
+            WHERE chain_id = ? AND factory_address = ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let contracts = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(factory_address)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(contracts)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::event_log::model::{AbiLog, EventLog};
+use alloy::dyn_abi::EventExt;
+use eyre::{Result, eyre};
+use std::convert::TryFrom;
+
+impl TryFrom<AbiLog> for EventLog {
+    type Error = eyre::Report;
+
+    fn try_from(abi_log: AbiLog) -> Result<Self> {
+        let AbiLog { event, log } = abi_log;
+        let decoded = event.decode_log(log.data())?;
+
+        // Merge back indexed (topics) and non-indexed (data) values into the ABI inputs order
+        let mut indexed = decoded.indexed.into_iter();
+        let mut body = decoded.body.into_iter();
+        let values = event
+            .inputs
+            .iter()
+            .map(|input| if input.indexed { indexed.next() } else { body.next() })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| eyre!("missing decoded values"))?;
+
+        Ok(Self {
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+            contract_address: log.address().to_vec(),
+            event,
+            values,
+        })
+    }
+}
@@ -0,0 +1,21 @@
+use alloy::{dyn_abi::DynSolValue, json_abi::Event, rpc::types::Log};
+use std::sync::Arc;
+
+// Raw log paired with the ABI event used to decode it
+#[derive(Debug, Clone)]
+pub struct AbiLog {
+    pub event: Arc<Event>,
+    pub log: Log,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct EventLog {
+    pub block_number: i64,
+    pub block_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub log_index: i64,
+    pub contract_address: Vec<u8>,
+    pub event: Arc<Event>,
+    // Decoded event parameters, following the ABI inputs order
+    pub values: Vec<DynSolValue>,
+}
@@ -0,0 +1,18 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{event_log::model::EventLog, utils};
+
+impl Debug for EventLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let values: Vec<String> = self.values.iter().map(utils::sol_value_to_string).collect();
+        f.debug_struct("EventLog")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("log_index", &self.log_index)
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("event", &self.event.signature())
+            .field("values", &values)
+            .finish()
+    }
+}
@@ -0,0 +1,332 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::event_log::model::EventLog;
+use crate::migration;
+use crate::utils::contract_filter;
+use alloy::{
+    dyn_abi::DynSolValue,
+    json_abi::{Event, EventParam},
+    primitives::BlockNumber,
+};
+use eyre::{Result, bail};
+use sqlx::{
+    Error, Sqlite,
+    query::Query,
+    sqlite::{SqliteArguments, SqliteQueryResult},
+};
+use std::sync::Arc;
+
+// Columns shared by every event table, before the event parameters
+const BASE_COLUMNS: [&str; 5] =
+    ["block_number", "block_hash", "transaction_hash", "log_index", "contract_address"];
+
+// Columns added to event tables created by earlier versions: (column, definition)
+const ADDED_COLUMNS: [(&str, &str); 2] =
+    [("chain_id", "INTEGER NOT NULL DEFAULT 1"), ("job", "TEXT NOT NULL DEFAULT 'default'")];
+
+// Prefix of the comment recording the event signature in the `CREATE TABLE` statement
+const EVENT_COMMENT: &str = "-- Event: ";
+
+// Per event tables are created on init, from the given ABI events.
+// Event logs are those of a single chain. Tables are named after their event: events sharing
+// a name but not a signature are rejected, rather than mixed up in the same table.
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+    events: Vec<Arc<Event>>,
+}
+
+impl Store {
+    pub async fn init(client: Client, events: Vec<Arc<Event>>) -> Result<Self> {
+        let store = Self {
+            client,
+            chain_id: DEFAULT_CHAIN_ID as i64,
+            job: DEFAULT_JOB.to_string(),
+            events,
+        };
+        let pool = store.client.pool();
+        for event in &store.events {
+            if let Some(other) = store.events.iter().find(|other| {
+                table_name(other) == table_name(event)
+                    && other.full_signature() != event.full_signature()
+            }) {
+                bail!(
+                    "Events `{}` and `{}` would share the table {}",
+                    event.full_signature(),
+                    other.full_signature(),
+                    table_name(event)
+                );
+            }
+            check_table_event(pool, event).await?;
+
+            // Tables created before chain ids and jobs were recorded are brought up to date
+            let create_table = create_table_query(event);
+            sqlx::raw_sql(&create_table).execute(pool).await?;
+            for (column, definition) in ADDED_COLUMNS {
+                migration::add_column(pool, &table_name(event), column, definition).await?;
+            }
+            migration::rebuild_outdated_table(pool, &create_table).await?;
+
+            let create_index = create_index_query(event);
+            migration::drop_outdated_index(pool, &create_index).await?;
+            sqlx::raw_sql(&create_index).execute(pool).await?;
+        }
+        Ok(store)
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // EVENT LOGS
+    // ---------------------------
+
+    pub async fn insert_event_log(&self, event_log: &EventLog) -> Result<(), Error> {
+        self.insert_event_logs_batch(std::slice::from_ref(event_log)).await
+    }
+
+    /// Inserts multiple event logs in batches (one per event table),
+    /// respecting SQLite's max variable limit.
+    pub async fn insert_event_logs_batch(&self, event_logs: &[EventLog]) -> Result<(), Error> {
+        if event_logs.is_empty() {
+            return Ok(());
+        }
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        for event in &self.events {
+            let event_batch: Vec<&EventLog> =
+                event_logs.iter().filter(|event_log| event_log.event == *event).collect();
+            if event_batch.is_empty() {
+                continue;
+            }
+
+            // SQLite variable limit = 999 by default
+            let cols = 2 + BASE_COLUMNS.len() + event.inputs.len();
+            const SQLITE_MAX_VARIABLES: usize = 999;
+            let max_rows_per_batch = (SQLITE_MAX_VARIABLES / cols).max(1);
+
+            for batch in event_batch.chunks(max_rows_per_batch) {
+                let row_placeholders = format!("({})", vec!["?"; cols].join(", "));
+                let values_placeholders = vec![row_placeholders; batch.len()].join(", ");
+
+                // SQLite skips rows that violate the constraint, keeps the rest.
+                let query = format!(
+                    "INSERT OR IGNORE INTO {} (chain_id, job, {}) VALUES {}",
+                    quote(&table_name(event)),
+                    column_names(event).iter().map(|c| quote(c)).collect::<Vec<_>>().join(", "),
+                    values_placeholders
+                );
+
+                let mut q = sqlx::query(&query);
+                for event_log in batch {
+                    q = q
+                        .bind(self.chain_id)
+                        .bind(&self.job)
+                        .bind(event_log.block_number)
+                        .bind(&event_log.block_hash)
+                        .bind(&event_log.transaction_hash)
+                        .bind(event_log.log_index)
+                        .bind(&event_log.contract_address);
+                    for value in &event_log.values {
+                        q = bind_value(q, value);
+                    }
+                }
+                q.execute(&mut *tx).await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_event_logs_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let mut tx = self.client.pool().begin().await?;
+        let mut rows_affected = 0;
+        for event in &self.events {
+            let query = format!(
+                "DELETE FROM {} WHERE chain_id = ? AND job = ? AND block_number > ?{}",
+                quote(&table_name(event)),
+                contract_filter(contract_addresses)
+            );
+            let mut q =
+                sqlx::query(&query).bind(self.chain_id).bind(&self.job).bind(block_number as i64);
+            for contract_address in contract_addresses {
+                q = q.bind(contract_address);
+            }
+            let result: SqliteQueryResult = q.execute(&mut *tx).await?;
+            rows_affected += result.rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(rows_affected)
+    }
+}
+
+// Table name derived from the event name, e.g. `PairCreated` -> `event_pair_created`
+pub fn table_name(event: &Event) -> String {
+    let mut name = String::from("event");
+    let mut previous_lowercase = false;
+    for c in event.name.chars() {
+        if (c.is_ascii_uppercase() && previous_lowercase) || name.len() == 5 {
+            name.push('_');
+        }
+        previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        name.push(if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' });
+    }
+    name
+}
+
+// Table column names: base columns followed by one column per event parameter.
+// Parameters named after a column of every table (e.g. `job`) are prefixed with `param_`.
+pub fn column_names(event: &Event) -> Vec<String> {
+    let params = event.inputs.iter().enumerate().map(|(index, param)| {
+        let name: String = param
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+            .collect();
+        if name.is_empty() {
+            format!("param_{index}")
+        } else if is_reserved(&name) {
+            format!("param_{name}")
+        } else {
+            name
+        }
+    });
+    BASE_COLUMNS.iter().map(|c| c.to_string()).chain(params).collect()
+}
+
+fn is_reserved(column: &str) -> bool {
+    column == "id"
+        || BASE_COLUMNS.contains(&column)
+        || ADDED_COLUMNS.iter().any(|(added, _)| *added == column)
+}
+
+// Fails when the table of the event already holds another event of the same name: its
+// signature is recorded in the table definition, or for tables created before it was,
+// its columns differ
+async fn check_table_event(pool: &sqlx::SqlitePool, event: &Event) -> Result<()> {
+    let table = table_name(event);
+    let existing: Option<String> =
+        sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(&table)
+            .fetch_optional(pool)
+            .await?;
+    let Some(existing) = existing else { return Ok(()) };
+
+    let signature = existing.lines().find_map(|line| line.trim().strip_prefix(EVENT_COMMENT));
+    match signature {
+        Some(signature) if signature != event.full_signature() => {
+            bail!("Table {table} holds `{signature}` logs, not `{}` ones", event.full_signature())
+        }
+        Some(_) => Ok(()),
+        None => {
+            let mut columns: Vec<String> = migration::table_columns(pool, &table)
+                .await?
+                .into_iter()
+                .filter(|column| column != "id")
+                .filter(|column| ADDED_COLUMNS.iter().all(|(added, _)| added != column))
+                .collect();
+            let mut expected = column_names(event);
+            columns.sort();
+            expected.sort();
+            if columns != expected {
+                bail!(
+                    "Table {table} holds other logs than `{}` ones: columns {columns:?}",
+                    event.full_signature()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+fn column_type(param: &EventParam) -> &'static str {
+    // Indexed dynamic types are stored as their topic hash
+    let is_dynamic = param.ty == "string"
+        || param.ty == "bytes"
+        || param.ty.ends_with(']')
+        || param.ty.starts_with('(');
+    match param.ty.as_str() {
+        _ if param.indexed && is_dynamic => "BLOB",
+        "bool" => "INTEGER",
+        "string" => "TEXT",
+        _ => "BLOB",
+    }
+}
+
+fn create_table_query(event: &Event) -> String {
+    let table = table_name(event);
+    let params = column_names(event)
+        .into_iter()
+        .skip(BASE_COLUMNS.len())
+        .zip(&event.inputs)
+        .map(|(name, param)| format!(",\n    {} {} NOT NULL", quote(&name), column_type(param)))
+        .collect::<String>();
+    format!(
+        r#"
+-- Table: {table}
+
+CREATE TABLE IF NOT EXISTS {quoted_table} (
+    {EVENT_COMMENT}{signature}
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Chain id, as reported by the node (`eth_chainId`)
+    chain_id            INTEGER NOT NULL DEFAULT 1,
+    -- Indexing job that stored the row, the only one rolling it back
+    job                 TEXT NOT NULL DEFAULT 'default',
+    block_number        INTEGER NOT NULL,
+    block_hash          BLOB NOT NULL,
+    transaction_hash    BLOB NOT NULL,
+    log_index           INTEGER NOT NULL,
+    contract_address    BLOB NOT NULL{params},
+
+    -- Constraint to ensure no duplicate logs
+    UNIQUE(chain_id, transaction_hash, log_index)
+);
+"#,
+        signature = event.full_signature(),
+        quoted_table = quote(&table),
+    )
+}
+
+fn create_index_query(event: &Event) -> String {
+    let table = table_name(event);
+    format!(
+        r#"
+CREATE INDEX IF NOT EXISTS {index_block_number}
+    ON {quoted_table} (chain_id, block_number);
+"#,
+        quoted_table = quote(&table),
+        index_block_number = quote(&format!("idx_{table}_block_number")),
+    )
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn bind_value<'q>(
+    q: Query<'q, Sqlite, SqliteArguments<'q>>,
+    value: &DynSolValue,
+) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    match value {
+        DynSolValue::Bool(b) => q.bind(*b as i64),
+        DynSolValue::String(s) => q.bind(s.clone()),
+        DynSolValue::Address(address) => q.bind(address.to_vec()),
+        DynSolValue::Int(i, _) => q.bind(i.to_be_bytes::<32>().to_vec()),
+        DynSolValue::Uint(u, _) => q.bind(u.to_be_bytes::<32>().to_vec()),
+        DynSolValue::FixedBytes(word, size) => q.bind(word[..*size].to_vec()),
+        DynSolValue::Bytes(bytes) => q.bind(bytes.clone()),
+        other => q.bind(other.abi_encode()),
+    }
+}
@@ -1,14 +1,88 @@
 pub mod client;
+pub mod migration;
 pub mod utils;
+pub mod approval {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod balance {
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod block {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
 pub mod checkpoint {
     pub mod adapter;
     pub mod model;
     pub mod pretty;
     pub mod store;
 }
+pub mod contract {
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod event_log {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod nft_transfer {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod raw_log {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod supply {
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod token {
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod transaction {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
 pub mod transfer {
     pub mod adapter;
     pub mod model;
     pub mod pretty;
     pub mod store;
 }
+pub mod transfer_batch {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod transfer_single {
+    pub mod adapter;
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
+pub mod watched_address {
+    pub mod model;
+    pub mod pretty;
+    pub mod store;
+}
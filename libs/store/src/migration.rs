@@ -0,0 +1,169 @@
+use eyre::Result;
+use sqlx::{SqliteExecutor, SqlitePool};
+
+const CREATE_TABLES: &str = include_str!("../resources/create_tables.sql");
+const CREATE_INDEXES: &str = include_str!("../resources/create_indexes.sql");
+
+// Columns added to tables created by earlier versions: (table, column, definition).
+// The default of the definition is the value of the rows stored before the column existed.
+const ADDED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("transfers", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("supply_changes", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("balances", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("checkpoints", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("checkpoints", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("blocks", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("nft_transfers", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("transfer_singles", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("transfer_batches", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("approvals", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("allowances", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("tokens", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("contracts", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("transactions", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("raw_logs", "chain_id", "INTEGER NOT NULL DEFAULT 1"),
+    ("transfers", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("supply_changes", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("nft_transfers", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("transfer_singles", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("transfer_batches", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("approvals", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("transactions", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("raw_logs", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("blocks", "job", "TEXT NOT NULL DEFAULT 'default'"),
+    ("balances", "job", "TEXT NOT NULL DEFAULT 'default'"),
+];
+
+// Creates the tables and indexes, bringing the ones of earlier versions up to date:
+// missing columns are added, tables whose unique key changed are rebuilt
+// and indexes whose definition changed are re-created
+pub async fn migrate(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(CREATE_TABLES).execute(pool).await?;
+
+    for (table, column, definition) in ADDED_COLUMNS {
+        add_column(pool, table, column, definition).await?;
+    }
+    for statement in CREATE_TABLES.split(';') {
+        rebuild_outdated_table(pool, statement).await?;
+    }
+    for statement in CREATE_INDEXES.split(';') {
+        drop_outdated_index(pool, statement).await?;
+    }
+    sqlx::query(CREATE_INDEXES).execute(pool).await?;
+
+    Ok(())
+}
+
+pub(crate) async fn add_column(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    if !table_columns(pool, table).await?.iter().any(|existing| existing == column) {
+        sqlx::query(&format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"))
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
+
+// Rebuilds the table of a `CREATE TABLE` statement when its unique key differs
+pub(crate) async fn rebuild_outdated_table(pool: &SqlitePool, statement: &str) -> Result<()> {
+    let Some((table, unique_key)) = parse_table(statement) else { return Ok(()) };
+    if !unique_keys(pool, table).await?.contains(&unique_key) {
+        rebuild_table(pool, table, statement).await?;
+    }
+    Ok(())
+}
+
+// Drops the index of a `CREATE INDEX` statement when its definition differs,
+// for the statement to create it again
+pub(crate) async fn drop_outdated_index(pool: &SqlitePool, statement: &str) -> Result<()> {
+    let Some(index) = parse_name(statement, "CREATE INDEX IF NOT EXISTS ") else { return Ok(()) };
+    let existing: Option<String> =
+        sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'index' AND name = ?")
+            .bind(index)
+            .fetch_optional(pool)
+            .await?;
+    if existing.is_some_and(|existing| normalize(&existing) != normalize(statement)) {
+        sqlx::query(&format!("DROP INDEX {index}")).execute(pool).await?;
+    }
+    Ok(())
+}
+
+// Unique constraints can not be altered in SQLite: the table is re-created
+// and its rows copied over
+async fn rebuild_table(pool: &SqlitePool, table: &str, create_statement: &str) -> Result<()> {
+    // Quoted, as event parameters may be named after keywords (e.g. `from`)
+    let columns = table_columns(pool, table)
+        .await?
+        .iter()
+        .map(|column| format!("\"{column}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!("ALTER TABLE {table} RENAME TO {table}_migrated"))
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(create_statement).execute(&mut *tx).await?;
+    sqlx::query(&format!("INSERT INTO {table} ({columns}) SELECT {columns} FROM {table}_migrated"))
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(&format!("DROP TABLE {table}_migrated")).execute(&mut *tx).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub(crate) async fn table_columns<'e>(
+    executor: impl SqliteExecutor<'e>,
+    table: &str,
+) -> Result<Vec<String>> {
+    Ok(sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+        .bind(table)
+        .fetch_all(executor)
+        .await?)
+}
+
+// Columns of every unique constraint of a table
+async fn unique_keys(pool: &SqlitePool, table: &str) -> Result<Vec<Vec<String>>> {
+    let indexes: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM pragma_index_list(?) WHERE origin = 'u'")
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+    let mut unique_keys = vec![];
+    for index in indexes {
+        let columns = sqlx::query_scalar("SELECT name FROM pragma_index_info(?) ORDER BY seqno")
+            .bind(index)
+            .fetch_all(pool)
+            .await?;
+        unique_keys.push(columns);
+    }
+    Ok(unique_keys)
+}
+
+// Name and unique key columns of a `CREATE TABLE` statement
+fn parse_table(statement: &str) -> Option<(&str, Vec<String>)> {
+    let table = parse_name(statement, "CREATE TABLE IF NOT EXISTS ")?;
+    let (_, unique) = statement.split_once("UNIQUE(")?;
+    let (columns, _) = unique.split_once(')')?;
+    Some((table, columns.split(',').map(|column| column.trim().to_string()).collect()))
+}
+
+fn parse_name<'a>(statement: &'a str, prefix: &str) -> Option<&'a str> {
+    let (_, rest) = statement.split_once(prefix)?;
+    // Names of generated tables and indexes are quoted
+    rest.split_whitespace().next().map(|name| name.trim_matches('"'))
+}
+
+// SQLite stores statements without their `IF NOT EXISTS` clause
+fn normalize(statement: &str) -> String {
+    statement
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("IF NOT EXISTS ", "")
+}
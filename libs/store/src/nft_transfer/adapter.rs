@@ -0,0 +1,42 @@
+use crate::nft_transfer::model::NftTransfer;
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use std::convert::TryFrom;
+
+// ERC-721 `Transfer(address,address,uint256)` indexes all of its parameters:
+// 4 topics (signature, from, to, token id) and empty data.
+const ERC721_TRANSFER_TOPICS: usize = 4;
+
+impl TryFrom<Log> for NftTransfer {
+    type Error = eyre::Report;
+
+    fn try_from(log: Log) -> Result<Self> {
+        let nbr_of_topics = log.topics().len();
+        if nbr_of_topics != ERC721_TRANSFER_TOPICS {
+            return Err(eyre!("not an ERC-721 transfer: {nbr_of_topics:?} topics"));
+        }
+        Ok(Self {
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+            contract_address: log.address().to_vec(),
+            from_address: log
+                .topics()
+                .get(1)
+                .ok_or_else(|| eyre!("missing from"))?
+                .as_slice()
+                .to_vec(),
+            to_address: log.topics().get(2).ok_or_else(|| eyre!("missing to"))?.as_slice().to_vec(),
+            token_id: log
+                .topics()
+                .get(3)
+                .ok_or_else(|| eyre!("missing token_id"))?
+                .as_slice()
+                .to_vec(),
+        })
+    }
+}
@@ -0,0 +1,18 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{nft_transfer::model::NftTransfer, utils};
+
+impl Debug for NftTransfer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("NftTransfer")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("log_index", &self.log_index)
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("from_address", &utils::bytes_to_address(&self.from_address[..]))
+            .field("to_address", &utils::bytes_to_address(&self.to_address[..]))
+            .field("token_id", &utils::bytes_to_u256(&self.token_id))
+            .finish()
+    }
+}
@@ -0,0 +1,58 @@
+use crate::raw_log::model::RawLog;
+use alloy::primitives::{Address, B256, Bytes, LogData};
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use std::convert::TryFrom;
+
+impl TryFrom<&Log> for RawLog {
+    type Error = eyre::Report;
+
+    fn try_from(log: &Log) -> Result<Self> {
+        let topic = |index: usize| log.topics().get(index).map(|topic| topic.to_vec());
+        Ok(Self {
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
+            block_timestamp: log.block_timestamp.map(|timestamp| timestamp as i64),
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            transaction_index: log
+                .transaction_index
+                .map(|transaction_index| transaction_index as i64),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+            contract_address: log.address().to_vec(),
+            topic0: topic(0),
+            topic1: topic(1),
+            topic2: topic(2),
+            topic3: topic(3),
+            data: log.data().data.to_vec(),
+        })
+    }
+}
+
+// Rebuilds the log as served by the node, so that it goes through the same decoders
+impl From<&RawLog> for Log {
+    fn from(raw_log: &RawLog) -> Self {
+        let topics = [&raw_log.topic0, &raw_log.topic1, &raw_log.topic2, &raw_log.topic3]
+            .into_iter()
+            .map_while(|topic| topic.as_deref().map(B256::from_slice))
+            .collect();
+        let data = LogData::new_unchecked(topics, Bytes::copy_from_slice(&raw_log.data));
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::from_slice(&raw_log.contract_address),
+                data,
+            },
+            block_hash: Some(B256::from_slice(&raw_log.block_hash)),
+            block_number: Some(raw_log.block_number as u64),
+            block_timestamp: raw_log.block_timestamp.map(|timestamp| timestamp as u64),
+            transaction_hash: Some(B256::from_slice(&raw_log.transaction_hash)),
+            transaction_index: raw_log
+                .transaction_index
+                .map(|transaction_index| transaction_index as u64),
+            log_index: Some(raw_log.log_index as u64),
+            removed: false,
+        }
+    }
+}
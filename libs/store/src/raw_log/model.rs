@@ -0,0 +1,18 @@
+use sqlx::FromRow;
+
+// Undecoded log, archived so that decoded tables can be re-derived without the node
+#[derive(Clone, FromRow, PartialEq)]
+pub struct RawLog {
+    pub block_number: i64,
+    pub block_hash: Vec<u8>,
+    pub block_timestamp: Option<i64>,
+    pub transaction_hash: Vec<u8>,
+    pub transaction_index: Option<i64>,
+    pub log_index: i64,
+    pub contract_address: Vec<u8>,
+    pub topic0: Option<Vec<u8>>,
+    pub topic1: Option<Vec<u8>>,
+    pub topic2: Option<Vec<u8>>,
+    pub topic3: Option<Vec<u8>>,
+    pub data: Vec<u8>,
+}
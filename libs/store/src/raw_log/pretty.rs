@@ -0,0 +1,24 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{raw_log::model::RawLog, utils};
+
+impl Debug for RawLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let topics: Vec<String> = [&self.topic0, &self.topic1, &self.topic2, &self.topic3]
+            .into_iter()
+            .flatten()
+            .map(|topic| utils::bytes_to_hex(topic))
+            .collect();
+        f.debug_struct("RawLog")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("block_timestamp", &self.block_timestamp)
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("transaction_index", &self.transaction_index)
+            .field("log_index", &self.log_index)
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address))
+            .field("topics", &topics)
+            .field("data", &utils::bytes_to_hex(&self.data))
+            .finish()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::raw_log::model::RawLog;
+use crate::utils::contract_filter;
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use sqlx::Error;
+
+// Raw logs of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // RAW LOGS
+    // ---------------------------
+
+    /// Inserts multiple raw logs in batches, respecting SQLite's max variable limit.
+    pub async fn insert_raw_logs_batch(&self, raw_logs: &[RawLog]) -> Result<(), Error> {
+        if raw_logs.is_empty() {
+            return Ok(());
+        }
+
+        // SQLite variable limit = 999 by default
+        const COLS: usize = 14;
+        const SQLITE_MAX_VARIABLES: usize = 999;
+        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        for batch in raw_logs.chunks(max_rows_per_batch) {
+            let values_placeholders = (0..batch.len())
+                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            // SQLite skips rows that violate the constraint, keeps the rest.
+            let mut query = String::from(
+                "INSERT OR IGNORE INTO raw_logs (
+                    chain_id, job, block_number, block_hash, block_timestamp,
+                    transaction_hash, transaction_index, log_index,
+                    contract_address, topic0, topic1, topic2, topic3, data
+                ) VALUES ",
+            );
+            query.push_str(&values_placeholders);
+
+            let mut q = sqlx::query(&query);
+            for log in batch {
+                q = q
+                    .bind(self.chain_id)
+                    .bind(&self.job)
+                    .bind(log.block_number)
+                    .bind(&log.block_hash)
+                    .bind(log.block_timestamp)
+                    .bind(&log.transaction_hash)
+                    .bind(log.transaction_index)
+                    .bind(log.log_index)
+                    .bind(&log.contract_address)
+                    .bind(&log.topic0)
+                    .bind(&log.topic1)
+                    .bind(&log.topic2)
+                    .bind(&log.topic3)
+                    .bind(&log.data);
+            }
+            q.execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Raw logs between the given block numbers (inclusive),
+    /// emitted by any of the given contracts with any of the given first topics.
+    /// An empty filter matches everything.
+    pub async fn get_raw_logs_between_block_numbers(
+        &self,
+        contract_addresses: &[Vec<u8>],
+        topic0s: &[Vec<u8>],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<RawLog>, Error> {
+        let mut query = String::from(
+            "SELECT
+                block_number, block_hash, block_timestamp,
+                transaction_hash, transaction_index, log_index,
+                contract_address, topic0, topic1, topic2, topic3, data
+            FROM raw_logs
+            WHERE chain_id = ? AND block_number BETWEEN ? AND ?",
+        );
+        query.push_str(&contract_filter(contract_addresses));
+        if !topic0s.is_empty() {
+            let placeholders = vec!["?"; topic0s.len()].join(", ");
+            query.push_str(&format!(" AND topic0 IN ({placeholders})"));
+        }
+        query.push_str(" ORDER BY block_number ASC, log_index ASC");
+
+        let mut q = sqlx::query_as(&query)
+            .bind(self.chain_id)
+            .bind(from_block as i64)
+            .bind(to_block as i64);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        for topic0 in topic0s {
+            q = q.bind(topic0);
+        }
+        let raw_logs = q.fetch_all(self.client.pool()).await?;
+
+        Ok(raw_logs)
+    }
+
+    pub async fn get_last_raw_log_block_number(
+        &self,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<Option<BlockNumber>, Error> {
+        let query = format!(
+            "SELECT MAX(block_number) FROM raw_logs WHERE chain_id = ?{}",
+            contract_filter(contract_addresses)
+        );
+        let mut q = sqlx::query_scalar(&query).bind(self.chain_id);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        let block_number: Option<i64> = q.fetch_one(self.client.pool()).await?;
+
+        Ok(block_number.map(|block_number| block_number as u64))
+    }
+
+    pub async fn delete_raw_logs_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let query = format!(
+            "DELETE FROM raw_logs WHERE chain_id = ? AND job = ? AND block_number > ?{}",
+            contract_filter(contract_addresses)
+        );
+        let mut q =
+            sqlx::query(&query).bind(self.chain_id).bind(&self.job).bind(block_number as i64);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        let result = q.execute(self.client.pool()).await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,14 @@
+use sqlx::FromRow;
+
+// Change of a token total supply, from a mint (positive) or a burn (negative)
+#[derive(Clone, FromRow, PartialEq, PartialOrd)]
+pub struct SupplyChange {
+    pub block_number: i64,
+    pub transaction_hash: Vec<u8>,
+    pub log_index: i64,
+    pub contract_address: Vec<u8>,
+    pub amount: Vec<u8>,
+    // Running total supply after this change, computed on query
+    #[sqlx(default)]
+    pub total_supply: Vec<u8>,
+}
@@ -0,0 +1,16 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{supply::model::SupplyChange, utils};
+
+impl Debug for SupplyChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("SupplyChange")
+            .field("block_number", &self.block_number)
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("log_index", &self.log_index)
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("amount", &utils::bytes_to_i256(&self.amount))
+            .field("total_supply", &utils::bytes_to_i256(&self.total_supply))
+            .finish()
+    }
+}
@@ -0,0 +1,140 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID};
+use crate::supply::model::SupplyChange;
+use crate::transfer::model::Transfer;
+use crate::utils;
+use alloy::primitives::{BlockNumber, I256, U256};
+use eyre::Result;
+use sqlx::{Error, Sqlite, Transaction};
+
+// Supply changes are written by the transfer store, within its insert transaction
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64 }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    // ---------------------------
+    // SUPPLY CHANGES
+    // ---------------------------
+
+    /// Supply changes of a token between the given block numbers,
+    /// along with the running total supply (since the first indexed change).
+    pub async fn get_supply_changes_between_block_numbers(
+        &self,
+        contract_address: &[u8],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<SupplyChange>, Error> {
+        let query = r#"
+            SELECT block_number, transaction_hash, log_index, contract_address, amount
+            FROM supply_changes
+            WHERE chain_id = ? AND contract_address = ? AND block_number <= ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let supply_changes: Vec<SupplyChange> = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(contract_address)
+            .bind(to_block as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        let mut total_supply = I256::ZERO;
+        Ok(supply_changes
+            .into_iter()
+            .map(|mut supply_change| {
+                let amount = U256::try_from_be_slice(&supply_change.amount).unwrap_or_default();
+                total_supply = total_supply.wrapping_add(I256::from_raw(amount));
+                supply_change.total_supply = total_supply.into_raw().to_be_bytes::<32>().to_vec();
+                supply_change
+            })
+            .filter(|supply_change| supply_change.block_number >= from_block as i64)
+            .collect())
+    }
+}
+
+// Records the supply changes of the given (newly inserted) transfers:
+// mints come from the zero address, burns go to the zero address.
+pub(crate) async fn insert_supply_changes(
+    tx: &mut Transaction<'_, Sqlite>,
+    chain_id: i64,
+    job: &str,
+    transfers: &[Transfer],
+) -> Result<(), Error> {
+    let supply_changes: Vec<(&Transfer, I256)> = transfers
+        .iter()
+        .filter_map(|transfer| {
+            // NOTE: Malformed amounts (longer than a word) do not change the supply
+            let amount = U256::try_from_be_slice(&transfer.amount).unwrap_or_default();
+            let amount = I256::from_raw(amount);
+            match (
+                utils::is_zero_address(&transfer.from_address),
+                utils::is_zero_address(&transfer.to_address),
+            ) {
+                (true, false) => Some((transfer, amount)),
+                (false, true) => Some((transfer, amount.wrapping_neg())),
+                _ => None,
+            }
+        })
+        .collect();
+
+    // SQLite variable limit = 999 by default
+    const COLS: usize = 7;
+    const SQLITE_MAX_VARIABLES: usize = 999;
+    let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+    for batch in supply_changes.chunks(max_rows_per_batch) {
+        let values_placeholders =
+            (0..batch.len()).map(|_| "(?, ?, ?, ?, ?, ?, ?)").collect::<Vec<_>>().join(", ");
+
+        // SQLite skips rows that violate the constraint, keeps the rest.
+        let mut query = String::from(
+            "INSERT OR IGNORE INTO supply_changes (
+                chain_id, job, block_number, transaction_hash, log_index, contract_address, amount
+            ) VALUES ",
+        );
+        query.push_str(&values_placeholders);
+
+        let mut q = sqlx::query(&query);
+        for (transfer, amount) in batch {
+            q = q
+                .bind(chain_id)
+                .bind(job)
+                .bind(transfer.block_number)
+                .bind(&transfer.transaction_hash)
+                .bind(transfer.log_index)
+                .bind(&transfer.contract_address)
+                .bind(amount.into_raw().to_be_bytes::<32>().to_vec());
+        }
+        q.execute(&mut **tx).await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn delete_supply_changes_after(
+    tx: &mut Transaction<'_, Sqlite>,
+    chain_id: i64,
+    job: &str,
+    block_number: BlockNumber,
+    contract_addresses: &[Vec<u8>],
+) -> Result<u64, Error> {
+    let query = format!(
+        "DELETE FROM supply_changes WHERE chain_id = ? AND job = ? AND block_number > ?{}",
+        utils::contract_filter(contract_addresses)
+    );
+    let mut q = sqlx::query(&query).bind(chain_id).bind(job).bind(block_number as i64);
+    for contract_address in contract_addresses {
+        q = q.bind(contract_address);
+    }
+    let result = q.execute(&mut **tx).await?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,10 @@
+use sqlx::FromRow;
+
+// ERC-20 metadata of an indexed contract, fetched once when first seen
+#[derive(Clone, FromRow, PartialEq, PartialOrd)]
+pub struct Token {
+    pub contract_address: Vec<u8>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<i64>,
+}
@@ -0,0 +1,14 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{token::model::Token, utils};
+
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Token")
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("name", &self.name)
+            .field("symbol", &self.symbol)
+            .field("decimals", &self.decimals)
+            .finish()
+    }
+}
@@ -0,0 +1,69 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID};
+use crate::token::model::Token;
+use eyre::Result;
+use sqlx::Error;
+
+// Token metadata of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64 }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    // ---------------------------
+    // TOKENS
+    // ---------------------------
+
+    pub async fn insert_token(&self, token: &Token) -> Result<(), Error> {
+        let query = r#"
+            INSERT OR IGNORE INTO tokens (chain_id, contract_address, name, symbol, decimals)
+            VALUES (?, ?, ?, ?, ?)
+            "#;
+
+        sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&token.contract_address)
+            .bind(&token.name)
+            .bind(&token.symbol)
+            .bind(token.decimals)
+            .execute(self.client.pool())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_token(&self, contract_address: &[u8]) -> Result<Option<Token>, Error> {
+        let query = r#"
+            SELECT contract_address, name, symbol, decimals
+            FROM tokens
+            WHERE chain_id = ? AND contract_address = ?
+            "#;
+        let token = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(contract_address)
+            .fetch_optional(self.client.pool())
+            .await?;
+
+        Ok(token)
+    }
+
+    pub async fn get_tokens(&self) -> Result<Vec<Token>, Error> {
+        let query = r#"
+            SELECT contract_address, name, symbol, decimals
+            FROM tokens
+            WHERE chain_id = ?
+            ORDER BY contract_address ASC
+            "#;
+        let tokens =
+            sqlx::query_as(query).bind(self.chain_id).fetch_all(self.client.pool()).await?;
+
+        Ok(tokens)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::transaction::model::Transaction;
+use alloy::{primitives::U256, rpc::types::TransactionReceipt};
+
+impl From<&TransactionReceipt> for Transaction {
+    fn from(receipt: &TransactionReceipt) -> Self {
+        Transaction {
+            block_number: receipt.block_number.unwrap_or_default() as i64,
+            block_hash: receipt.block_hash.unwrap_or_default().to_vec(),
+            transaction_hash: receipt.transaction_hash.to_vec(),
+            tx_from: receipt.from.to_vec(),
+            tx_to: receipt.to.map(|to| to.to_vec()),
+            gas_used: receipt.gas_used as i64,
+            effective_gas_price: U256::from(receipt.effective_gas_price)
+                .to_be_bytes::<32>()
+                .to_vec(),
+            status: receipt.status(),
+        }
+    }
+}
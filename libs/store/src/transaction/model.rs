@@ -0,0 +1,15 @@
+use sqlx::FromRow;
+
+// Receipt of a transaction emitting indexed logs, joined to them by `transaction_hash`
+#[derive(Clone, FromRow, PartialEq)]
+pub struct Transaction {
+    pub block_number: i64,
+    pub block_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub tx_from: Vec<u8>,
+    // None for contract creations
+    pub tx_to: Option<Vec<u8>>,
+    pub gas_used: i64,
+    pub effective_gas_price: Vec<u8>,
+    pub status: bool,
+}
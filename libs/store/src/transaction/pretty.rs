@@ -0,0 +1,18 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{transaction::model::Transaction, utils};
+
+impl Debug for Transaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Transaction")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("tx_from", &utils::bytes_to_address(&self.tx_from))
+            .field("tx_to", &self.tx_to.as_deref().map(utils::bytes_to_address))
+            .field("gas_used", &self.gas_used)
+            .field("effective_gas_price", &utils::bytes_to_u256(&self.effective_gas_price))
+            .field("status", &self.status)
+            .finish()
+    }
+}
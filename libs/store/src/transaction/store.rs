@@ -0,0 +1,141 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::transaction::model::Transaction;
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use sqlx::Error;
+
+// Transactions of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // TRANSACTIONS
+    // ---------------------------
+
+    /// Inserts multiple transactions in batches, respecting SQLite's max variable limit.
+    pub async fn insert_transactions_batch(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<(), Error> {
+        if transactions.is_empty() {
+            return Ok(());
+        }
+
+        // SQLite variable limit = 999 by default
+        const COLS: usize = 10;
+        const SQLITE_MAX_VARIABLES: usize = 999;
+        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        for batch in transactions.chunks(max_rows_per_batch) {
+            let values_placeholders = (0..batch.len())
+                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            // SQLite skips rows that violate the constraint, keeps the rest.
+            let mut query = String::from(
+                "INSERT OR IGNORE INTO transactions (
+                    chain_id, job, block_number, block_hash, transaction_hash,
+                    tx_from, tx_to, gas_used, effective_gas_price, status
+                ) VALUES ",
+            );
+            query.push_str(&values_placeholders);
+
+            let mut q = sqlx::query(&query);
+            for transaction in batch {
+                q = q
+                    .bind(self.chain_id)
+                    .bind(&self.job)
+                    .bind(transaction.block_number)
+                    .bind(&transaction.block_hash)
+                    .bind(&transaction.transaction_hash)
+                    .bind(&transaction.tx_from)
+                    .bind(&transaction.tx_to)
+                    .bind(transaction.gas_used)
+                    .bind(&transaction.effective_gas_price)
+                    .bind(transaction.status);
+            }
+            q.execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_transaction(
+        &self,
+        transaction_hash: &[u8],
+    ) -> Result<Option<Transaction>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash,
+                tx_from, tx_to, gas_used, effective_gas_price, status
+            FROM transactions
+            WHERE chain_id = ? AND transaction_hash = ?
+            "#;
+        let transaction = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(transaction_hash)
+            .fetch_optional(self.client.pool())
+            .await?;
+
+        Ok(transaction)
+    }
+
+    pub async fn get_transactions_from_block_number(
+        &self,
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<Transaction>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash,
+                tx_from, tx_to, gas_used, effective_gas_price, status
+            FROM transactions
+            WHERE chain_id = ? AND block_number >= ?
+            ORDER BY block_number ASC, id ASC
+            "#;
+        let transactions = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(transactions)
+    }
+
+    pub async fn delete_transactions_after_block_number(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<u64, Error> {
+        let query = r#"
+            DELETE FROM transactions
+            WHERE chain_id = ? AND job = ? AND block_number > ?
+            "#;
+        let result = sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(block_number as i64)
+            .execute(self.client.pool())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -3,10 +3,18 @@ use alloy::rpc::types::Log;
 use eyre::{Result, eyre};
 use std::convert::TryFrom;
 
+// ERC-20 `Transfer(address,address,uint256)` indexes `from` and `to` only:
+// 3 topics (signature, from, to) and the amount in data.
+const ERC20_TRANSFER_TOPICS: usize = 3;
+
 impl TryFrom<Log> for Transfer {
     type Error = eyre::Report;
 
     fn try_from(log: Log) -> Result<Self> {
+        let nbr_of_topics = log.topics().len();
+        if nbr_of_topics != ERC20_TRANSFER_TOPICS {
+            return Err(eyre!("not an ERC-20 transfer: {nbr_of_topics:?} topics"));
+        }
         Ok(Self {
             block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
             block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
@@ -24,6 +32,8 @@ impl TryFrom<Log> for Transfer {
                 .to_vec(),
             to_address: log.topics().get(2).ok_or_else(|| eyre!("missing to"))?.as_slice().to_vec(),
             amount: log.data().data.to_vec(),
+            block_timestamp: log.block_timestamp.map(|timestamp| timestamp as i64),
+            chain_id: None,
         })
     }
 }
@@ -10,4 +10,10 @@ pub struct Transfer {
     pub from_address: Vec<u8>,
     pub to_address: Vec<u8>,
     pub amount: Vec<u8>,
+    // Timestamp of the block, when its header has been recorded
+    #[sqlx(default)]
+    pub block_timestamp: Option<i64>,
+    // Chain the transfer happened on, set by the store it is persisted with
+    #[sqlx(default)]
+    pub chain_id: Option<i64>,
 }
@@ -13,6 +13,8 @@ impl Debug for Transfer {
             .field("from_address", &utils::bytes_to_address(&self.from_address[..]))
             .field("to_address", &utils::bytes_to_address(&self.to_address[..]))
             .field("amount", &utils::bytes_to_u256(&self.amount))
+            .field("block_timestamp", &self.block_timestamp)
+            .field("chain_id", &self.chain_id)
             .finish()
     }
 }
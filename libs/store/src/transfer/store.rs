@@ -1,16 +1,29 @@
-use crate::client::Client;
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::supply::store as supply_store;
 use crate::transfer::model::Transfer;
+use crate::utils::contract_filter;
 use alloy::primitives::BlockNumber;
 use eyre::Result;
-use sqlx::Error;
+use sqlx::{Error, Sqlite, Transaction};
 
+// Transfers of a single chain
 pub struct Store {
     client: Client,
+    chain_id: i64,
+    job: String,
 }
 
 impl Store {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
     }
 
     // ---------------------------
@@ -18,26 +31,7 @@ impl Store {
     // ---------------------------
 
     pub async fn insert_transfer(&self, log: &Transfer) -> Result<(), Error> {
-        let query = r#"
-            INSERT OR IGNORE INTO transfers (
-                block_number, block_hash, transaction_hash, log_index,
-                contract_address, from_address, to_address, amount
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-            "#;
-
-        sqlx::query(query)
-            .bind(log.block_number)
-            .bind(&log.block_hash)
-            .bind(&log.transaction_hash)
-            .bind(log.log_index)
-            .bind(&log.contract_address)
-            .bind(&log.from_address)
-            .bind(&log.to_address)
-            .bind(&log.amount)
-            .execute(self.client.pool())
-            .await?;
-        Ok(())
+        self.insert_transfers_batch(std::slice::from_ref(log)).await
     }
 
     /// Inserts multiple transfers in batches, respecting SQLite's max variable limit.
@@ -46,48 +40,10 @@ impl Store {
             return Ok(());
         }
 
-        // SQLite variable limit = 999 by default
-        const COLS: usize = 8;
-        const SQLITE_MAX_VARIABLES: usize = 999;
-        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
-
-        let mut start = 0;
-        while start < transfers.len() {
-            let end = (start + max_rows_per_batch).min(transfers.len());
-            let batch = &transfers[start..end];
-
-            let values_placeholders =
-                (0..batch.len()).map(|_| "(?, ?, ?, ?, ?, ?, ?, ?)").collect::<Vec<_>>().join(", ");
-
-            // SQLite skips rows that violate the constraint, keeps the rest.
-            let mut query = String::from(
-                "INSERT OR IGNORE INTO transfers (
-                    block_number, block_hash, transaction_hash, log_index,
-                    contract_address, from_address, to_address, amount
-                ) VALUES ",
-            );
-            query.push_str(&values_placeholders);
-
-            let mut q = sqlx::query(&query);
-            for log in batch {
-                q = q
-                    .bind(log.block_number)
-                    .bind(&log.block_hash)
-                    .bind(&log.transaction_hash)
-                    .bind(log.log_index)
-                    .bind(&log.contract_address)
-                    .bind(&log.from_address)
-                    .bind(&log.to_address)
-                    .bind(&log.amount);
-            }
-
-            // Wrap in transaction for speed + atomicity
-            let mut tx = self.client.pool().begin().await?;
-            q.execute(&mut *tx).await?;
-            tx.commit().await?;
-
-            start = end;
-        }
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        insert_transfers(&mut tx, self.chain_id, &self.job, transfers).await?;
+        tx.commit().await?;
 
         Ok(())
     }
@@ -98,13 +54,44 @@ impl Store {
     ) -> Result<Vec<Transfer>, Error> {
         let query = r#"
             SELECT
-                block_number, block_hash, transaction_hash, log_index,
-                contract_address, from_address, to_address, amount
-            FROM transfers
-            WHERE block_number >= ?
-            ORDER BY block_number ASC, log_index ASC
+                t.block_number, t.block_hash, t.transaction_hash, t.log_index,
+                t.contract_address, t.from_address, t.to_address, t.amount,
+                t.chain_id, b.timestamp AS block_timestamp
+            FROM transfers t
+            LEFT JOIN blocks b ON b.chain_id = t.chain_id AND b.job = t.job
+                AND b.block_number = t.block_number AND b.block_hash = t.block_hash
+            WHERE t.chain_id = ? AND t.block_number >= ?
+            ORDER BY t.block_number ASC, t.log_index ASC
+            "#;
+        let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(logs)
+    }
+
+    /// Transfers of a single contract, served by the (contract, block number) index
+    pub async fn get_contract_transfers_from_block_number(
+        &self,
+        contract_address: &[u8],
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<Transfer>, Error> {
+        let query = r#"
+            SELECT
+                t.block_number, t.block_hash, t.transaction_hash, t.log_index,
+                t.contract_address, t.from_address, t.to_address, t.amount,
+                t.chain_id, b.timestamp AS block_timestamp
+            FROM transfers t
+            LEFT JOIN blocks b ON b.chain_id = t.chain_id AND b.job = t.job
+                AND b.block_number = t.block_number AND b.block_hash = t.block_hash
+            WHERE t.chain_id = ? AND t.contract_address = ? AND t.block_number >= ?
+            ORDER BY t.block_number ASC, t.log_index ASC
             "#;
         let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(contract_address)
             .bind(from_block_number as i64)
             .fetch_all(self.client.pool())
             .await?;
@@ -119,13 +106,17 @@ impl Store {
     ) -> Result<Vec<Transfer>, Error> {
         let query = r#"
             SELECT
-                block_number, block_hash, transaction_hash, log_index,
-                contract_address, from_address, to_address, amount
-            FROM transfers
-            WHERE block_number BETWEEN ? AND ?
-            ORDER BY block_number ASC, log_index ASC
+                t.block_number, t.block_hash, t.transaction_hash, t.log_index,
+                t.contract_address, t.from_address, t.to_address, t.amount,
+                t.chain_id, b.timestamp AS block_timestamp
+            FROM transfers t
+            LEFT JOIN blocks b ON b.chain_id = t.chain_id AND b.job = t.job
+                AND b.block_number = t.block_number AND b.block_hash = t.block_hash
+            WHERE t.chain_id = ? AND t.block_number BETWEEN ? AND ?
+            ORDER BY t.block_number ASC, t.log_index ASC
             "#;
         let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
             .bind(from_block as i64)
             .bind(to_block as i64)
             .fetch_all(self.client.pool())
@@ -133,4 +124,134 @@ impl Store {
 
         Ok(logs)
     }
+
+    // Only transfers whose block header has been recorded have a timestamp
+    pub async fn get_transfers_between_timestamps(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<Transfer>, Error> {
+        let query = r#"
+            SELECT
+                t.block_number, t.block_hash, t.transaction_hash, t.log_index,
+                t.contract_address, t.from_address, t.to_address, t.amount,
+                t.chain_id, b.timestamp AS block_timestamp
+            FROM transfers t
+            INNER JOIN blocks b ON b.chain_id = t.chain_id AND b.job = t.job
+                AND b.block_number = t.block_number AND b.block_hash = t.block_hash
+            WHERE t.chain_id = ? AND b.timestamp BETWEEN ? AND ?
+            ORDER BY t.block_number ASC, t.log_index ASC
+            "#;
+        let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_timestamp)
+            .bind(to_timestamp)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(logs)
+    }
+
+    pub async fn delete_transfers_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let mut tx = self.client.pool().begin().await?;
+        let deleted = delete_transfers_after(
+            &mut tx,
+            self.chain_id,
+            &self.job,
+            block_number,
+            contract_addresses,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(deleted.len() as u64)
+    }
+}
+
+// Inserts transfers within the given transaction, respecting SQLite's max variable limit,
+// along with the supply changes of mints and burns.
+// Returns the newly inserted ones only (duplicates are ignored).
+pub(crate) async fn insert_transfers(
+    tx: &mut Transaction<'_, Sqlite>,
+    chain_id: i64,
+    job: &str,
+    transfers: &[Transfer],
+) -> Result<Vec<Transfer>, Error> {
+    // SQLite variable limit = 999 by default
+    const COLS: usize = 10;
+    const SQLITE_MAX_VARIABLES: usize = 999;
+    let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+    let mut inserted = Vec::with_capacity(transfers.len());
+    for batch in transfers.chunks(max_rows_per_batch) {
+        let values_placeholders = (0..batch.len())
+            .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // SQLite skips rows that violate the constraint, keeps the rest.
+        let mut query = String::from(
+            "INSERT OR IGNORE INTO transfers (
+                chain_id, job, block_number, block_hash, transaction_hash, log_index,
+                contract_address, from_address, to_address, amount
+            ) VALUES ",
+        );
+        query.push_str(&values_placeholders);
+        query.push_str(
+            " RETURNING
+                chain_id, block_number, block_hash, transaction_hash, log_index,
+                contract_address, from_address, to_address, amount",
+        );
+
+        let mut q = sqlx::query_as(&query);
+        for log in batch {
+            q = q
+                .bind(chain_id)
+                .bind(job)
+                .bind(log.block_number)
+                .bind(&log.block_hash)
+                .bind(&log.transaction_hash)
+                .bind(log.log_index)
+                .bind(&log.contract_address)
+                .bind(&log.from_address)
+                .bind(&log.to_address)
+                .bind(&log.amount);
+        }
+        inserted.extend(q.fetch_all(&mut **tx).await?);
+    }
+    supply_store::insert_supply_changes(tx, chain_id, job, &inserted).await?;
+
+    Ok(inserted)
+}
+
+// Deletes the transfers of a job above the given block number within the given transaction,
+// along with their supply changes, of the given contracts only if any. Returns the deleted ones.
+pub(crate) async fn delete_transfers_after(
+    tx: &mut Transaction<'_, Sqlite>,
+    chain_id: i64,
+    job: &str,
+    block_number: BlockNumber,
+    contract_addresses: &[Vec<u8>],
+) -> Result<Vec<Transfer>, Error> {
+    let query = format!(
+        "DELETE FROM transfers
+        WHERE chain_id = ? AND job = ? AND block_number > ?{}
+        RETURNING
+            chain_id, block_number, block_hash, transaction_hash, log_index,
+            contract_address, from_address, to_address, amount",
+        contract_filter(contract_addresses)
+    );
+    let mut q = sqlx::query_as(&query).bind(chain_id).bind(job).bind(block_number as i64);
+    for contract_address in contract_addresses {
+        q = q.bind(contract_address);
+    }
+    let deleted = q.fetch_all(&mut **tx).await?;
+    supply_store::delete_supply_changes_after(tx, chain_id, job, block_number, contract_addresses)
+        .await?;
+
+    Ok(deleted)
 }
@@ -0,0 +1,68 @@
+use crate::transfer_batch::model::TransferBatch;
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    rpc::types::Log,
+};
+use eyre::{Result, eyre};
+use std::convert::TryFrom;
+
+// ERC-1155 `TransferBatch(address,address,address,uint256[],uint256[])` indexes
+// operator, from and to. The token ids and amounts are ABI encoded in data.
+impl TryFrom<Log> for TransferBatch {
+    type Error = eyre::Report;
+
+    fn try_from(log: Log) -> Result<Self> {
+        let uint_array = DynSolType::Array(Box::new(DynSolType::Uint(256)));
+        let decoded = DynSolType::Tuple(vec![uint_array.clone(), uint_array])
+            .abi_decode_params(&log.data().data)?;
+        let (token_ids, amounts) = match decoded.as_tuple() {
+            Some([token_ids, amounts]) => (uint_words(token_ids)?, uint_words(amounts)?),
+            _ => return Err(eyre!("invalid TransferBatch data")),
+        };
+        if token_ids.len() != amounts.len() {
+            return Err(eyre!(
+                "mismatched TransferBatch lengths: {:?} ids, {:?} values",
+                token_ids.len(),
+                amounts.len()
+            ));
+        }
+
+        Ok(Self {
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+            contract_address: log.address().to_vec(),
+            operator_address: log
+                .topics()
+                .get(1)
+                .ok_or_else(|| eyre!("missing operator"))?
+                .as_slice()
+                .to_vec(),
+            from_address: log
+                .topics()
+                .get(2)
+                .ok_or_else(|| eyre!("missing from"))?
+                .as_slice()
+                .to_vec(),
+            to_address: log.topics().get(3).ok_or_else(|| eyre!("missing to"))?.as_slice().to_vec(),
+            token_ids,
+            amounts,
+        })
+    }
+}
+
+fn uint_words(value: &DynSolValue) -> Result<Vec<Vec<u8>>> {
+    value
+        .as_array()
+        .ok_or_else(|| eyre!("expected uint256[]"))?
+        .iter()
+        .map(|value| match value.as_uint() {
+            Some((u, _)) => Ok(u.to_be_bytes::<32>().to_vec()),
+            None => Err(eyre!("expected uint256")),
+        })
+        .collect()
+}
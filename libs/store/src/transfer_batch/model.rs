@@ -0,0 +1,14 @@
+#[derive(Clone, PartialEq, PartialOrd)]
+pub struct TransferBatch {
+    pub block_number: i64,
+    pub block_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub log_index: i64,
+    pub contract_address: Vec<u8>,
+    pub operator_address: Vec<u8>,
+    pub from_address: Vec<u8>,
+    pub to_address: Vec<u8>,
+    // Token ids and amounts are paired by position
+    pub token_ids: Vec<Vec<u8>>,
+    pub amounts: Vec<Vec<u8>>,
+}
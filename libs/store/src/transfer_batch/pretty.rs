@@ -0,0 +1,23 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{transfer_batch::model::TransferBatch, utils};
+
+impl Debug for TransferBatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let token_ids: Vec<String> =
+            self.token_ids.iter().map(|id| utils::bytes_to_u256(id)).collect();
+        let amounts: Vec<String> = self.amounts.iter().map(|a| utils::bytes_to_u256(a)).collect();
+        f.debug_struct("TransferBatch")
+            .field("block_number", &self.block_number)
+            .field("block_hash", &utils::bytes_to_hex(&self.block_hash))
+            .field("transaction_hash", &utils::bytes_to_hex(&self.transaction_hash))
+            .field("log_index", &self.log_index)
+            .field("contract_address", &utils::bytes_to_address(&self.contract_address[..]))
+            .field("operator_address", &utils::bytes_to_address(&self.operator_address[..]))
+            .field("from_address", &utils::bytes_to_address(&self.from_address[..]))
+            .field("to_address", &utils::bytes_to_address(&self.to_address[..]))
+            .field("token_ids", &token_ids)
+            .field("amounts", &amounts)
+            .finish()
+    }
+}
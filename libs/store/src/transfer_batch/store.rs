@@ -0,0 +1,202 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::transfer_batch::model::TransferBatch;
+use crate::utils::contract_filter;
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use sqlx::{Error, FromRow};
+
+// A batch transfer is stored as one row per (token id, amount) pair
+#[derive(FromRow)]
+struct TransferBatchRow {
+    block_number: i64,
+    block_hash: Vec<u8>,
+    transaction_hash: Vec<u8>,
+    log_index: i64,
+    contract_address: Vec<u8>,
+    operator_address: Vec<u8>,
+    from_address: Vec<u8>,
+    to_address: Vec<u8>,
+    token_id: Vec<u8>,
+    amount: Vec<u8>,
+}
+
+// Batch transfers of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // TRANSFER BATCH LOGS
+    // ---------------------------
+
+    pub async fn insert_transfer_batch(&self, log: &TransferBatch) -> Result<(), Error> {
+        self.insert_transfer_batches_batch(std::slice::from_ref(log)).await
+    }
+
+    /// Inserts multiple batch transfers (expanded into rows) in batches,
+    /// respecting SQLite's max variable limit.
+    pub async fn insert_transfer_batches_batch(
+        &self,
+        transfer_batches: &[TransferBatch],
+    ) -> Result<(), Error> {
+        let rows: Vec<(&TransferBatch, usize)> = transfer_batches
+            .iter()
+            .flat_map(|log| (0..log.token_ids.len()).map(move |batch_index| (log, batch_index)))
+            .collect();
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        // SQLite variable limit = 999 by default
+        const COLS: usize = 13;
+        const SQLITE_MAX_VARIABLES: usize = 999;
+        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+        // Wrap in transaction for speed + atomicity
+        let mut tx = self.client.pool().begin().await?;
+        for batch in rows.chunks(max_rows_per_batch) {
+            let values_placeholders = (0..batch.len())
+                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            // SQLite skips rows that violate the constraint, keeps the rest.
+            let mut query = String::from(
+                "INSERT OR IGNORE INTO transfer_batches (
+                    chain_id, job, block_number, block_hash, transaction_hash, log_index, batch_index,
+                    contract_address, operator_address, from_address, to_address, token_id, amount
+                ) VALUES ",
+            );
+            query.push_str(&values_placeholders);
+
+            let mut q = sqlx::query(&query);
+            for (log, batch_index) in batch {
+                q = q
+                    .bind(self.chain_id)
+                    .bind(&self.job)
+                    .bind(log.block_number)
+                    .bind(&log.block_hash)
+                    .bind(&log.transaction_hash)
+                    .bind(log.log_index)
+                    .bind(*batch_index as i64)
+                    .bind(&log.contract_address)
+                    .bind(&log.operator_address)
+                    .bind(&log.from_address)
+                    .bind(&log.to_address)
+                    .bind(&log.token_ids[*batch_index])
+                    .bind(&log.amounts[*batch_index]);
+            }
+            q.execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_transfer_batches_from_block_number(
+        &self,
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<TransferBatch>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, operator_address, from_address, to_address, token_id, amount
+            FROM transfer_batches
+            WHERE chain_id = ? AND block_number >= ?
+            ORDER BY block_number ASC, log_index ASC, batch_index ASC
+            "#;
+        let rows = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(group_rows(rows))
+    }
+
+    pub async fn get_transfer_batches_between_block_numbers(
+        &self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<TransferBatch>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, operator_address, from_address, to_address, token_id, amount
+            FROM transfer_batches
+            WHERE chain_id = ? AND block_number BETWEEN ? AND ?
+            ORDER BY block_number ASC, log_index ASC, batch_index ASC
+            "#;
+        let rows = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block as i64)
+            .bind(to_block as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(group_rows(rows))
+    }
+
+    pub async fn delete_transfer_batches_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let query = format!(
+            "DELETE FROM transfer_batches WHERE chain_id = ? AND job = ? AND block_number > ?{}",
+            contract_filter(contract_addresses)
+        );
+        let mut q =
+            sqlx::query(&query).bind(self.chain_id).bind(&self.job).bind(block_number as i64);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        let result = q.execute(self.client.pool()).await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+// Groups back ordered rows into their batch transfer log
+fn group_rows(rows: Vec<TransferBatchRow>) -> Vec<TransferBatch> {
+    let mut transfer_batches: Vec<TransferBatch> = vec![];
+    for row in rows {
+        match transfer_batches.last_mut() {
+            Some(last)
+                if last.transaction_hash == row.transaction_hash
+                    && last.log_index == row.log_index =>
+            {
+                last.token_ids.push(row.token_id);
+                last.amounts.push(row.amount);
+            }
+            _ => transfer_batches.push(TransferBatch {
+                block_number: row.block_number,
+                block_hash: row.block_hash,
+                transaction_hash: row.transaction_hash,
+                log_index: row.log_index,
+                contract_address: row.contract_address,
+                operator_address: row.operator_address,
+                from_address: row.from_address,
+                to_address: row.to_address,
+                token_ids: vec![row.token_id],
+                amounts: vec![row.amount],
+            }),
+        }
+    }
+    transfer_batches
+}
@@ -0,0 +1,44 @@
+use crate::transfer_single::model::TransferSingle;
+use alloy::rpc::types::Log;
+use eyre::{Result, eyre};
+use std::convert::TryFrom;
+
+// ERC-1155 `TransferSingle(address,address,address,uint256,uint256)` indexes
+// operator, from and to. The token id and amount are the two data words.
+const WORD: usize = 32;
+
+impl TryFrom<Log> for TransferSingle {
+    type Error = eyre::Report;
+
+    fn try_from(log: Log) -> Result<Self> {
+        let data = &log.data().data;
+        if data.len() != 2 * WORD {
+            return Err(eyre!("invalid TransferSingle data length: {:?}", data.len()));
+        }
+        Ok(Self {
+            block_number: log.block_number.ok_or_else(|| eyre!("missing block_number"))? as i64,
+            block_hash: log.block_hash.ok_or_else(|| eyre!("missing block_hash"))?.to_vec(),
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| eyre!("missing transaction_hash"))?
+                .to_vec(),
+            log_index: log.log_index.ok_or_else(|| eyre!("missing log_index"))? as i64,
+            contract_address: log.address().to_vec(),
+            operator_address: log
+                .topics()
+                .get(1)
+                .ok_or_else(|| eyre!("missing operator"))?
+                .as_slice()
+                .to_vec(),
+            from_address: log
+                .topics()
+                .get(2)
+                .ok_or_else(|| eyre!("missing from"))?
+                .as_slice()
+                .to_vec(),
+            to_address: log.topics().get(3).ok_or_else(|| eyre!("missing to"))?.as_slice().to_vec(),
+            token_id: data[..WORD].to_vec(),
+            amount: data[WORD..].to_vec(),
+        })
+    }
+}
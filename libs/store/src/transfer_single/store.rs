@@ -0,0 +1,182 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::transfer_single::model::TransferSingle;
+use crate::utils::contract_filter;
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use sqlx::Error;
+
+// Single transfers of a single chain
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // TRANSFER SINGLE LOGS
+    // ---------------------------
+
+    pub async fn insert_transfer_single(&self, log: &TransferSingle) -> Result<(), Error> {
+        let query = r#"
+            INSERT OR IGNORE INTO transfer_singles (
+                chain_id, job, block_number, block_hash, transaction_hash, log_index,
+                contract_address, operator_address, from_address, to_address, token_id, amount
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#;
+
+        sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(log.block_number)
+            .bind(&log.block_hash)
+            .bind(&log.transaction_hash)
+            .bind(log.log_index)
+            .bind(&log.contract_address)
+            .bind(&log.operator_address)
+            .bind(&log.from_address)
+            .bind(&log.to_address)
+            .bind(&log.token_id)
+            .bind(&log.amount)
+            .execute(self.client.pool())
+            .await?;
+        Ok(())
+    }
+
+    /// Inserts multiple single transfers in batches, respecting SQLite's max variable limit.
+    pub async fn insert_transfer_singles_batch(
+        &self,
+        transfer_singles: &[TransferSingle],
+    ) -> Result<(), Error> {
+        if transfer_singles.is_empty() {
+            return Ok(());
+        }
+
+        // SQLite variable limit = 999 by default
+        const COLS: usize = 12;
+        const SQLITE_MAX_VARIABLES: usize = 999;
+        let max_rows_per_batch = SQLITE_MAX_VARIABLES / COLS;
+
+        let mut start = 0;
+        while start < transfer_singles.len() {
+            let end = (start + max_rows_per_batch).min(transfer_singles.len());
+            let batch = &transfer_singles[start..end];
+
+            let values_placeholders = (0..batch.len())
+                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            // SQLite skips rows that violate the constraint, keeps the rest.
+            let mut query = String::from(
+                "INSERT OR IGNORE INTO transfer_singles (
+                    chain_id, job, block_number, block_hash, transaction_hash, log_index,
+                    contract_address, operator_address, from_address, to_address, token_id, amount
+                ) VALUES ",
+            );
+            query.push_str(&values_placeholders);
+
+            let mut q = sqlx::query(&query);
+            for log in batch {
+                q = q
+                    .bind(self.chain_id)
+                    .bind(&self.job)
+                    .bind(log.block_number)
+                    .bind(&log.block_hash)
+                    .bind(&log.transaction_hash)
+                    .bind(log.log_index)
+                    .bind(&log.contract_address)
+                    .bind(&log.operator_address)
+                    .bind(&log.from_address)
+                    .bind(&log.to_address)
+                    .bind(&log.token_id)
+                    .bind(&log.amount);
+            }
+
+            // Wrap in transaction for speed + atomicity
+            let mut tx = self.client.pool().begin().await?;
+            q.execute(&mut *tx).await?;
+            tx.commit().await?;
+
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_transfer_singles_from_block_number(
+        &self,
+        from_block_number: BlockNumber,
+    ) -> Result<Vec<TransferSingle>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, operator_address, from_address, to_address, token_id, amount
+            FROM transfer_singles
+            WHERE chain_id = ? AND block_number >= ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block_number as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(logs)
+    }
+
+    pub async fn get_transfer_singles_between_block_numbers(
+        &self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<TransferSingle>, Error> {
+        let query = r#"
+            SELECT
+                block_number, block_hash, transaction_hash, log_index,
+                contract_address, operator_address, from_address, to_address, token_id, amount
+            FROM transfer_singles
+            WHERE chain_id = ? AND block_number BETWEEN ? AND ?
+            ORDER BY block_number ASC, log_index ASC
+            "#;
+        let logs = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(from_block as i64)
+            .bind(to_block as i64)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(logs)
+    }
+
+    pub async fn delete_transfer_singles_after_block_number(
+        &self,
+        block_number: BlockNumber,
+        contract_addresses: &[Vec<u8>],
+    ) -> Result<u64, Error> {
+        let query = format!(
+            "DELETE FROM transfer_singles WHERE chain_id = ? AND job = ? AND block_number > ?{}",
+            contract_filter(contract_addresses)
+        );
+        let mut q =
+            sqlx::query(&query).bind(self.chain_id).bind(&self.job).bind(block_number as i64);
+        for contract_address in contract_addresses {
+            q = q.bind(contract_address);
+        }
+        let result = q.execute(self.client.pool()).await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,6 +1,7 @@
 use alloy::{
+    dyn_abi::DynSolValue,
     hex,
-    primitives::{Address, U256},
+    primitives::{Address, I256, U256},
 };
 
 pub fn bytes_to_hex(bytes: &[u8]) -> String {
@@ -23,6 +24,10 @@ pub fn bytes_to_address(bytes: &[u8]) -> String {
     }
 }
 
+pub fn is_zero_address(bytes: &[u8]) -> bool {
+    bytes.iter().all(|byte| *byte == 0)
+}
+
 pub fn bytes_to_u256(bytes: &[u8]) -> String {
     if bytes.len() <= 32 {
         let mut arr = [0u8; 32];
@@ -33,3 +38,40 @@ pub fn bytes_to_u256(bytes: &[u8]) -> String {
         format!("<invalid u256: {}>", bytes_to_hex(bytes))
     }
 }
+
+pub fn bytes_to_i256(bytes: &[u8]) -> String {
+    if bytes.len() == 32 {
+        let balance = I256::from_raw(U256::from_be_slice(bytes));
+        format!("{balance}")
+    } else {
+        format!("<invalid i256: {}>", bytes_to_hex(bytes))
+    }
+}
+
+pub fn sol_value_to_string(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Address(address) => format!("{address:?}"),
+        DynSolValue::Bool(b) => format!("{b}"),
+        DynSolValue::Int(i, _) => format!("{i}"),
+        DynSolValue::Uint(u, _) => format!("{u}"),
+        DynSolValue::FixedBytes(word, size) => bytes_to_hex(&word[..*size]),
+        DynSolValue::Bytes(bytes) => bytes_to_hex(bytes),
+        DynSolValue::String(s) => s.clone(),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values) => {
+            let values: Vec<String> = values.iter().map(sol_value_to_string).collect();
+            format!("[{}]", values.join(", "))
+        }
+        other => bytes_to_hex(&other.abi_encode()),
+    }
+}
+
+// Restricts a query to the rows of the given contracts, an empty filter matching every one
+pub(crate) fn contract_filter(contract_addresses: &[Vec<u8>]) -> String {
+    if contract_addresses.is_empty() {
+        return String::new();
+    }
+    let placeholders = vec!["?"; contract_addresses.len()].join(", ");
+    format!(" AND contract_address IN ({placeholders})")
+}
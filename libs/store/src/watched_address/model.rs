@@ -0,0 +1,24 @@
+use sqlx::FromRow;
+
+// Address added to (or removed from) the watch list of a job while running
+#[derive(Clone, FromRow, PartialEq)]
+pub struct WatchedAddress {
+    pub address: Vec<u8>,
+    // False once removed, overriding the addresses the job was started with
+    pub watched: bool,
+    // Next block to backfill (the backfill checkpoint), up to the block the address was added at
+    pub backfill_from_block: Option<i64>,
+    pub backfill_to_block: Option<i64>,
+}
+
+impl WatchedAddress {
+    // Blocks left to backfill, if any (e.g. interrupted by a restart)
+    pub fn pending_backfill(&self) -> Option<(i64, i64)> {
+        match (self.watched, self.backfill_from_block, self.backfill_to_block) {
+            (true, Some(from_block), Some(to_block)) if from_block <= to_block => {
+                Some((from_block, to_block))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::{utils, watched_address::model::WatchedAddress};
+
+impl Debug for WatchedAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("WatchedAddress")
+            .field("address", &utils::bytes_to_address(&self.address))
+            .field("watched", &self.watched)
+            .field("backfill_from_block", &self.backfill_from_block)
+            .field("backfill_to_block", &self.backfill_to_block)
+            .finish()
+    }
+}
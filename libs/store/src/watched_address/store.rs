@@ -0,0 +1,117 @@
+use crate::client::{Client, DEFAULT_CHAIN_ID, DEFAULT_JOB};
+use crate::watched_address::model::WatchedAddress;
+use alloy::primitives::BlockNumber;
+use eyre::Result;
+use sqlx::Error;
+
+// Watch list changes of a single indexing job on a single chain
+#[derive(Clone)]
+pub struct Store {
+    client: Client,
+    chain_id: i64,
+    job: String,
+}
+
+impl Store {
+    pub fn new(client: Client) -> Self {
+        Self { client, chain_id: DEFAULT_CHAIN_ID as i64, job: DEFAULT_JOB.to_string() }
+    }
+
+    pub fn on_chain(self, chain_id: u64) -> Self {
+        Self { chain_id: chain_id as i64, ..self }
+    }
+
+    pub fn for_job(self, job: &str) -> Self {
+        Self { job: job.to_string(), ..self }
+    }
+
+    // ---------------------------
+    // WATCHED ADDRESSES
+    // ---------------------------
+
+    /// Records an added address, along with the blocks to backfill if any.
+    pub async fn insert_watched_address(
+        &self,
+        address: &[u8],
+        backfill: Option<(BlockNumber, BlockNumber)>,
+    ) -> Result<(), Error> {
+        let query = r#"
+            INSERT INTO watched_addresses (
+                chain_id, job, address, watched, backfill_from_block, backfill_to_block
+            ) VALUES (?, ?, ?, TRUE, ?, ?)
+            ON CONFLICT (chain_id, job, address) DO UPDATE SET
+                watched = excluded.watched,
+                backfill_from_block = excluded.backfill_from_block,
+                backfill_to_block = excluded.backfill_to_block
+            "#;
+        sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(address)
+            .bind(backfill.map(|(from_block, _)| from_block as i64))
+            .bind(backfill.map(|(_, to_block)| to_block as i64))
+            .execute(self.client.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Records a removed address, cancelling its backfill.
+    pub async fn remove_watched_address(&self, address: &[u8]) -> Result<(), Error> {
+        let query = r#"
+            INSERT INTO watched_addresses (
+                chain_id, job, address, watched, backfill_from_block, backfill_to_block
+            ) VALUES (?, ?, ?, FALSE, NULL, NULL)
+            ON CONFLICT (chain_id, job, address) DO UPDATE SET
+                watched = excluded.watched,
+                backfill_from_block = NULL,
+                backfill_to_block = NULL
+            "#;
+        sqlx::query(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(address)
+            .execute(self.client.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Moves the backfill checkpoint of an address to the next block to backfill.
+    pub async fn update_backfill_from_block(
+        &self,
+        address: &[u8],
+        from_block: BlockNumber,
+    ) -> Result<(), Error> {
+        let query = r#"
+            UPDATE watched_addresses
+            SET backfill_from_block = ?
+            WHERE chain_id = ? AND job = ? AND address = ? AND watched
+            "#;
+        sqlx::query(query)
+            .bind(from_block as i64)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .bind(address)
+            .execute(self.client.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_watched_addresses(&self) -> Result<Vec<WatchedAddress>, Error> {
+        let query = r#"
+            SELECT address, watched, backfill_from_block, backfill_to_block
+            FROM watched_addresses
+            WHERE chain_id = ? AND job = ?
+            ORDER BY id ASC
+            "#;
+        let watched_addresses = sqlx::query_as(query)
+            .bind(self.chain_id)
+            .bind(&self.job)
+            .fetch_all(self.client.pool())
+            .await?;
+
+        Ok(watched_addresses)
+    }
+}
@@ -1,9 +1,42 @@
 #[cfg(test)]
 mod tests {
-    use alloy::primitives::B256;
+    use alloy::{
+        dyn_abi::DynSolValue,
+        json_abi::{Event, JsonAbi},
+        primitives::{Address, B256, Bytes, I256, LogData, U256},
+        rpc::types::Log,
+    };
     use eyre::Result;
+    use std::sync::Arc;
     use store::{
-        checkpoint::model::Checkpoint, checkpoint::store::Store as CheckpointStore, client::Client,
+        approval::model::Approval,
+        approval::store::Store as ApprovalStore,
+        balance::store::Store as BalanceStore,
+        block::model::BlockHeader,
+        block::store::Store as BlockStore,
+        checkpoint::model::Checkpoint,
+        checkpoint::store::Store as CheckpointStore,
+        client::Client,
+        contract::model::Contract,
+        contract::store::Store as ContractStore,
+        event_log::model::{AbiLog, EventLog},
+        event_log::store::{Store as EventLogStore, column_names},
+        nft_transfer::model::NftTransfer,
+        nft_transfer::store::Store as NftTransferStore,
+        raw_log::model::RawLog,
+        raw_log::store::Store as RawLogStore,
+        supply::store::Store as SupplyStore,
+        token::model::Token,
+        token::store::Store as TokenStore,
+        transaction::model::Transaction,
+        transaction::store::Store as TransactionStore,
+        transfer::model::Transfer,
+        transfer::store::Store as TransferStore,
+        transfer_batch::model::TransferBatch,
+        transfer_batch::store::Store as TransferBatchStore,
+        transfer_single::model::TransferSingle,
+        transfer_single::store::Store as TransferSingleStore,
+        watched_address::store::Store as WatchedAddressStore,
     };
 
     #[tokio::test]
@@ -16,6 +49,7 @@ mod tests {
             block_number: 12345,
             block_hash: B256::repeat_byte(0xAB).to_vec(),
             parent_hash: B256::repeat_byte(0xBA).to_vec(),
+            chain_id: None,
         };
         store.insert_checkpoint(&checkpoint_1).await?;
 
@@ -23,6 +57,7 @@ mod tests {
             block_number: 12346,
             block_hash: B256::repeat_byte(0xCD).to_vec(),
             parent_hash: B256::repeat_byte(0xDC).to_vec(),
+            chain_id: None,
         };
         store.insert_checkpoint(&checkpoint_2).await?;
 
@@ -38,4 +73,934 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_delete_checkpoints_after_block_number() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = CheckpointStore::new(client);
+
+        for (block_number, byte) in [(100, 0x01), (101, 0x02), (102, 0x03)] {
+            let checkpoint = Checkpoint {
+                block_number,
+                block_hash: B256::repeat_byte(byte).to_vec(),
+                parent_hash: B256::repeat_byte(byte - 1).to_vec(),
+                chain_id: None,
+            };
+            store.insert_checkpoint(&checkpoint).await?;
+        }
+
+        let deleted = store.delete_checkpoints_after_block_number(100).await?;
+        assert_eq!(deleted, 2);
+
+        let last_checkpoints = store.get_last_checkpoints(10).await?;
+        assert_eq!(last_checkpoints.len(), 1);
+        assert_eq!(last_checkpoints[0].block_number, 100);
+
+        let last_checkpoint = store.get_last_checkpoint().await?.unwrap();
+        assert_eq!(last_checkpoint.block_number, 100);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checkpoints_per_job() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let usdc_store = CheckpointStore::new(client.clone()).for_job("usdc");
+        let weth_store = CheckpointStore::new(client.clone()).for_job("weth");
+
+        // Same block checkpointed by both jobs
+        let checkpoint = Checkpoint {
+            block_number: 100,
+            block_hash: B256::repeat_byte(0x01).to_vec(),
+            parent_hash: B256::repeat_byte(0x00).to_vec(),
+            chain_id: None,
+        };
+        usdc_store.insert_checkpoint(&checkpoint).await?;
+        weth_store.insert_checkpoint(&checkpoint).await?;
+
+        let ahead = Checkpoint {
+            block_number: 200,
+            block_hash: B256::repeat_byte(0x02).to_vec(),
+            parent_hash: B256::repeat_byte(0x01).to_vec(),
+            chain_id: None,
+        };
+        usdc_store.insert_checkpoint(&ahead).await?;
+
+        assert_eq!(usdc_store.get_last_checkpoint().await?.unwrap().block_number, 200);
+        assert_eq!(weth_store.get_last_checkpoint().await?.unwrap().block_number, 100);
+        assert!(CheckpointStore::new(client).get_last_checkpoint().await?.is_none());
+
+        // Rolling back a job leaves the others untouched
+        assert_eq!(weth_store.delete_checkpoints_after_block_number(50).await?, 1);
+        assert!(weth_store.get_checkpoint_by_number(100).await?.is_none());
+        assert!(usdc_store.get_checkpoint_by_number(100).await?.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_delete_event_logs() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let event = Arc::new(Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )?);
+        let store = EventLogStore::init(client.clone(), vec![Arc::clone(&event)]).await?;
+
+        let event_logs: Vec<EventLog> = (0..3)
+            .map(|i| EventLog {
+                block_number: 100 + i,
+                block_hash: B256::repeat_byte(i as u8).to_vec(),
+                transaction_hash: B256::repeat_byte(0xF0 + i as u8).to_vec(),
+                log_index: 0,
+                contract_address: Address::repeat_byte(0xAA).to_vec(),
+                event: Arc::clone(&event),
+                values: vec![
+                    DynSolValue::Address(Address::repeat_byte(0xBB)),
+                    DynSolValue::Address(Address::repeat_byte(0xCC)),
+                    DynSolValue::Uint(U256::from(i), 256),
+                ],
+            })
+            .collect();
+        store.insert_event_logs_batch(&event_logs).await?;
+        // duplicates are ignored
+        store.insert_event_log(&event_logs[0]).await?;
+
+        let count: i64 = sqlx::query_scalar(r#"SELECT COUNT(*) FROM event_transfer"#)
+            .fetch_one(client.pool())
+            .await?;
+        assert_eq!(count, 3);
+
+        let deleted = store.delete_event_logs_after_block_number(100, &[]).await?;
+        assert_eq!(deleted, 2);
+
+        let value: Vec<u8> = sqlx::query_scalar(r#"SELECT "value" FROM event_transfer"#)
+            .fetch_one(client.pool())
+            .await?;
+        assert_eq!(value, U256::ZERO.to_be_bytes::<32>().to_vec());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_event_log_tables_per_signature() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+
+        // Parameters named after the columns of every table are prefixed
+        let event =
+            Arc::new(Event::parse("event Staked(uint256 id, bytes32 job, uint64 chain_id)")?);
+        assert_eq!(
+            column_names(&event)[column_names(&event).len() - 3..],
+            ["param_id", "param_job", "param_chain_id"]
+        );
+        let store = EventLogStore::init(client.clone(), vec![Arc::clone(&event)]).await?;
+        let event_log = EventLog {
+            block_number: 100,
+            block_hash: B256::repeat_byte(0x01).to_vec(),
+            transaction_hash: B256::repeat_byte(0xF0).to_vec(),
+            log_index: 0,
+            contract_address: Address::repeat_byte(0xAA).to_vec(),
+            event: Arc::clone(&event),
+            values: vec![
+                DynSolValue::Uint(U256::from(7), 256),
+                DynSolValue::FixedBytes(B256::repeat_byte(0x0B), 32),
+                DynSolValue::Uint(U256::from(1), 64),
+            ],
+        };
+        store.insert_event_log(&event_log).await?;
+        let job: String =
+            sqlx::query_scalar(r#"SELECT job FROM event_staked"#).fetch_one(client.pool()).await?;
+        assert_eq!(job, "default");
+
+        // Events sharing a name but not a signature are rejected, rather than mixed up
+        let erc20_transfer = Arc::new(Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )?);
+        let erc721_transfer = Arc::new(Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+        )?);
+        let both = vec![Arc::clone(&erc20_transfer), Arc::clone(&erc721_transfer)];
+        assert!(EventLogStore::init(client.clone(), both).await.is_err());
+        EventLogStore::init(client.clone(), vec![erc20_transfer]).await?;
+        assert!(EventLogStore::init(client.clone(), vec![erc721_transfer]).await.is_err());
+
+        Ok(())
+    }
+
+    fn transfer_log(topics: Vec<B256>, data: Bytes) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xAA),
+                data: LogData::new_unchecked(topics, data),
+            },
+            block_number: Some(100),
+            block_hash: Some(B256::repeat_byte(0x01)),
+            transaction_hash: Some(B256::repeat_byte(0xF0)),
+            log_index: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_zamatoken_event_logs() -> Result<()> {
+        let artifact: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../resources/tests/zamatoken/ZamaToken.json"
+        ))?;
+        let abi: JsonAbi = serde_json::from_value(artifact["abi"].clone())?;
+        let approval = Arc::new(abi.event("Approval").and_then(|e| e.first()).unwrap().clone());
+
+        // Approval(address indexed owner, address indexed spender, uint256 value)
+        let owner = Address::repeat_byte(0xBB);
+        let spender = Address::repeat_byte(0xCC);
+        let value = U256::from(1_000_000u64);
+        let topics = vec![approval.selector(), owner.into_word(), spender.into_word()];
+        let log = transfer_log(topics.clone(), value.to_be_bytes::<32>().to_vec().into());
+
+        let event_log = EventLog::try_from(AbiLog { event: Arc::clone(&approval), log })?;
+        assert_eq!(event_log.block_number, 100);
+        assert_eq!(event_log.log_index, 0);
+        assert_eq!(event_log.contract_address, Address::repeat_byte(0xAA).to_vec());
+        assert_eq!(event_log.event.name, "Approval");
+        // Values follow the ABI inputs order, indexed or not
+        assert_eq!(
+            event_log.values,
+            vec![
+                DynSolValue::Address(owner),
+                DynSolValue::Address(spender),
+                DynSolValue::Uint(value, 256),
+            ]
+        );
+
+        // A log missing its non-indexed value does not decode
+        let log = transfer_log(topics, Bytes::new());
+        assert!(EventLog::try_from(AbiLog { event: approval, log }).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_delete_nft_transfers() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = NftTransferStore::new(client);
+
+        let signature = B256::repeat_byte(0xDD);
+        let from = Address::repeat_byte(0xBB).into_word();
+        let to = Address::repeat_byte(0xCC).into_word();
+        let token_id = B256::from(U256::from(42));
+
+        // ERC-721 logs index the token id and carry no data
+        let nft_log = transfer_log(vec![signature, from, to, token_id], Bytes::new());
+        assert!(Transfer::try_from(nft_log.clone()).is_err());
+        let nft_transfer = NftTransfer::try_from(nft_log)?;
+        assert_eq!(nft_transfer.token_id, token_id.to_vec());
+
+        // ERC-20 logs carry the amount in data
+        let erc20_log = transfer_log(vec![signature, from, to], token_id.into());
+        assert!(NftTransfer::try_from(erc20_log.clone()).is_err());
+        assert!(Transfer::try_from(erc20_log).is_ok());
+
+        store.insert_nft_transfers_batch(std::slice::from_ref(&nft_transfer)).await?;
+        // duplicates are ignored
+        store.insert_nft_transfer(&nft_transfer).await?;
+
+        let nft_transfers = store.get_nft_transfers_from_block_number(0).await?;
+        assert_eq!(nft_transfers, vec![nft_transfer.clone()]);
+
+        // per contract
+        let contract_address = Address::repeat_byte(0xAA);
+        let nft_transfers = store
+            .get_contract_nft_transfers_from_block_number(contract_address.as_slice(), 0)
+            .await?;
+        assert_eq!(nft_transfers, vec![nft_transfer]);
+        let nft_transfers =
+            store.get_contract_nft_transfers_from_block_number(Address::ZERO.as_slice(), 0).await?;
+        assert!(nft_transfers.is_empty());
+
+        let deleted = store.delete_nft_transfers_after_block_number(99, &[]).await?;
+        assert_eq!(deleted, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get_transfer_batches() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = TransferBatchStore::new(client.clone());
+
+        let uints = |values: &[u64]| {
+            DynSolValue::Array(
+                values.iter().map(|v| DynSolValue::Uint(U256::from(*v), 256)).collect(),
+            )
+        };
+        let data = DynSolValue::Tuple(vec![uints(&[1, 2, 3]), uints(&[10, 20, 30])]);
+        let topics = vec![
+            B256::repeat_byte(0xDD),
+            Address::repeat_byte(0xEE).into_word(),
+            Address::repeat_byte(0xBB).into_word(),
+            Address::repeat_byte(0xCC).into_word(),
+        ];
+        let transfer_batch =
+            TransferBatch::try_from(transfer_log(topics, data.abi_encode_params().into()))?;
+        assert_eq!(transfer_batch.token_ids.len(), 3);
+        assert_eq!(transfer_batch.amounts[2], U256::from(30).to_be_bytes::<32>().to_vec());
+
+        store.insert_transfer_batches_batch(std::slice::from_ref(&transfer_batch)).await?;
+        // duplicates are ignored
+        store.insert_transfer_batch(&transfer_batch).await?;
+
+        // expanded into one row per id/value pair
+        let count: i64 = sqlx::query_scalar(r#"SELECT COUNT(*) FROM transfer_batches"#)
+            .fetch_one(client.pool())
+            .await?;
+        assert_eq!(count, 3);
+
+        // grouped back into the batch transfer
+        let transfer_batches = store.get_transfer_batches_from_block_number(0).await?;
+        assert_eq!(transfer_batches, vec![transfer_batch]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get_transfer_singles() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = TransferSingleStore::new(client);
+
+        let transfer_single = |block_number: u64, token_id: u64| -> Result<TransferSingle> {
+            let data = DynSolValue::Tuple(vec![
+                DynSolValue::Uint(U256::from(token_id), 256),
+                DynSolValue::Uint(U256::from(10), 256),
+            ]);
+            let topics = vec![
+                B256::repeat_byte(0xDD),
+                Address::repeat_byte(0xEE).into_word(),
+                Address::repeat_byte(0xBB).into_word(),
+                Address::repeat_byte(0xCC).into_word(),
+            ];
+            let mut log = transfer_log(topics, data.abi_encode_params().into());
+            log.block_number = Some(block_number);
+            log.transaction_hash = Some(B256::repeat_byte(block_number as u8));
+            TransferSingle::try_from(log)
+        };
+        let transfer_single_1 = transfer_single(100, 1)?;
+        let transfer_single_2 = transfer_single(101, 2)?;
+        assert_eq!(transfer_single_2.token_id, U256::from(2).to_be_bytes::<32>().to_vec());
+
+        store
+            .insert_transfer_singles_batch(&[transfer_single_1.clone(), transfer_single_2.clone()])
+            .await?;
+        // duplicates are ignored
+        store.insert_transfer_single(&transfer_single_1).await?;
+
+        let transfer_singles = store.get_transfer_singles_from_block_number(0).await?;
+        assert_eq!(transfer_singles, vec![transfer_single_1.clone(), transfer_single_2.clone()]);
+        let transfer_singles = store.get_transfer_singles_between_block_numbers(101, 101).await?;
+        assert_eq!(transfer_singles, vec![transfer_single_2]);
+
+        // rolling back drops the transfers above the block
+        let deleted = store.delete_transfer_singles_after_block_number(100, &[]).await?;
+        assert_eq!(deleted, 1);
+        let transfer_singles = store.get_transfer_singles_from_block_number(0).await?;
+        assert_eq!(transfer_singles, vec![transfer_single_1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_allowances_follow_approvals() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = ApprovalStore::new(client);
+
+        let approval = |block_number: i64, amount: U256| Approval {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            transaction_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            log_index: 0,
+            contract_address: Address::repeat_byte(0xAA).to_vec(),
+            owner_address: Address::repeat_byte(0xBB).into_word().to_vec(),
+            spender_address: Address::repeat_byte(0xCC).into_word().to_vec(),
+            amount: amount.to_be_bytes::<32>().to_vec(),
+        };
+
+        // latest approval wins, regardless of the insertion order
+        store
+            .insert_approvals_batch(&[approval(101, U256::MAX), approval(100, U256::from(5))])
+            .await?;
+        let allowances = store.get_allowances_from_block_number(0).await?;
+        assert_eq!(allowances.len(), 1);
+        assert_eq!(allowances[0].block_number, 101);
+        assert_eq!(store.get_unlimited_allowances_from_block_number(0).await?.len(), 1);
+
+        // rolling back restores the previous allowance
+        let deleted = store.delete_approvals_after_block_number(100, &[]).await?;
+        assert_eq!(deleted, 1);
+        let allowances = store.get_allowances_from_block_number(0).await?;
+        assert_eq!(allowances.len(), 1);
+        assert_eq!(allowances[0].amount, U256::from(5).to_be_bytes::<32>().to_vec());
+        assert!(store.get_unlimited_allowances_from_block_number(0).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_balances_follow_transfers() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = BalanceStore::new(client.clone());
+        let checkpoint_store = CheckpointStore::new(client);
+
+        let contract = Address::repeat_byte(0xAA).to_vec();
+        let zero = Address::ZERO.into_word().to_vec();
+        let alice = Address::repeat_byte(0xBB).into_word().to_vec();
+        let bob = Address::repeat_byte(0xCC).into_word().to_vec();
+        let transfer = |block_number: i64, from: &[u8], to: &[u8], amount: u64| Transfer {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            transaction_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            log_index: 0,
+            contract_address: contract.clone(),
+            from_address: from.to_vec(),
+            to_address: to.to_vec(),
+            amount: U256::from(amount).to_be_bytes::<32>().to_vec(),
+            block_timestamp: None,
+            chain_id: None,
+        };
+        let balance_of = async |holder: &[u8]| -> Result<I256> {
+            let balance = store.get_balance(&contract, holder).await?.expect("balance");
+            Ok(I256::from_raw(U256::from_be_slice(&balance.balance)))
+        };
+
+        // mint to alice, then alice sends to bob, then bob burns
+        let transfers = [
+            transfer(100, &zero, &alice, 50),
+            transfer(101, &alice, &bob, 20),
+            transfer(102, &bob, &zero, 5),
+        ];
+        store.insert_transfers_batch(&transfers).await?;
+        // duplicates do not move balances twice
+        store.insert_transfer(&transfers[1]).await?;
+
+        assert_eq!(balance_of(&alice).await?, I256::try_from(30)?);
+        assert_eq!(balance_of(&bob).await?, I256::try_from(15)?);
+        assert!(store.get_balance(&contract, &zero).await?.is_none());
+
+        // historical balances are folded from the nearest snapshot up to the given block,
+        // only known up to the last checkpoint
+        for block_number in [100, 101] {
+            checkpoint_store
+                .insert_checkpoint(&Checkpoint {
+                    block_number,
+                    block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+                    parent_hash: B256::ZERO.to_vec(),
+                    chain_id: None,
+                })
+                .await?;
+        }
+        assert_eq!(store.snapshot_balances(100).await?, 1);
+        let at_block = store.get_balance_at_block_number(&contract, &bob, 101).await?;
+        assert_eq!(at_block.balance, U256::from(20).to_be_bytes::<32>().to_vec());
+        let at_block = store.get_balance_at_block_number(&contract, &alice, 101).await?;
+        assert_eq!(at_block.balance, U256::from(30).to_be_bytes::<32>().to_vec());
+        let top_holders = store.get_top_holders_at_block_number(&contract, 101, 1).await?;
+        assert_eq!(top_holders.len(), 1);
+        assert_eq!(top_holders[0].holder_address, alice);
+        assert!(store.get_balance_at_block_number(&contract, &bob, 102).await.is_err());
+        assert!(store.get_top_holders_at_block_number(&contract, 102, 1).await.is_err());
+
+        // snapshots only hold the holders moved since the previous one
+        assert_eq!(store.snapshot_balances(101).await?, 2);
+        let top_holders = store.get_top_holders_at_block_number(&contract, 101, 2).await?;
+        assert_eq!(top_holders.len(), 2);
+        assert_eq!(top_holders[1].holder_address, bob);
+
+        // rolling back reverts the orphaned transfers
+        let deleted = store.delete_transfers_after_block_number(100, &[]).await?;
+        assert_eq!(deleted, 2);
+        assert_eq!(balance_of(&alice).await?, I256::try_from(50)?);
+        assert_eq!(balance_of(&bob).await?, I256::ZERO);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transfers_per_chain() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let mainnet_store = TransferStore::new(client.clone()).on_chain(1);
+        let base_store = TransferStore::new(client).on_chain(8453);
+
+        let transfer = |block_number: i64| Transfer {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            transaction_hash: B256::repeat_byte(0xF0).to_vec(),
+            log_index: 0,
+            contract_address: Address::repeat_byte(0xAA).to_vec(),
+            from_address: Address::repeat_byte(0xBB).into_word().to_vec(),
+            to_address: Address::repeat_byte(0xCC).into_word().to_vec(),
+            amount: U256::from(1).to_be_bytes::<32>().to_vec(),
+            block_timestamp: None,
+            chain_id: None,
+        };
+
+        // Same transaction hash and log index on both chains
+        mainnet_store.insert_transfer(&transfer(100)).await?;
+        base_store.insert_transfer(&transfer(200)).await?;
+
+        let mainnet_transfers = mainnet_store.get_transfers_from_block_number(0).await?;
+        assert_eq!(mainnet_transfers.len(), 1);
+        assert_eq!(mainnet_transfers[0].chain_id, Some(1));
+        let base_transfers = base_store.get_transfers_from_block_number(0).await?;
+        assert_eq!(base_transfers.len(), 1);
+        assert_eq!(base_transfers[0].chain_id, Some(8453));
+
+        // Rolling back a chain leaves the others untouched
+        assert_eq!(mainnet_store.delete_transfers_after_block_number(50, &[]).await?, 1);
+        assert!(mainnet_store.get_transfers_from_block_number(0).await?.is_empty());
+        assert_eq!(base_store.get_transfers_from_block_number(0).await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rollbacks_per_job() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let usdc_store = BalanceStore::new(client.clone()).for_job("usdc");
+        let weth_store = BalanceStore::new(client.clone()).for_job("weth");
+
+        let transfer = |contract: u8, log_index: i64| Transfer {
+            block_number: 100,
+            block_hash: B256::repeat_byte(0x01).to_vec(),
+            transaction_hash: B256::repeat_byte(0xF0).to_vec(),
+            log_index,
+            contract_address: Address::repeat_byte(contract).to_vec(),
+            from_address: Address::ZERO.into_word().to_vec(),
+            to_address: Address::repeat_byte(0xCC).into_word().to_vec(),
+            amount: U256::from(1).to_be_bytes::<32>().to_vec(),
+            block_timestamp: None,
+            chain_id: None,
+        };
+        usdc_store.insert_transfer(&transfer(0xAA, 0)).await?;
+        weth_store.insert_transfer(&transfer(0xBB, 1)).await?;
+
+        // A job only rolls back the rows it stored, with their balances and supply changes
+        assert_eq!(usdc_store.delete_transfers_after_block_number(50, &[]).await?, 1);
+        let transfers =
+            TransferStore::new(client.clone()).get_transfers_from_block_number(0).await?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].contract_address, Address::repeat_byte(0xBB).to_vec());
+        let weth = Address::repeat_byte(0xBB).to_vec();
+        let one = U256::from(1).to_be_bytes::<32>().to_vec();
+        let weth_balances: Vec<_> = weth_store
+            .get_balances()
+            .await?
+            .into_iter()
+            .filter(|balance| balance.contract_address == weth && balance.balance == one)
+            .collect();
+        assert_eq!(weth_balances.len(), 1);
+        // Running balances are kept per job
+        let usdc_balances = usdc_store.get_balances().await?;
+        assert!(usdc_balances.iter().all(|balance| balance.contract_address != weth));
+        let supply_store = SupplyStore::new(client.clone());
+        assert_eq!(
+            supply_store.get_supply_changes_between_block_numbers(&weth, 0, 100).await?.len(),
+            1
+        );
+
+        // Block headers are recorded and rolled back per job too
+        let usdc_block_store = BlockStore::new(client.clone()).for_job("usdc");
+        let weth_block_store = BlockStore::new(client).for_job("weth");
+        let block = BlockHeader {
+            block_number: 100,
+            block_hash: B256::repeat_byte(0x01).to_vec(),
+            parent_hash: B256::repeat_byte(0x00).to_vec(),
+            timestamp: 1_700_000_000,
+        };
+        usdc_block_store.insert_block(&block).await?;
+        weth_block_store.insert_block(&block).await?;
+        assert_eq!(usdc_block_store.delete_blocks_after_block_number(50).await?, 1);
+        assert_eq!(usdc_block_store.get_block_by_number(100).await?, None);
+        assert_eq!(weth_block_store.get_block_by_number(100).await?, Some(block));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_baseline_schema() -> Result<()> {
+        let db_path =
+            std::env::temp_dir().join(format!("store-migration-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+
+        // Tables as created before chain ids and jobs were recorded
+        let baseline = Client::new(&db_url).await?;
+        sqlx::query(
+            r#"CREATE TABLE transfers (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                block_number INTEGER NOT NULL,
+                block_hash BLOB NOT NULL,
+                transaction_hash BLOB NOT NULL,
+                log_index INTEGER NOT NULL,
+                contract_address BLOB NOT NULL,
+                from_address BLOB NOT NULL,
+                to_address BLOB NOT NULL,
+                amount BLOB NOT NULL,
+                UNIQUE(transaction_hash, log_index)
+            );
+            CREATE INDEX idx_transfers_block_number ON transfers (block_number);
+            CREATE TABLE checkpoints (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                block_number INTEGER NOT NULL,
+                block_hash BLOB NOT NULL,
+                parent_hash BLOB NOT NULL,
+                UNIQUE(block_number, block_hash)
+            );
+            CREATE TABLE tokens (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                contract_address BLOB NOT NULL,
+                name TEXT,
+                symbol TEXT,
+                decimals INTEGER,
+                UNIQUE(contract_address)
+            );
+            CREATE TABLE "event_transfer" (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                block_number INTEGER NOT NULL,
+                block_hash BLOB NOT NULL,
+                transaction_hash BLOB NOT NULL,
+                log_index INTEGER NOT NULL,
+                contract_address BLOB NOT NULL,
+                "from" BLOB NOT NULL,
+                "to" BLOB NOT NULL,
+                "value" BLOB NOT NULL,
+                UNIQUE(transaction_hash, log_index)
+            );
+            INSERT INTO tokens (contract_address, symbol) VALUES (x'AA', 'ZAMA');
+            INSERT INTO "event_transfer" (block_number, block_hash, transaction_hash,
+                log_index, contract_address, "from", "to", "value")
+                VALUES (100, x'01', x'F0', 0, x'AA', x'BB', x'CC', x'01');
+            INSERT INTO transfers (block_number, block_hash, transaction_hash, log_index,
+                contract_address, from_address, to_address, amount)
+                VALUES (100, x'01', x'F0', 0, x'AA', x'BB', x'CC', x'01');
+            INSERT INTO checkpoints (block_number, block_hash, parent_hash)
+                VALUES (100, x'01', x'00');"#,
+        )
+        .execute(baseline.pool())
+        .await?;
+        baseline.pool().close().await;
+
+        let client = Client::init(&db_url).await?;
+
+        // Existing rows belong to the default chain and job
+        let mainnet_store = TransferStore::new(client.clone()).on_chain(1);
+        assert_eq!(mainnet_store.get_transfers_from_block_number(0).await?.len(), 1);
+        let checkpoint_store = CheckpointStore::new(client.clone());
+        assert_eq!(
+            checkpoint_store.get_last_checkpoint().await?.map(|c| c.block_number),
+            Some(100)
+        );
+        assert_eq!(TokenStore::new(client.clone()).get_tokens().await?.len(), 1);
+        let event = Arc::new(Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )?);
+        let event_log_store = EventLogStore::init(client.clone(), vec![event]).await?;
+        assert_eq!(event_log_store.delete_event_logs_after_block_number(0, &[]).await?, 1);
+
+        // Unique keys now include the chain id
+        let transfer = Transfer {
+            block_number: 200,
+            block_hash: vec![0x02],
+            transaction_hash: vec![0xF0],
+            log_index: 0,
+            contract_address: vec![0xAA],
+            from_address: vec![0xBB],
+            to_address: vec![0xCC],
+            amount: vec![0x01],
+            block_timestamp: None,
+            chain_id: None,
+        };
+        let base_store = TransferStore::new(client.clone()).on_chain(8453);
+        base_store.insert_transfer(&transfer).await?;
+        assert_eq!(base_store.get_transfers_from_block_number(0).await?.len(), 1);
+
+        // Migrating again leaves the database as is
+        client.pool().close().await;
+        let client = Client::init(&db_url).await?;
+        assert_eq!(
+            TransferStore::new(client.clone()).get_transfers_from_block_number(0).await?.len(),
+            1
+        );
+        client.pool().close().await;
+
+        std::fs::remove_file(&db_path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_supply_changes_follow_mints_and_burns() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let transfer_store = TransferStore::new(client.clone());
+        let supply_store = SupplyStore::new(client);
+
+        let contract = Address::repeat_byte(0xAA).to_vec();
+        let zero = Address::ZERO.into_word().to_vec();
+        let alice = Address::repeat_byte(0xBB).into_word().to_vec();
+        let transfer = |block_number: i64, from: &[u8], to: &[u8], amount: u64| Transfer {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            transaction_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            log_index: 0,
+            contract_address: contract.clone(),
+            from_address: from.to_vec(),
+            to_address: to.to_vec(),
+            amount: U256::from(amount).to_be_bytes::<32>().to_vec(),
+            block_timestamp: None,
+            chain_id: None,
+        };
+        let word =
+            |value: i64| I256::try_from(value).unwrap().into_raw().to_be_bytes::<32>().to_vec();
+
+        // mint, plain transfer (no supply change), burn
+        transfer_store
+            .insert_transfers_batch(&[
+                transfer(100, &zero, &alice, 50),
+                transfer(101, &alice, &alice, 20),
+                transfer(102, &alice, &zero, 5),
+            ])
+            .await?;
+
+        let supply_changes =
+            supply_store.get_supply_changes_between_block_numbers(&contract, 101, 200).await?;
+        assert_eq!(supply_changes.len(), 1);
+        assert_eq!(supply_changes[0].amount, word(-5));
+        assert_eq!(supply_changes[0].total_supply, word(45));
+
+        // rolled back with their transfers
+        transfer_store.delete_transfers_after_block_number(100, &[]).await?;
+        let supply_changes =
+            supply_store.get_supply_changes_between_block_numbers(&contract, 0, 200).await?;
+        assert_eq!(supply_changes.len(), 1);
+        assert_eq!(supply_changes[0].total_supply, word(50));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get_tokens() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = TokenStore::new(client.clone());
+
+        let token = Token {
+            contract_address: Address::repeat_byte(0xAA).to_vec(),
+            name: Some("Zama".to_string()),
+            symbol: Some("ZAMA".to_string()),
+            decimals: Some(18),
+        };
+        store.insert_token(&token).await?;
+        // metadata is fetched once, later inserts are ignored
+        store.insert_token(&Token { name: None, ..token.clone() }).await?;
+
+        assert_eq!(store.get_token(&token.contract_address).await?, Some(token.clone()));
+        assert_eq!(store.get_tokens().await?, vec![token.clone()]);
+
+        // the same address holds another token on another chain
+        let base_store = TokenStore::new(client).on_chain(8453);
+        assert_eq!(base_store.get_token(&token.contract_address).await?, None);
+        let base_token = Token { symbol: Some("BZAMA".to_string()), ..token.clone() };
+        base_store.insert_token(&base_token).await?;
+        assert_eq!(base_store.get_tokens().await?, vec![base_token]);
+        assert_eq!(store.get_tokens().await?, vec![token]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transfers_block_timestamp() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let block_store = BlockStore::new(client.clone());
+        let transfer_store = TransferStore::new(client);
+
+        let transfers: Vec<Transfer> = (0..2)
+            .map(|i| Transfer {
+                block_number: 100 + i,
+                block_hash: B256::repeat_byte(i as u8).to_vec(),
+                transaction_hash: B256::repeat_byte(0xF0 + i as u8).to_vec(),
+                log_index: 0,
+                contract_address: Address::repeat_byte(0xAA).to_vec(),
+                from_address: Address::repeat_byte(0xBB).into_word().to_vec(),
+                to_address: Address::repeat_byte(0xCC).into_word().to_vec(),
+                amount: U256::from(1).to_be_bytes::<32>().to_vec(),
+                block_timestamp: None,
+                chain_id: None,
+            })
+            .collect();
+        transfer_store.insert_transfers_batch(&transfers).await?;
+
+        // only the first block header is recorded
+        let block = BlockHeader {
+            block_number: 100,
+            block_hash: B256::repeat_byte(0).to_vec(),
+            parent_hash: B256::repeat_byte(0xFF).to_vec(),
+            timestamp: 1_700_000_000,
+        };
+        block_store.insert_block(&block).await?;
+        assert_eq!(block_store.get_block_by_number(100).await?, Some(block));
+
+        let transfers = transfer_store.get_transfers_from_block_number(0).await?;
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].block_timestamp, Some(1_700_000_000));
+        assert_eq!(transfers[1].block_timestamp, None);
+
+        let transfers =
+            transfer_store.get_transfers_between_timestamps(1_700_000_000, 1_800_000_000).await?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].block_number, 100);
+
+        let deleted = block_store.delete_blocks_after_block_number(99).await?;
+        assert_eq!(deleted, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_delete_transactions() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = TransactionStore::new(client);
+
+        let transaction = |block_number: i64, tx_to: Option<Vec<u8>>| Transaction {
+            block_number,
+            block_hash: B256::repeat_byte(block_number as u8).to_vec(),
+            transaction_hash: B256::repeat_byte(0xF0 + block_number as u8).to_vec(),
+            tx_from: Address::repeat_byte(0xAA).to_vec(),
+            tx_to,
+            gas_used: 21_000,
+            effective_gas_price: U256::from(1_000_000_000u64).to_be_bytes::<32>().to_vec(),
+            status: true,
+        };
+        let transactions =
+            vec![transaction(1, Some(Address::repeat_byte(0xBB).to_vec())), transaction(2, None)];
+        store.insert_transactions_batch(&transactions).await?;
+        // Duplicated transactions are ignored
+        store.insert_transactions_batch(&transactions).await?;
+
+        assert_eq!(store.get_transactions_from_block_number(0).await?, transactions);
+        assert_eq!(
+            store.get_transaction(&transactions[1].transaction_hash).await?,
+            Some(transactions[1].clone())
+        );
+
+        assert_eq!(store.delete_transactions_after_block_number(1).await?, 1);
+        assert_eq!(store.get_transactions_from_block_number(0).await?, transactions[..1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raw_logs_replay_into_transfers() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = RawLogStore::new(client);
+
+        let signature = B256::repeat_byte(0xDD);
+        let from = Address::repeat_byte(0xBB).into_word();
+        let to = Address::repeat_byte(0xCC).into_word();
+        let amount = Bytes::from(U256::from(7).to_be_bytes::<32>().to_vec());
+        let log = transfer_log(vec![signature, from, to], amount);
+
+        let raw_log = RawLog::try_from(&log)?;
+        store.insert_raw_logs_batch(&[raw_log.clone(), raw_log.clone()]).await?;
+        assert_eq!(store.get_last_raw_log_block_number(&[]).await?, Some(100));
+
+        // Filtered by contract address and first topic
+        let contract_addresses = vec![Address::repeat_byte(0xAA).to_vec()];
+        let raw_logs = store
+            .get_raw_logs_between_block_numbers(&contract_addresses, &[signature.to_vec()], 0, 100)
+            .await?;
+        assert_eq!(raw_logs, vec![raw_log]);
+        let raw_logs =
+            store.get_raw_logs_between_block_numbers(&[], &[B256::ZERO.to_vec()], 0, 100).await?;
+        assert!(raw_logs.is_empty());
+
+        // The rebuilt log decodes like the original one
+        let transfers = store
+            .get_raw_logs_between_block_numbers(&[], &[], 0, 100)
+            .await?
+            .iter()
+            .map(|raw_log| Transfer::try_from(Log::from(raw_log)))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(transfers, vec![Transfer::try_from(log)?]);
+
+        assert_eq!(store.delete_raw_logs_after_block_number(99, &[]).await?, 1);
+        assert_eq!(store.get_last_raw_log_block_number(&[]).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get_discovered_contracts() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = ContractStore::new(client);
+
+        let contract = |contract_address: u8, factory_address: u8, block_number: i64| Contract {
+            contract_address: Address::repeat_byte(contract_address).to_vec(),
+            factory_address: Address::repeat_byte(factory_address).to_vec(),
+            block_number,
+            transaction_hash: B256::repeat_byte(contract_address).to_vec(),
+            log_index: 0,
+        };
+        let contracts = vec![contract(0x01, 0xFA, 10), contract(0x02, 0xFB, 11)];
+        store.insert_contracts_batch(&contracts).await?;
+        // Contracts are created only once
+        store.insert_contracts_batch(&[contract(0x01, 0xFA, 12)]).await?;
+
+        assert_eq!(store.get_contracts().await?, contracts);
+        let factory_address = Address::repeat_byte(0xFB);
+        assert_eq!(
+            store.get_contracts_by_factory(factory_address.as_slice()).await?,
+            contracts[1..]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watched_addresses_and_backfill_checkpoints() -> Result<()> {
+        let db_url = "sqlite::memory:";
+        let client = Client::init(db_url).await?;
+        let store = WatchedAddressStore::new(client.clone()).for_job("usdc");
+
+        let added = Address::repeat_byte(0xAA);
+        let removed = Address::repeat_byte(0xBB);
+        store.insert_watched_address(added.as_slice(), Some((10, 20))).await?;
+        store.insert_watched_address(removed.as_slice(), None).await?;
+        store.remove_watched_address(removed.as_slice()).await?;
+
+        // The backfill resumes from its checkpoint, until it reaches the block the address
+        // was added at
+        store.update_backfill_from_block(added.as_slice(), 15).await?;
+        let watched_addresses = store.get_watched_addresses().await?;
+        assert_eq!(watched_addresses.len(), 2);
+        assert_eq!(watched_addresses[0].pending_backfill(), Some((15, 20)));
+        assert!(!watched_addresses[1].watched);
+        assert_eq!(watched_addresses[1].pending_backfill(), None);
+        store.update_backfill_from_block(added.as_slice(), 21).await?;
+        assert_eq!(store.get_watched_addresses().await?[0].pending_backfill(), None);
+
+        // Each job has its own watch list
+        assert!(WatchedAddressStore::new(client).get_watched_addresses().await?.is_empty());
+
+        Ok(())
+    }
 }